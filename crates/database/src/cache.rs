@@ -0,0 +1,204 @@
+//! Caches serialized graphs, so reloading an unchanged graph doesn't query the store again.
+
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+};
+
+use grapher::prelude::GraphDisplayData;
+use vowlr_util::{
+    flags::GraphFlags,
+    limits::{QueryLimits, QueryOutcome},
+    options::SerializerOptions,
+};
+
+/// A serialized graph, its flags and how its query completed.
+pub type CachedGraph = (GraphDisplayData, GraphFlags, QueryOutcome);
+
+/// Identifies a serialized graph.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    /// The graphs the query was evaluated against, e.g. the default and the inferred graph.
+    pub graphs: Vec<String>,
+    /// The query drawing the graph.
+    pub query: String,
+    /// The options the graph was serialized with.
+    pub options: SerializerOptions,
+    /// The limits the query was evaluated with.
+    pub limits: QueryLimits,
+    /// The generation of the store the graph was serialized from.
+    pub generation: u64,
+}
+
+impl CacheKey {
+    pub fn new(
+        graphs: Vec<String>,
+        query: &str,
+        options: &SerializerOptions,
+        limits: &QueryLimits,
+        generation: u64,
+    ) -> Self {
+        Self {
+            graphs,
+            query: query.to_string(),
+            options: options.clone(),
+            limits: *limits,
+            generation,
+        }
+    }
+}
+
+/// How well the cache performs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheMetrics {
+    pub hits: u64,
+    pub misses: u64,
+    /// The number of cached graphs.
+    pub entries: usize,
+}
+
+impl Display for CacheMetrics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let lookups = self.hits + self.misses;
+        write!(
+            f,
+            "{} hits, {} misses ({:.0}% hit rate), {} cached graphs",
+            self.hits,
+            self.misses,
+            if lookups == 0 {
+                0.0
+            } else {
+                100.0 * self.hits as f64 / lookups as f64
+            },
+            self.entries
+        )
+    }
+}
+
+/// A least recently used cache of serialized graphs.
+pub struct GraphCache {
+    capacity: usize,
+    /// Maps each key to its graph and the tick it was last used at.
+    entries: HashMap<CacheKey, (u64, CachedGraph)>,
+    /// Increments on every lookup and insertion, ordering the uses of entries.
+    tick: u64,
+    metrics: CacheMetrics,
+}
+
+impl GraphCache {
+    /// The number of graphs cached unless configured otherwise.
+    ///
+    /// Graphs of large ontologies take a lot of memory, so only a few are kept.
+    pub const DEFAULT_CAPACITY: usize = 8;
+
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            tick: 0,
+            metrics: CacheMetrics::default(),
+        }
+    }
+
+    /// Returns the graph cached for `key`, if any, and marks it as recently used.
+    pub fn get(&mut self, key: &CacheKey) -> Option<CachedGraph> {
+        self.tick += 1;
+        match self.entries.get_mut(key) {
+            Some((last_used, graph)) => {
+                *last_used = self.tick;
+                self.metrics.hits += 1;
+                Some(graph.clone())
+            }
+            None => {
+                self.metrics.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Caches `graph` for `key`, evicting the least recently used graph if the cache is full.
+    pub fn insert(&mut self, key: CacheKey, graph: CachedGraph) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.tick += 1;
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            let least_recently_used = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (last_used, _))| *last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(key) = least_recently_used {
+                self.entries.remove(&key);
+            }
+        }
+        self.entries.insert(key, (self.tick, graph));
+    }
+
+    /// Removes the graphs serialized before `generation`, as they no longer match the store.
+    pub fn retain_generation(&mut self, generation: u64) {
+        self.entries.retain(|key, _| key.generation >= generation);
+    }
+
+    pub fn metrics(&self) -> CacheMetrics {
+        CacheMetrics {
+            entries: self.entries.len(),
+            ..self.metrics
+        }
+    }
+}
+
+impl Default for GraphCache {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_graph_cache() {
+        let (options, limits) = (SerializerOptions::default(), QueryLimits::default());
+        let key = |query: &str, generation: u64| {
+            CacheKey::new(vec![String::new()], query, &options, &limits, generation)
+        };
+        let graph = || {
+            (
+                GraphDisplayData::new(),
+                GraphFlags::default(),
+                QueryOutcome::default(),
+            )
+        };
+        let mut cache = GraphCache::new(2);
+
+        assert!(cache.get(&key("A", 0)).is_none());
+        cache.insert(key("A", 0), graph());
+        cache.insert(key("B", 0), graph());
+        assert!(cache.get(&key("A", 0)).is_some());
+        // B is the least recently used graph.
+        cache.insert(key("C", 0), graph());
+        assert!(cache.get(&key("B", 0)).is_none());
+        assert!(cache.get(&key("C", 0)).is_some());
+        // Graphs of older generations are stale.
+        assert!(cache.get(&key("A", 1)).is_none());
+        // Graphs serialized with other options are different graphs.
+        let inferred = SerializerOptions {
+            inference: true,
+            ..options.clone()
+        };
+        let inferred_key = CacheKey::new(vec![String::new()], "A", &inferred, &limits, 0);
+        assert!(cache.get(&inferred_key).is_none());
+        cache.retain_generation(1);
+
+        assert_eq!(
+            cache.metrics(),
+            CacheMetrics {
+                hits: 2,
+                misses: 4,
+                entries: 0,
+            }
+        );
+    }
+}
@@ -0,0 +1,284 @@
+//! Checks the loaded ontologies for problems which don't need a full reasoner to be found.
+//!
+//! The check only considers asserted subsumption, equivalence and disjointness,
+//! so it may miss problems, but the problems it finds are real.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::{
+    inference::{Closure, Fact, closure, instances, pairs, property_classes},
+    vocab::owl,
+};
+use rdf_fusion::model::{
+    Term,
+    vocab::{rdf, rdfs},
+};
+use vowlr_util::diagnostics::{Diagnostic, DiagnosticKind};
+
+/// Returns the members of the RDF list starting at `head`.
+fn list_members<'a>(
+    head: &'a Term,
+    firsts: &HashMap<&'a Term, &'a Term>,
+    rests: &HashMap<&'a Term, &'a Term>,
+) -> Vec<&'a Term> {
+    let mut members = Vec::new();
+    let mut visited = HashSet::new();
+    let mut cell = head;
+    while visited.insert(cell) {
+        let Some(first) = firsts.get(cell) else {
+            break;
+        };
+        members.push(*first);
+        let Some(rest) = rests.get(cell) else {
+            break;
+        };
+        cell = rest;
+    }
+    members
+}
+
+/// Returns the pairs of classes declared disjoint, in both orders.
+///
+/// Classes are disjoint through `owl:disjointWith`, `owl:AllDisjointClasses`
+/// and the members of `owl:disjointUnionOf`.
+fn disjoint_classes(facts: &HashSet<Fact>) -> HashSet<(&Term, &Term)> {
+    let firsts = pairs(facts, rdf::FIRST).collect::<HashMap<_, _>>();
+    let rests = pairs(facts, rdf::REST).collect::<HashMap<_, _>>();
+    let groups = instances(facts, owl::ALL_DISJOINT_CLASSES);
+    let lists = pairs(facts, owl::MEMBERS)
+        .filter(|(group, _)| groups.contains(group))
+        .chain(pairs(facts, owl::DISJOINT_UNION_OF))
+        .map(|(_, head)| list_members(head, &firsts, &rests));
+
+    let mut disjoint = HashSet::new();
+    for (a, b) in pairs(facts, owl::DISJOINT_WITH) {
+        disjoint.insert((a, b));
+        disjoint.insert((b, a));
+    }
+    for members in lists {
+        for a in members.iter() {
+            for b in members.iter().filter(|b| *b != a) {
+                disjoint.insert((*a, *b));
+            }
+        }
+    }
+    disjoint
+}
+
+/// Finds the problems of the classes and properties in `facts`.
+struct Checker<'a> {
+    /// The super classes of each class, through subsumption and equivalence.
+    supers: Closure,
+    disjoint: HashSet<(&'a Term, &'a Term)>,
+    nothing: Term,
+}
+
+impl Checker<'_> {
+    /// Returns why no individual can be an instance of all `classes`, if that's the case.
+    fn conflict<'t>(&self, classes: impl IntoIterator<Item = &'t Term>) -> Option<String> {
+        let mut ancestors = classes
+            .into_iter()
+            .flat_map(|class| {
+                std::iter::once(class).chain(self.supers.get(class).into_iter().flatten())
+            })
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        ancestors.sort_by_key(|class| class.to_string());
+        if ancestors.contains(&&self.nothing) {
+            return Some("owl:Nothing".to_string());
+        }
+        ancestors.iter().find_map(|a| {
+            ancestors
+                .iter()
+                .find(|b| self.disjoint.contains(&(*a, **b)))
+                .map(|b| format!("the disjoint classes {a} and {b}"))
+        })
+    }
+}
+
+/// Returns the problems found in `facts`, sorted by their message.
+pub fn check(facts: &HashSet<Fact>) -> Vec<Diagnostic> {
+    let asserted = closure(pairs(facts, rdfs::SUB_CLASS_OF));
+    let checker = Checker {
+        supers: closure(
+            pairs(facts, rdfs::SUB_CLASS_OF)
+                .chain(pairs(facts, owl::EQUIVALENT_CLASS).flat_map(|(s, o)| [(s, o), (o, s)])),
+        ),
+        disjoint: disjoint_classes(facts),
+        nothing: Term::NamedNode(owl::NOTHING.into_owned()),
+    };
+    let mut diagnostics = Vec::new();
+
+    for class in checker.supers.keys() {
+        if *class == checker.nothing {
+            continue;
+        }
+        if let Some(conflict) = checker.conflict([class]) {
+            diagnostics.push(Diagnostic {
+                kind: DiagnosticKind::UnsatisfiableClass,
+                elements: vec![class.to_string()],
+                message: format!(
+                    "Class {class} is unsatisfiable, as it is a subclass of {conflict}"
+                ),
+            });
+        }
+    }
+
+    let mut cycles = HashSet::new();
+    for (class, reachable) in asserted.iter() {
+        let mut members = reachable
+            .iter()
+            .filter(|other| {
+                asserted
+                    .get(*other)
+                    .is_some_and(|reachable| reachable.contains(class))
+            })
+            .map(|member| member.to_string())
+            .collect::<BTreeSet<_>>();
+        if members.is_empty() {
+            continue;
+        }
+        members.insert(class.to_string());
+        if cycles.insert(members.clone()) {
+            let members = members.into_iter().collect::<Vec<_>>();
+            diagnostics.push(Diagnostic {
+                kind: DiagnosticKind::SubclassCycle,
+                message: format!("Classes {} form a cycle of subclasses", members.join(", ")),
+                elements: members,
+            });
+        }
+    }
+
+    let domains = property_classes(facts, rdfs::DOMAIN);
+    let ranges = property_classes(facts, rdfs::RANGE);
+    let symmetric = instances(facts, owl::SYMMETRIC_PROPERTY);
+    let properties = domains.keys().chain(ranges.keys()).collect::<HashSet<_>>();
+    for property in properties {
+        let domain = domains.get(*property).into_iter().flatten().copied();
+        let range = ranges.get(*property).into_iter().flatten().copied();
+        // Symmetric properties relate their range to their domain as well.
+        let conflict = if symmetric.contains(*property) {
+            checker
+                .conflict(domain.chain(range))
+                .map(|conflict| format!("its domain and range include {conflict}"))
+        } else {
+            checker
+                .conflict(domain)
+                .map(|conflict| format!("its domain includes {conflict}"))
+                .or_else(|| {
+                    checker
+                        .conflict(range)
+                        .map(|conflict| format!("its range includes {conflict}"))
+                })
+        };
+        if let Some(conflict) = conflict {
+            diagnostics.push(Diagnostic {
+                kind: DiagnosticKind::DisjointDomainOrRange,
+                elements: vec![property.to_string()],
+                message: format!("Property {property} can't relate any individuals, as {conflict}"),
+            });
+        }
+    }
+
+    diagnostics.sort_by(|a, b| a.message.cmp(&b.message));
+    diagnostics
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rdf_fusion::model::{NamedNode, NamedNodeRef};
+
+    fn named(iri: &str) -> Term {
+        Term::NamedNode(NamedNode::new(iri).unwrap())
+    }
+
+    fn fact(subject: &str, predicate: NamedNodeRef<'_>, object: &str) -> Fact {
+        (named(subject), predicate.into_owned(), named(object))
+    }
+
+    #[test]
+    fn test_check() {
+        let facts = HashSet::from([
+            fact(
+                "http://example.com#Cat",
+                owl::DISJOINT_WITH,
+                "http://example.com#Dog",
+            ),
+            fact(
+                "http://example.com#CatDog",
+                rdfs::SUB_CLASS_OF,
+                "http://example.com#Cat",
+            ),
+            fact(
+                "http://example.com#CatDog",
+                owl::EQUIVALENT_CLASS,
+                "http://example.com#DogCat",
+            ),
+            fact(
+                "http://example.com#DogCat",
+                rdfs::SUB_CLASS_OF,
+                "http://example.com#Dog",
+            ),
+            fact(
+                "http://example.com#Chicken",
+                rdfs::SUB_CLASS_OF,
+                "http://example.com#Egg",
+            ),
+            fact(
+                "http://example.com#Egg",
+                rdfs::SUB_CLASS_OF,
+                "http://example.com#Chicken",
+            ),
+            fact(
+                "http://example.com#chases",
+                rdfs::DOMAIN,
+                "http://example.com#Dog",
+            ),
+            fact(
+                "http://example.com#chases",
+                rdfs::RANGE,
+                "http://example.com#Cat",
+            ),
+            fact(
+                "http://example.com#barksAt",
+                rdfs::DOMAIN,
+                "http://example.com#Dog",
+            ),
+            fact(
+                "http://example.com#barksAt",
+                rdfs::DOMAIN,
+                "http://example.com#Cat",
+            ),
+        ]);
+        let diagnostics = check(&facts);
+        let affected = |kind: DiagnosticKind| {
+            diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.kind == kind)
+                .flat_map(|diagnostic| diagnostic.elements.clone())
+                .collect::<BTreeSet<_>>()
+        };
+
+        assert_eq!(
+            affected(DiagnosticKind::UnsatisfiableClass),
+            BTreeSet::from([
+                "<http://example.com#CatDog>".to_string(),
+                "<http://example.com#DogCat>".to_string(),
+            ])
+        );
+        assert_eq!(
+            affected(DiagnosticKind::SubclassCycle),
+            BTreeSet::from([
+                "<http://example.com#Chicken>".to_string(),
+                "<http://example.com#Egg>".to_string(),
+            ])
+        );
+        // A domain disjoint from the range is fine, unlike disjoint domains.
+        assert_eq!(
+            affected(DiagnosticKind::DisjointDomainOrRange),
+            BTreeSet::from(["<http://example.com#barksAt>".to_string()])
+        );
+    }
+}
@@ -0,0 +1,501 @@
+//! Materializes the consequences of [RDFS](https://www.w3.org/TR/rdf11-mt/#rdfs-entailment)
+//! and [OWL 2 RL](https://www.w3.org/TR/owl2-profiles/#Reasoning_in_OWL_2_RL_and_RDF_Graphs_using_Rules) rules.
+//!
+//! Only the rules whose consequences can be visualized are applied:
+//! - Subsumption through equivalent classes and properties (`scm-sco`, `scm-eqc1`, `scm-spo`, `scm-eqp1`).
+//! - Domains and ranges inherited from super, inverse and symmetric properties (`scm-dom2`, `scm-rng2`).
+//! - Types of individuals (`cax-sco`, `prp-dom`, `prp-rng`).
+//! - Property assertions (`prp-spo1`, `prp-symp`, `prp-trp`, `prp-inv1`, `prp-inv2`, `eq-sym`, `eq-trans`).
+
+use std::collections::{HashMap, HashSet};
+
+use crate::vocab::owl;
+use rdf_fusion::model::{
+    NamedNode, NamedNodeRef, Term,
+    vocab::{rdf, rdfs},
+};
+
+/// A triple, with its subject as a term so triples can be joined on either end.
+pub type Fact = (Term, NamedNode, Term);
+
+/// The classes or properties reachable from each class or property.
+pub type Closure = HashMap<Term, HashSet<Term>>;
+
+/// Computes the transitive closure of `edges`, excluding each element itself.
+pub fn closure<'a>(edges: impl IntoIterator<Item = (&'a Term, &'a Term)>) -> Closure {
+    let mut direct: Closure = HashMap::new();
+    for (from, to) in edges {
+        if from != to {
+            direct.entry(from.clone()).or_default().insert(to.clone());
+        }
+    }
+    let mut closure = Closure::new();
+    for start in direct.keys() {
+        let mut reachable = HashSet::new();
+        let mut pending = vec![start];
+        while let Some(element) = pending.pop() {
+            for next in direct.get(element).into_iter().flatten() {
+                if next != start && reachable.insert(next.clone()) {
+                    pending.push(next);
+                }
+            }
+        }
+        closure.insert(start.clone(), reachable);
+    }
+    closure
+}
+
+/// Returns the subjects and objects of the facts with `predicate`.
+pub fn pairs<'a>(
+    facts: &'a HashSet<Fact>,
+    predicate: NamedNodeRef<'a>,
+) -> impl Iterator<Item = (&'a Term, &'a Term)> {
+    facts
+        .iter()
+        .filter(move |(_, p, _)| p.as_ref() == predicate)
+        .map(|(s, _, o)| (s, o))
+}
+
+/// Returns the subjects typed with `class`.
+pub fn instances<'a>(facts: &'a HashSet<Fact>, class: NamedNodeRef<'a>) -> HashSet<&'a Term> {
+    pairs(facts, rdf::TYPE)
+        .filter(|(_, o)| *o == &Term::NamedNode(class.into_owned()))
+        .map(|(s, _)| s)
+        .collect()
+}
+
+/// The subsumption of `predicate` between classes or properties,
+/// where elements are subsumed by their equivalents.
+fn subsumption(
+    facts: &HashSet<Fact>,
+    predicate: NamedNodeRef<'_>,
+    equivalence: NamedNodeRef<'_>,
+) -> (Closure, Closure) {
+    let asserted = closure(pairs(facts, predicate));
+    let equivalent = closure(
+        pairs(facts, predicate)
+            .chain(pairs(facts, equivalence).flat_map(|(s, o)| [(s, o), (o, s)])),
+    );
+    (asserted, equivalent)
+}
+
+/// Infers the subsumptions that don't follow from the asserted ones alone.
+///
+/// Subsumptions following from transitivity alone are left out,
+/// as they would only clutter the visualization.
+/// Equivalent elements don't subsume each other either, as they are drawn as one.
+fn infer_subsumptions(
+    predicate: NamedNodeRef<'_>,
+    asserted: &Closure,
+    equivalent: &Closure,
+    inferred: &mut HashSet<Fact>,
+) {
+    for (element, supers) in equivalent {
+        for super_element in supers {
+            let is_equivalent = equivalent
+                .get(super_element)
+                .is_some_and(|supers| supers.contains(element));
+            let is_asserted = asserted
+                .get(element)
+                .is_some_and(|supers| supers.contains(super_element));
+            if !is_equivalent && !is_asserted {
+                inferred.insert((
+                    element.clone(),
+                    predicate.into_owned(),
+                    super_element.clone(),
+                ));
+            }
+        }
+    }
+}
+
+/// Returns the domains or ranges of each property, depending on `predicate`.
+pub fn property_classes<'a>(
+    facts: &'a HashSet<Fact>,
+    predicate: NamedNodeRef<'a>,
+) -> HashMap<&'a Term, Vec<&'a Term>> {
+    let mut classes: HashMap<&Term, Vec<&Term>> = HashMap::new();
+    for (property, class) in pairs(facts, predicate) {
+        classes.entry(property).or_default().push(class);
+    }
+    classes
+}
+
+/// Infers the domains or ranges of properties which don't declare any.
+///
+/// `inherited` maps each property to the properties it inherits from,
+/// whose `predicate` values are used, while `mirrored` maps each property to the properties
+/// whose values of the opposite predicate apply, e.g. the range of an inverse as domain.
+fn infer_property_classes(
+    facts: &HashSet<Fact>,
+    predicate: NamedNodeRef<'_>,
+    opposite: NamedNodeRef<'_>,
+    inherited: &Closure,
+    mirrored: &Closure,
+    inferred: &mut HashSet<Fact>,
+) {
+    let own = property_classes(facts, predicate);
+    let opposite = property_classes(facts, opposite);
+    let properties = inherited
+        .keys()
+        .chain(mirrored.keys())
+        .collect::<HashSet<_>>();
+    for property in properties {
+        if own.contains_key(property) {
+            continue;
+        }
+        let from_supers = inherited
+            .get(property)
+            .into_iter()
+            .flatten()
+            .filter_map(|super_property| own.get(super_property));
+        let from_mirrors = mirrored
+            .get(property)
+            .into_iter()
+            .flatten()
+            .filter_map(|mirror| opposite.get(mirror));
+        for class in from_supers.chain(from_mirrors).flatten() {
+            inferred.insert((property.clone(), predicate.into_owned(), (*class).clone()));
+        }
+    }
+}
+
+/// The facts of each predicate, indexed by subject and by object,
+/// so new facts can be joined with all facts without scanning them.
+#[derive(Default)]
+struct FactIndex {
+    /// Maps each predicate and subject to the objects of their facts.
+    objects: HashMap<(NamedNode, Term), HashSet<Term>>,
+    /// Maps each predicate and object to the subjects of their facts.
+    subjects: HashMap<(NamedNode, Term), HashSet<Term>>,
+}
+
+impl FactIndex {
+    fn insert(&mut self, (subject, predicate, object): &Fact) {
+        self.objects
+            .entry((predicate.clone(), subject.clone()))
+            .or_default()
+            .insert(object.clone());
+        self.subjects
+            .entry((predicate.clone(), object.clone()))
+            .or_default()
+            .insert(subject.clone());
+    }
+
+    fn objects(&self, predicate: &NamedNode, subject: &Term) -> impl Iterator<Item = &Term> {
+        self.objects
+            .get(&(predicate.clone(), subject.clone()))
+            .into_iter()
+            .flatten()
+    }
+
+    fn subjects(&self, predicate: &NamedNode, object: &Term) -> impl Iterator<Item = &Term> {
+        self.subjects
+            .get(&(predicate.clone(), object.clone()))
+            .into_iter()
+            .flatten()
+    }
+}
+
+/// The schema used by the rules about individuals.
+struct AssertionRules {
+    classes: Closure,
+    properties: Closure,
+    inverses: Closure,
+    symmetric: HashSet<Term>,
+    transitive: HashSet<Term>,
+    domains: HashMap<Term, Vec<Term>>,
+    ranges: HashMap<Term, Vec<Term>>,
+}
+
+impl AssertionRules {
+    /// Applies the rules to the `new` facts, returning their consequences.
+    ///
+    /// Transitivity joins the new facts with all facts in `index` on either end,
+    /// while the other rules only need the new fact itself.
+    fn apply(&self, new: &HashSet<Fact>, index: &FactIndex) -> HashSet<Fact> {
+        let type_predicate = rdf::TYPE.into_owned();
+        let mut consequences = HashSet::new();
+        for (subject, predicate, object) in new {
+            if matches!(subject, Term::Literal(_)) {
+                continue;
+            }
+            let property = Term::NamedNode(predicate.clone());
+            let is_resource = !matches!(object, Term::Literal(_));
+
+            if *predicate == type_predicate {
+                for class in self.classes.get(object).into_iter().flatten() {
+                    consequences.insert((subject.clone(), type_predicate.clone(), class.clone()));
+                }
+                continue;
+            }
+            for class in self.domains.get(&property).into_iter().flatten() {
+                consequences.insert((subject.clone(), type_predicate.clone(), class.clone()));
+            }
+            if is_resource {
+                for class in self.ranges.get(&property).into_iter().flatten() {
+                    consequences.insert((object.clone(), type_predicate.clone(), class.clone()));
+                }
+            }
+            for super_property in self.properties.get(&property).into_iter().flatten() {
+                if let Term::NamedNode(super_property) = super_property {
+                    consequences.insert((subject.clone(), super_property.clone(), object.clone()));
+                }
+            }
+            if !is_resource {
+                continue;
+            }
+            if self.symmetric.contains(&property) {
+                consequences.insert((object.clone(), predicate.clone(), subject.clone()));
+            }
+            for inverse in self.inverses.get(&property).into_iter().flatten() {
+                if let Term::NamedNode(inverse) = inverse {
+                    consequences.insert((object.clone(), inverse.clone(), subject.clone()));
+                }
+            }
+            if self.transitive.contains(&property) {
+                for next in index.objects(predicate, object) {
+                    consequences.insert((subject.clone(), predicate.clone(), next.clone()));
+                }
+                for previous in index.subjects(predicate, subject) {
+                    consequences.insert((previous.clone(), predicate.clone(), object.clone()));
+                }
+            }
+        }
+        consequences
+    }
+}
+
+/// Returns the triples entailed by `asserted` which aren't asserted themselves.
+pub fn infer(asserted: &HashSet<Fact>) -> HashSet<Fact> {
+    let mut inferred = HashSet::new();
+
+    let (asserted_classes, classes) =
+        subsumption(asserted, rdfs::SUB_CLASS_OF, owl::EQUIVALENT_CLASS);
+    infer_subsumptions(
+        rdfs::SUB_CLASS_OF,
+        &asserted_classes,
+        &classes,
+        &mut inferred,
+    );
+    let (asserted_properties, properties) =
+        subsumption(asserted, rdfs::SUB_PROPERTY_OF, owl::EQUIVALENT_PROPERTY);
+    infer_subsumptions(
+        rdfs::SUB_PROPERTY_OF,
+        &asserted_properties,
+        &properties,
+        &mut inferred,
+    );
+
+    let mut inverses = Closure::new();
+    for (property, inverse) in pairs(asserted, owl::INVERSE_OF) {
+        inverses
+            .entry(property.clone())
+            .or_default()
+            .insert(inverse.clone());
+        inverses
+            .entry(inverse.clone())
+            .or_default()
+            .insert(property.clone());
+    }
+    let symmetric = instances(asserted, owl::SYMMETRIC_PROPERTY)
+        .into_iter()
+        .cloned()
+        .collect::<HashSet<_>>();
+    // A symmetric property is its own inverse.
+    let mut mirrored = inverses.clone();
+    for property in symmetric.iter() {
+        mirrored
+            .entry(property.clone())
+            .or_default()
+            .insert(property.clone());
+    }
+    // Ranges may mirror inferred domains, so each step sees the consequences of the previous one.
+    let mut schema = asserted.clone();
+    schema.extend(inferred.iter().cloned());
+    infer_property_classes(
+        &schema,
+        rdfs::DOMAIN,
+        rdfs::RANGE,
+        &properties,
+        &mirrored,
+        &mut inferred,
+    );
+    schema.extend(inferred.iter().cloned());
+    infer_property_classes(
+        &schema,
+        rdfs::RANGE,
+        rdfs::DOMAIN,
+        &properties,
+        &mirrored,
+        &mut inferred,
+    );
+
+    let mut facts = schema;
+    facts.extend(inferred.iter().cloned());
+    let owned = |classes: HashMap<&Term, Vec<&Term>>| {
+        classes
+            .into_iter()
+            .map(|(property, classes)| (property.clone(), classes.into_iter().cloned().collect()))
+            .collect::<HashMap<_, _>>()
+    };
+    let domains = owned(property_classes(&facts, rdfs::DOMAIN));
+    let ranges = owned(property_classes(&facts, rdfs::RANGE));
+    let mut transitive = instances(asserted, owl::TRANSITIVE_PROPERTY)
+        .into_iter()
+        .cloned()
+        .collect::<HashSet<_>>();
+    let same_as = Term::NamedNode(owl::SAME_AS.into_owned());
+    let mut symmetric = symmetric;
+    symmetric.insert(same_as.clone());
+    transitive.insert(same_as);
+    let rules = AssertionRules {
+        classes,
+        properties,
+        inverses,
+        symmetric,
+        transitive,
+        domains,
+        ranges,
+    };
+
+    // Semi-naive evaluation: each round only applies the rules to the facts
+    // derived in the previous round, so no consequence is derived twice.
+    let mut index = FactIndex::default();
+    for fact in facts.iter() {
+        index.insert(fact);
+    }
+    let mut new = facts.clone();
+    while !new.is_empty() {
+        new = rules
+            .apply(&new, &index)
+            .into_iter()
+            .filter(|fact| fact.0 != fact.2 || fact.1.as_ref() == rdf::TYPE)
+            .filter(|fact| !facts.contains(fact))
+            .collect();
+        for fact in new.iter() {
+            index.insert(fact);
+            facts.insert(fact.clone());
+        }
+    }
+
+    facts.retain(|fact| !asserted.contains(fact));
+    facts
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn named(iri: &str) -> Term {
+        Term::NamedNode(NamedNode::new(iri).unwrap())
+    }
+
+    fn fact(subject: &str, predicate: NamedNodeRef<'_>, object: &str) -> Fact {
+        (named(subject), predicate.into_owned(), named(object))
+    }
+
+    #[test]
+    fn test_infer() {
+        let knows = NamedNodeRef::new("http://example.com#knows").unwrap();
+        let asserted = HashSet::from([
+            fact(
+                "http://example.com#Student",
+                rdfs::SUB_CLASS_OF,
+                "http://example.com#Pupil",
+            ),
+            fact(
+                "http://example.com#Pupil",
+                owl::EQUIVALENT_CLASS,
+                "http://example.com#Learner",
+            ),
+            fact(
+                "http://example.com#Learner",
+                rdfs::SUB_CLASS_OF,
+                "http://example.com#Person",
+            ),
+            fact(
+                "http://example.com#Student",
+                rdfs::SUB_CLASS_OF,
+                "http://example.com#Person",
+            ),
+            fact(
+                "http://example.com#knows",
+                rdf::TYPE,
+                owl::SYMMETRIC_PROPERTY.as_str(),
+            ),
+            fact(
+                "http://example.com#knows",
+                rdfs::SUB_PROPERTY_OF,
+                "http://example.com#relatedTo",
+            ),
+            fact(
+                "http://example.com#relatedTo",
+                rdfs::DOMAIN,
+                "http://example.com#Person",
+            ),
+            fact("http://example.com#alice", knows, "http://example.com#bob"),
+        ]);
+        let inferred = infer(&asserted);
+
+        // Subsumption through an equivalence, but not through transitivity alone.
+        assert!(inferred.contains(&fact(
+            "http://example.com#Student",
+            rdfs::SUB_CLASS_OF,
+            "http://example.com#Learner",
+        )));
+        assert!(!inferred.contains(&fact(
+            "http://example.com#Pupil",
+            rdfs::SUB_CLASS_OF,
+            "http://example.com#Learner",
+        )));
+        // Inherited domain, and the range of a symmetric property.
+        assert!(inferred.contains(&fact(
+            "http://example.com#knows",
+            rdfs::DOMAIN,
+            "http://example.com#Person",
+        )));
+        assert!(inferred.contains(&fact(
+            "http://example.com#knows",
+            rdfs::RANGE,
+            "http://example.com#Person",
+        )));
+        // Assertions about individuals.
+        assert!(inferred.contains(&fact(
+            "http://example.com#bob",
+            knows,
+            "http://example.com#alice",
+        )));
+        assert!(inferred.contains(&fact(
+            "http://example.com#alice",
+            NamedNodeRef::new("http://example.com#relatedTo").unwrap(),
+            "http://example.com#bob",
+        )));
+        assert!(inferred.contains(&fact(
+            "http://example.com#bob",
+            rdf::TYPE,
+            "http://example.com#Person",
+        )));
+        assert!(inferred.is_disjoint(&asserted));
+    }
+
+    #[test]
+    fn test_infer_transitive_chain() {
+        let part_of = NamedNodeRef::new("http://example.com#partOf").unwrap();
+        let part = |index: usize| format!("http://example.com#part{index}");
+        let mut asserted = HashSet::from([fact(
+            part_of.as_str(),
+            rdf::TYPE,
+            owl::TRANSITIVE_PROPERTY.as_str(),
+        )]);
+        for index in 0..50 {
+            asserted.insert(fact(&part(index), part_of, &part(index + 1)));
+        }
+        let inferred = infer(&asserted);
+
+        // Every part is part of all parts further down the chain.
+        assert_eq!(inferred.len(), 51 * 50 / 2 - 50);
+        assert!(inferred.contains(&fact(&part(0), part_of, &part(50))));
+        assert!(inferred.contains(&fact(&part(24), part_of, &part(26))));
+    }
+}
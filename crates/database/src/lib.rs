@@ -1,8 +1,14 @@
+mod cache;
+mod consistency;
+mod inference;
+pub mod results;
 mod serializers;
 pub mod store;
 pub use vowlr_sparql_queries;
 mod vocab;
 pub mod prelude {
+    pub use crate::cache::CacheMetrics;
     pub use crate::serializers::frontend::GraphDisplayDataSolutionSerializer;
+    pub use crate::serializers::subgraph::{filter_elements, select_subgraph};
     pub use rdf_fusion::execution::results::QueryResults;
 }
@@ -1,19 +1,37 @@
-mod store;
 use env_logger::Env;
 use grapher::prelude::GraphDisplayData;
-use log::info;
+use log::{error, info};
 use rdf_fusion::{execution::results::QueryResults, store::Store};
 use std::env;
 use std::path::Path;
 use vowlr_database::prelude::GraphDisplayDataSolutionSerializer;
 use vowlr_database::store::VOWLRStore;
 use vowlr_sparql_queries::prelude::DEFAULT_QUERY;
+use vowlr_util::diagnostics::{Profile, ProfileViolation};
+use vowlr_util::flags::GraphFlags;
 
 #[tokio::main]
 pub async fn main() {
     env_logger::Builder::from_env(Env::default().default_filter_or("info")).init();
     let session = Store::default();
     let args = env::args().collect::<Vec<String>>();
+    if args.get(1).is_some_and(|command| command == "profile") {
+        let Some(path) = args.get(2) else {
+            error!("Usage: {} profile <file>", args[0]);
+            return;
+        };
+        let vowlr = VOWLRStore::new(session);
+        vowlr
+            .insert_file(Path::new(path), false)
+            .await
+            .expect("Error inserting file");
+        let violations = vowlr
+            .profile_violations()
+            .await
+            .expect("Error validating profiles");
+        print_profile_violations(&violations);
+        return;
+    }
     let path;
     if args.len() > 1 {
         path = Path::new(&args[1]);
@@ -32,7 +50,7 @@ pub async fn main() {
     let query_stream = vowlr.session.query(DEFAULT_QUERY.as_str()).await.unwrap();
     if let QueryResults::Solutions(solutions) = query_stream {
         solution_serializer
-            .serialize_nodes_stream(&mut data_buffer, solutions)
+            .serialize_nodes_stream(&mut data_buffer, &mut GraphFlags::default(), solutions)
             .await
             .unwrap();
     } else {
@@ -42,6 +60,28 @@ pub async fn main() {
     print_graph_display_data(&data_buffer);
 }
 
+/// Logs whether the loaded ontology fits each profile, followed by its violations.
+pub fn print_profile_violations(violations: &[ProfileViolation]) {
+    for profile in Profile::ALL {
+        let profile_violations = violations
+            .iter()
+            .filter(|violation| violation.profile == profile)
+            .collect::<Vec<_>>();
+        if profile_violations.is_empty() {
+            info!("{}: no violations", profile.name());
+            continue;
+        }
+        info!(
+            "{}: {} violations",
+            profile.name(),
+            profile_violations.len()
+        );
+        for violation in profile_violations {
+            info!("  {}: {}", violation.axiom, violation.reason);
+        }
+    }
+}
+
 pub fn print_graph_display_data(data_buffer: &GraphDisplayData) {
     info!("--- Elements ---");
     for (index, (element, label)) in data_buffer
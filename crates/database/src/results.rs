@@ -0,0 +1,245 @@
+//! Converts query results between the store and the SPARQL console,
+//! and writes them in the standard SPARQL results and RDF formats.
+
+use oxrdf::{BlankNode, Literal, NamedNode, NamedOrBlankNode, Term, TripleRef, Variable};
+use rdf_fusion::io::{RdfFormat, RdfSerializer};
+use sparesults::{QueryResultsFormat, QueryResultsSerializer};
+use std::collections::BTreeMap;
+use vowlr_parser::errors::WebVowlStoreError;
+use vowlr_util::results::{ResultFormat, ResultTerm, SolutionTable, SparqlResults};
+
+/// The writer of a format, either of SPARQL results or of RDF.
+enum Writer {
+    Results(QueryResultsFormat),
+    Rdf(RdfFormat),
+}
+
+impl From<ResultFormat> for Writer {
+    fn from(format: ResultFormat) -> Self {
+        match format {
+            ResultFormat::Json => Self::Results(QueryResultsFormat::Json),
+            ResultFormat::Xml => Self::Results(QueryResultsFormat::Xml),
+            ResultFormat::Csv => Self::Results(QueryResultsFormat::Csv),
+            ResultFormat::Tsv => Self::Results(QueryResultsFormat::Tsv),
+            ResultFormat::Turtle => Self::Rdf(RdfFormat::Turtle),
+            ResultFormat::NTriples => Self::Rdf(RdfFormat::NTriples),
+        }
+    }
+}
+
+/// Returns the one of `formats` written with the media type `media_type`, if any.
+///
+/// Aliases such as `application/json` are recognized, as they are by the writers.
+pub fn format_of_media_type(media_type: &str, formats: &[ResultFormat]) -> Option<ResultFormat> {
+    formats
+        .iter()
+        .copied()
+        .find(|format| match Writer::from(*format) {
+            Writer::Results(writer) => {
+                QueryResultsFormat::from_media_type(media_type) == Some(writer)
+            }
+            Writer::Rdf(writer) => RdfFormat::from_media_type(media_type) == Some(writer),
+        })
+}
+
+/// Converts `term` into a term of the SPARQL console results.
+pub(crate) fn result_term(term: &Term) -> ResultTerm {
+    match term {
+        Term::NamedNode(iri) => ResultTerm::Iri(iri.as_str().to_string()),
+        Term::BlankNode(id) => ResultTerm::BlankNode(id.as_str().to_string()),
+        Term::Literal(literal) => ResultTerm::Literal {
+            value: literal.value().to_string(),
+            datatype: literal.datatype().as_str().to_string(),
+            language: literal.language().map(str::to_string),
+        },
+        // Quoted triples, if RDF 1.2 is enabled.
+        #[allow(unreachable_patterns)]
+        term => ResultTerm::Iri(term.to_string()),
+    }
+}
+
+/// Converts `term` of the SPARQL console results back into a term of the store.
+///
+/// The terms were read from the store, so they are known to be valid.
+fn term(term: &ResultTerm) -> Term {
+    match term {
+        ResultTerm::Iri(iri) => NamedNode::new_unchecked(iri).into(),
+        ResultTerm::BlankNode(id) => BlankNode::new_unchecked(id).into(),
+        ResultTerm::Literal {
+            value,
+            language: Some(language),
+            ..
+        } => Literal::new_language_tagged_literal_unchecked(value, language).into(),
+        ResultTerm::Literal {
+            value, datatype, ..
+        } => Literal::new_typed_literal(value, NamedNode::new_unchecked(datatype)).into(),
+    }
+}
+
+/// Writes `results` in `format`, which should be one of [`SparqlResults::formats`].
+///
+/// Triples are written in Turtle using `prefixes`.
+pub fn write_results(
+    results: &SparqlResults,
+    format: ResultFormat,
+    prefixes: &BTreeMap<String, String>,
+) -> Result<Vec<u8>, WebVowlStoreError> {
+    match (results, Writer::from(format)) {
+        (SparqlResults::Boolean(answer), Writer::Results(writer)) => {
+            Ok(QueryResultsSerializer::from_format(writer)
+                .serialize_boolean_to_writer(Vec::new(), *answer)?)
+        }
+        (SparqlResults::Solutions(table), Writer::Results(writer)) => {
+            write_solutions(table, writer)
+        }
+        (SparqlResults::Graph(table), Writer::Rdf(writer)) => {
+            write_triples(table, writer, prefixes)
+        }
+        _ => Err(format!("The results can't be written as {}", format.name()).into()),
+    }
+}
+
+/// Writes the solutions of `table` in the SPARQL results format `format`.
+fn write_solutions(
+    table: &SolutionTable,
+    format: QueryResultsFormat,
+) -> Result<Vec<u8>, WebVowlStoreError> {
+    let variables = table
+        .variables
+        .iter()
+        .map(|variable| Variable::new(variable).map_err(|e| e.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut serializer = QueryResultsSerializer::from_format(format)
+        .serialize_solutions_to_writer(Vec::new(), variables.clone())?;
+    for row in &table.rows {
+        let solution = variables
+            .iter()
+            .zip(row)
+            .filter_map(|(variable, value)| Some((variable, term(value.as_ref()?))))
+            .collect::<Vec<_>>();
+        serializer.serialize(solution.iter().map(|(variable, value)| (*variable, value)))?;
+    }
+    Ok(serializer.finish()?)
+}
+
+/// Writes the triples of `table` in the RDF format `format`, declaring `prefixes` if it can.
+///
+/// Each row with three bound terms is written as a triple.
+fn write_triples(
+    table: &SolutionTable,
+    format: RdfFormat,
+    prefixes: &BTreeMap<String, String>,
+) -> Result<Vec<u8>, WebVowlStoreError> {
+    let mut serializer = RdfSerializer::from_format(format);
+    for (name, iri) in prefixes {
+        serializer = serializer.with_prefix(name, iri)?;
+    }
+    let mut serializer = serializer.for_writer(Vec::new());
+    for row in &table.rows {
+        let [
+            Some(subject),
+            Some(ResultTerm::Iri(predicate)),
+            Some(object),
+        ] = row.as_slice()
+        else {
+            continue;
+        };
+        let subject: NamedOrBlankNode = match subject {
+            ResultTerm::Iri(iri) => NamedNode::new_unchecked(iri).into(),
+            ResultTerm::BlankNode(id) => BlankNode::new_unchecked(id).into(),
+            ResultTerm::Literal { .. } => continue,
+        };
+        serializer.serialize_triple(TripleRef::new(
+            &subject,
+            &NamedNode::new_unchecked(predicate),
+            &term(object),
+        ))?;
+    }
+    Ok(serializer.finish()?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn table() -> SolutionTable {
+        SolutionTable {
+            variables: vec!["s".to_string(), "label".to_string()],
+            rows: vec![
+                vec![
+                    Some(ResultTerm::Iri("http://example.com#A".to_string())),
+                    Some(ResultTerm::Literal {
+                        value: "Say \"hi\"".to_string(),
+                        datatype: "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString"
+                            .to_string(),
+                        language: Some("en".to_string()),
+                    }),
+                ],
+                vec![Some(ResultTerm::BlankNode("b0".to_string())), None],
+            ],
+            truncated: false,
+        }
+    }
+
+    #[test]
+    fn test_write_results() {
+        let prefixes = BTreeMap::new();
+        let solutions = SparqlResults::Solutions(table());
+        let json = write_results(&solutions, ResultFormat::Json, &prefixes).unwrap();
+        let json = String::from_utf8(json).unwrap();
+        assert!(json.contains(r#""vars":["s","label"]"#));
+        assert!(json.contains(r#""xml:lang":"en""#));
+        assert!(json.contains(r#""type":"bnode","value":"b0""#));
+        let csv = write_results(&solutions, ResultFormat::Csv, &prefixes).unwrap();
+        assert!(String::from_utf8(csv).unwrap().starts_with("s,label\r\n"));
+        assert!(write_results(&solutions, ResultFormat::Turtle, &prefixes).is_err());
+
+        let xml = write_results(&SparqlResults::Boolean(true), ResultFormat::Xml, &prefixes);
+        assert!(
+            String::from_utf8(xml.unwrap())
+                .unwrap()
+                .contains("<boolean>true</boolean>")
+        );
+
+        let triples = SparqlResults::Graph(SolutionTable {
+            variables: ["subject", "predicate", "object"]
+                .map(String::from)
+                .to_vec(),
+            rows: vec![vec![
+                Some(ResultTerm::Iri("http://example.com#A".to_string())),
+                Some(ResultTerm::Iri(
+                    "http://www.w3.org/2000/01/rdf-schema#label".to_string(),
+                )),
+                Some(ResultTerm::Literal {
+                    value: "A".to_string(),
+                    datatype: "http://www.w3.org/2001/XMLSchema#string".to_string(),
+                    language: None,
+                }),
+            ]],
+            truncated: false,
+        });
+        let ntriples = write_results(&triples, ResultFormat::NTriples, &prefixes).unwrap();
+        assert_eq!(
+            String::from_utf8(ntriples).unwrap(),
+            "<http://example.com#A> <http://www.w3.org/2000/01/rdf-schema#label> \"A\" .\n"
+        );
+    }
+
+    #[test]
+    fn test_format_of_media_type() {
+        let solutions = ResultFormat::SOLUTIONS;
+        assert_eq!(
+            format_of_media_type("application/json", &solutions),
+            Some(ResultFormat::Json)
+        );
+        assert_eq!(
+            format_of_media_type("application/sparql-results+xml", &solutions),
+            Some(ResultFormat::Xml)
+        );
+        assert_eq!(format_of_media_type("text/turtle", &solutions), None);
+        assert_eq!(
+            format_of_media_type("text/turtle", &ResultFormat::GRAPHS),
+            Some(ResultFormat::Turtle)
+        );
+    }
+}
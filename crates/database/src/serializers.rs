@@ -5,9 +5,13 @@ use std::{
 
 use grapher::prelude::{ElementType, GraphDisplayData};
 use log::error;
-use oxrdf::Term;
+use oxrdf::{Term, vocab::rdfs};
+use vowlr_util::flags::{ElementKind, GraphFlags};
 
+mod external;
 pub mod frontend;
+mod label;
+pub mod subgraph;
 
 #[derive(Debug, Hash, Clone, Eq, PartialEq)]
 pub struct Triple {
@@ -54,6 +58,10 @@ pub struct Edge {
     element_type: ElementType,
     /// The object IRI
     object: String,
+    /// The IRI of the property drawn by this edge, if any.
+    ///
+    /// Keeps edges of different properties between the same nodes apart.
+    property: Option<String>,
 }
 impl Display for Edge {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
@@ -62,6 +70,9 @@ impl Display for Edge {
             "Edge{{ {} - {:?} - {} }}",
             self.subject, self.element_type, self.object
         )?;
+        if let Some(property) = &self.property {
+            write!(f, " ({})", property)?;
+        }
         Ok(())
     }
 }
@@ -204,10 +215,48 @@ pub struct SerializationDataBuffer {
     /// - 0 = The triple (if any).
     /// - 1 = The reason it failed to serialize (or the message if no triple is available).
     failed_buffer: Vec<(Option<Triple>, String)>,
-    /// The base IRI of the document.
+    /// The IRIs and version IRIs of the visualized ontologies.
     ///
     /// For instance: `http://purl.obolibrary.org/obo/envo.owl`
-    document_base: Option<String>,
+    ontology_iris: Vec<String>,
+    /// The IRIs of ontologies imported by the visualized ontologies.
+    imported_ontologies: Vec<String>,
+    /// The IRIs of elements annotated with `owl:deprecated true`
+    /// or typed as `owl:DeprecatedProperty`.
+    deprecated_elements: HashSet<String>,
+    /// Stores triples about individuals until all classes and properties are known.
+    ///
+    /// This includes declarations, class assertions, property assertions,
+    /// `owl:sameAs` and `owl:differentFrom`.
+    individual_buffer: Vec<Triple>,
+    /// Stores `owl:oneOf` and `owl:hasValue` triples until all individuals are known.
+    value_buffer: Vec<Triple>,
+    /// Maps the IRI of a value restriction to the IRI of its restricted property.
+    restricted_properties: HashMap<String, String>,
+    /// Stores the definitions of datatypes until the labels of all their parts are known.
+    ///
+    /// This includes aliases, complements, unions, intersections and facet restrictions.
+    datatype_buffer: Vec<Triple>,
+    /// Stores `rdfs:subPropertyOf` and `owl:equivalentProperty` triples
+    /// until all properties and their edges are known.
+    property_hierarchy_buffer: Vec<Triple>,
+    /// Stores property chains, disjointness and difference axioms,
+    /// including the list cells of chains, until all their members are known.
+    axiom_buffer: Vec<Triple>,
+    /// The IRIs of declared annotation properties.
+    annotation_properties: HashSet<String>,
+    /// Stores annotations and annotated axioms until all annotated elements are known.
+    annotation_buffer: Vec<Triple>,
+    /// Stores the inferred triples, which are also serialized like asserted ones,
+    /// until all edges drawn from them are known.
+    inferred_buffer: Vec<Triple>,
+    /// The edges drawn from inferred triples, see [`GraphFlags::inferred`].
+    inferred_edges: HashSet<Edge>,
+    /// The kind of nodes drawn as the closest element type, see [`GraphFlags::kinds`].
+    ///
+    /// - Key = The node IRI.
+    /// - Value = The kind of the node.
+    node_kinds: HashMap<String, ElementKind>,
 }
 impl SerializationDataBuffer {
     pub fn new() -> Self {
@@ -224,18 +273,38 @@ impl SerializationDataBuffer {
             unknown_edge_buffer: HashMap::new(),
             unknown_buffer: HashMap::new(),
             failed_buffer: Vec::new(),
-            document_base: None,
+            ontology_iris: Vec::new(),
+            imported_ontologies: Vec::new(),
+            deprecated_elements: HashSet::new(),
+            individual_buffer: Vec::new(),
+            value_buffer: Vec::new(),
+            restricted_properties: HashMap::new(),
+            datatype_buffer: Vec::new(),
+            property_hierarchy_buffer: Vec::new(),
+            axiom_buffer: Vec::new(),
+            annotation_properties: HashSet::new(),
+            annotation_buffer: Vec::new(),
+            inferred_buffer: Vec::new(),
+            inferred_edges: HashSet::new(),
+            node_kinds: HashMap::new(),
             edge_characteristics: HashMap::new(),
             node_characteristics: HashMap::new(),
         }
     }
 }
 
-impl Into<GraphDisplayData> for SerializationDataBuffer {
-    fn into(mut self) -> GraphDisplayData {
+impl Into<(GraphDisplayData, GraphFlags)> for SerializationDataBuffer {
+    fn into(mut self) -> (GraphDisplayData, GraphFlags) {
         let mut display_data = GraphDisplayData::new();
+        let mut flags = GraphFlags::default();
+        let sub_property_of = rdfs::SUB_PROPERTY_OF.to_string();
         let mut iricache: HashMap<String, usize> = HashMap::new();
         for (iri, element) in self.node_element_buffer.into_iter() {
+            // The node is pushed next, at the current length.
+            if let Some(kind) = self.node_kinds.get(&iri) {
+                flags.kinds.insert(display_data.elements.len(), *kind);
+            }
+            flags.iris.insert(display_data.elements.len(), iri.clone());
             let label = self.label_buffer.remove(&iri);
             match label {
                 Some(label) => {
@@ -261,11 +330,24 @@ impl Into<GraphDisplayData> for SerializationDataBuffer {
                 (Some(subject_idx), Some(object_idx), Some(label)) => {
                     display_data.elements.push(edge.element_type);
                     display_data.labels.push(label);
-                    display_data.edges.push([
-                        *subject_idx,
-                        display_data.elements.len() - 1,
-                        *object_idx,
-                    ]);
+                    let edge_idx = display_data.elements.len() - 1;
+                    display_data
+                        .edges
+                        .push([*subject_idx, edge_idx, *object_idx]);
+                    if edge.property.as_ref() == Some(&sub_property_of) {
+                        flags.kinds.insert(edge_idx, ElementKind::SubPropertyOf);
+                    }
+                    if let Some(property) = &edge.property {
+                        flags.properties.insert(edge_idx, property.clone());
+                    }
+                    if self.inferred_edges.contains(edge) {
+                        flags.inferred.insert(edge_idx);
+                    }
+                    if let Some(characteristics) = self.edge_characteristics.remove(edge) {
+                        display_data
+                            .characteristics
+                            .insert(edge_idx, characteristics.join("\n"));
+                    }
                 }
                 (Some(_), Some(_), None) => {
                     error!("Label in edge not found in iricache: {}", edge.subject);
@@ -297,18 +379,58 @@ impl Into<GraphDisplayData> for SerializationDataBuffer {
         }
         // TODO: handle cardinalities
 
-        display_data
+        (display_data, flags)
     }
 }
 
 impl Display for SerializationDataBuffer {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "SerializationDataBuffer {{")?;
+        writeln!(f, "\tontology_iris: {:?}", self.ontology_iris)?;
+        writeln!(f, "\timported_ontologies: {:?}", self.imported_ontologies)?;
+        writeln!(f, "\tdeprecated_elements: {:?}", self.deprecated_elements)?;
+        writeln!(f, "\tindividual_buffer:")?;
+        for triple in self.individual_buffer.iter() {
+            writeln!(f, "\t\t{}", triple)?;
+        }
+        writeln!(f, "\tvalue_buffer:")?;
+        for triple in self.value_buffer.iter() {
+            writeln!(f, "\t\t{}", triple)?;
+        }
         writeln!(
             f,
-            "\tdocument_base: {}",
-            self.document_base.as_ref().unwrap_or(&"".to_string())
+            "\trestricted_properties: {:?}",
+            self.restricted_properties
         )?;
+        writeln!(f, "\tdatatype_buffer:")?;
+        for triple in self.datatype_buffer.iter() {
+            writeln!(f, "\t\t{}", triple)?;
+        }
+        writeln!(f, "\tproperty_hierarchy_buffer:")?;
+        for triple in self.property_hierarchy_buffer.iter() {
+            writeln!(f, "\t\t{}", triple)?;
+        }
+        writeln!(f, "\taxiom_buffer:")?;
+        for triple in self.axiom_buffer.iter() {
+            writeln!(f, "\t\t{}", triple)?;
+        }
+        writeln!(
+            f,
+            "\tannotation_properties: {:?}",
+            self.annotation_properties
+        )?;
+        writeln!(f, "\tannotation_buffer:")?;
+        for triple in self.annotation_buffer.iter() {
+            writeln!(f, "\t\t{}", triple)?;
+        }
+        writeln!(f, "\tinferred_buffer:")?;
+        for triple in self.inferred_buffer.iter() {
+            writeln!(f, "\t\t{}", triple)?;
+        }
+        writeln!(f, "\tinferred_edges:")?;
+        for edge in self.inferred_edges.iter() {
+            writeln!(f, "\t\t{}", edge)?;
+        }
         writeln!(f, "\tnode_element_buffer:")?;
         for (iri, element) in self.node_element_buffer.iter() {
             writeln!(f, "\t\t{} : {}", iri, element)?;
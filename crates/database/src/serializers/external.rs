@@ -0,0 +1,199 @@
+//! Decides which elements are external to the visualized ontology.
+//!
+//! An element is external if its IRI is not in the namespace of the ontology.
+//! p. 6 of https://www.semantic-web-journal.net/system/files/swj1114.pdf
+
+use std::collections::HashMap;
+
+use vowlr_util::options::SerializerOptions;
+
+/// Removes the angle brackets of a formatted IRI, if any.
+pub fn strip_brackets(iri: &str) -> &str {
+    iri.strip_prefix('<')
+        .and_then(|iri| iri.strip_suffix('>'))
+        .unwrap_or(iri)
+}
+
+/// Returns the namespace of `iri`, i.e. everything up to and including its last `#` or `/`.
+pub fn namespace(iri: &str) -> &str {
+    match iri.rfind(['#', '/']) {
+        Some(i) => &iri[..=i],
+        None => iri,
+    }
+}
+
+/// Returns the length of `base` if `iri` is in the namespace of `base`.
+///
+/// `base` is either a namespace, e.g. `http://example.com/onto#`,
+/// or the IRI of an ontology, e.g. `http://example.com/onto`,
+/// whose elements are then expected at `http://example.com/onto#` or `http://example.com/onto/`.
+fn match_length(iri: &str, base: &str) -> Option<usize> {
+    let base = base.trim_end_matches('#');
+    let rest = iri.strip_prefix(base)?;
+    let is_match =
+        base.ends_with('/') || rest.is_empty() || rest.starts_with('#') || rest.starts_with('/');
+    is_match.then_some(base.len())
+}
+
+/// Classifies IRIs as external or internal to the visualized ontology.
+#[derive(Debug, Default)]
+pub struct ExternalClassifier {
+    /// Namespaces of the visualized ontology.
+    internal: Vec<String>,
+    /// Namespaces of imported ontologies.
+    imported: Vec<String>,
+    /// Namespaces the user marked as internal.
+    user_internal: Vec<String>,
+    /// Namespaces the user marked as external.
+    user_external: Vec<String>,
+}
+
+impl ExternalClassifier {
+    /// Creates a classifier for an ontology containing the elements `iris`.
+    ///
+    /// `bases` are the ontology IRIs, version IRIs and base IRIs of the documents.
+    /// If none of them matches any element, e.g. because the ontology IRI
+    /// differs from the namespace of its elements, the most common namespace
+    /// among `iris` is used instead.
+    ///
+    /// Elements in the namespace of one of the `imports` are external,
+    /// unless they are in a more specific namespace of the ontology.
+    pub fn new<'a>(
+        bases: &[String],
+        imports: &[String],
+        iris: impl IntoIterator<Item = &'a str>,
+        options: &SerializerOptions,
+    ) -> Self {
+        let iris = iris.into_iter().map(strip_brackets).collect::<Vec<_>>();
+        let mut internal = bases
+            .iter()
+            .map(|base| strip_brackets(base).to_string())
+            .filter(|base| !base.is_empty())
+            .collect::<Vec<_>>();
+
+        let any_match = iris.iter().any(|iri| {
+            internal
+                .iter()
+                .any(|base| match_length(iri, base).is_some())
+        });
+        if !any_match {
+            let mut counts: HashMap<&str, usize> = HashMap::new();
+            for iri in iris.iter() {
+                *counts.entry(namespace(iri)).or_default() += 1;
+            }
+            // Ties are broken by the namespace itself to keep the result deterministic.
+            if let Some((most_common, _)) = counts
+                .into_iter()
+                .max_by_key(|&(namespace, count)| (count, std::cmp::Reverse(namespace)))
+            {
+                internal.push(most_common.to_string());
+            }
+        }
+
+        Self {
+            internal,
+            imported: imports
+                .iter()
+                .map(|import| strip_brackets(import).to_string())
+                .collect(),
+            user_internal: options.internal_namespaces.clone(),
+            user_external: options.external_namespaces.clone(),
+        }
+    }
+
+    /// Returns whether the element `iri` is external.
+    ///
+    /// `iri` may be enclosed in angle brackets. Blank nodes are never external.
+    pub fn is_external(&self, iri: &str) -> bool {
+        if iri.starts_with("_:") {
+            return false;
+        }
+        let iri = strip_brackets(iri);
+
+        // The user knows best, so their namespaces are matched as plain prefixes.
+        let longest_user_match = |namespaces: &[String]| {
+            namespaces
+                .iter()
+                .filter(|namespace| !namespace.is_empty() && iri.starts_with(namespace.as_str()))
+                .map(String::len)
+                .max()
+        };
+        match (
+            longest_user_match(&self.user_internal),
+            longest_user_match(&self.user_external),
+        ) {
+            (Some(internal), Some(external)) => return external > internal,
+            (Some(_), None) => return false,
+            (None, Some(_)) => return true,
+            (None, None) => {}
+        }
+
+        let longest_match = |namespaces: &[String]| {
+            namespaces
+                .iter()
+                .filter_map(|base| match_length(iri, base))
+                .max()
+        };
+        match (longest_match(&self.internal), longest_match(&self.imported)) {
+            (Some(internal), Some(imported)) => imported > internal,
+            (Some(_), None) => false,
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ontology_iri_base() {
+        let classifier = ExternalClassifier::new(
+            &["<http://example.com/onto>".to_string()],
+            &[],
+            ["<http://example.com/onto#Parent>"],
+            &SerializerOptions::default(),
+        );
+        assert!(!classifier.is_external("<http://example.com/onto#Parent>"));
+        assert!(!classifier.is_external("<http://example.com/onto/Child>"));
+        assert!(classifier.is_external("<http://example.com/ontology#Parent>"));
+        assert!(classifier.is_external("<http://xmlns.com/foaf/0.1/Person>"));
+        assert!(!classifier.is_external("_:b0"));
+    }
+
+    #[test]
+    fn test_most_common_namespace() {
+        // The ontology IRI does not match any element, as is common for OBO ontologies.
+        let classifier = ExternalClassifier::new(
+            &["http://purl.obolibrary.org/obo/envo.owl".to_string()],
+            &[],
+            [
+                "<http://purl.obolibrary.org/obo/ENVO_01000254>",
+                "<http://purl.obolibrary.org/obo/ENVO_00000428>",
+                "<http://xmlns.com/foaf/0.1/Person>",
+            ],
+            &SerializerOptions::default(),
+        );
+        assert!(!classifier.is_external("<http://purl.obolibrary.org/obo/ENVO_01000254>"));
+        assert!(classifier.is_external("<http://xmlns.com/foaf/0.1/Person>"));
+    }
+
+    #[test]
+    fn test_imports_and_user_overrides() {
+        let options = SerializerOptions {
+            external_namespaces: vec!["http://example.com/onto#Legacy".to_string()],
+            internal_namespaces: vec!["http://xmlns.com/foaf/".to_string()],
+            ..Default::default()
+        };
+        let classifier = ExternalClassifier::new(
+            &["http://example.com/".to_string()],
+            &["http://example.com/onto".to_string()],
+            ["<http://example.com/Thing>"],
+            &options,
+        );
+        assert!(!classifier.is_external("<http://example.com/Thing>"));
+        assert!(classifier.is_external("<http://example.com/onto#Parent>"));
+        assert!(classifier.is_external("<http://example.com/onto#LegacyParent>"));
+        assert!(!classifier.is_external("<http://xmlns.com/foaf/0.1/Person>"));
+    }
+}
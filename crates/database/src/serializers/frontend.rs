@@ -1,31 +1,93 @@
 use std::{
     collections::{HashMap, HashSet},
     fmt::format,
+    hash::{DefaultHasher, Hash, Hasher},
     mem::{swap, take},
     time::{Duration, Instant},
 };
 
 use super::{Edge, SerializationDataBuffer, Triple};
 use crate::{
-    serializers::{EdgeDirectionHint, EdgeDirections},
-    vocab::owl,
+    serializers::{
+        EdgeDirectionHint, EdgeDirections,
+        external::{ExternalClassifier, strip_brackets},
+        label::fallback_label,
+    },
+    vocab::{owl, skos, xsd},
 };
-use fluent_uri::Iri;
 use futures::StreamExt;
 use grapher::prelude::{
     Characteristic, ElementType, GenericEdge, GenericNode, GenericType, GraphDisplayData, OwlEdge,
     OwlNode, OwlType, RdfEdge, RdfType, RdfsEdge, RdfsNode, RdfsType,
 };
 use log::{debug, error, info, trace, warn};
-use oxrdf::{IriParseError, NamedNode, vocab::rdf};
+use oxrdf::{BlankNode, IriParseError, Literal, NamedNode, NamedNodeRef, vocab::rdf};
 use rdf_fusion::{
     execution::results::QuerySolutionStream,
     model::{Term, vocab::rdfs},
 };
 use vowlr_parser::errors::WebVowlStoreError;
+use vowlr_sparql_queries::prelude::VOCABULARY_PREFIXES;
+use vowlr_util::{
+    diagnostics::Diagnostic,
+    flags::{ElementKind, GraphFlags},
+    limits::QueryOutcome,
+    options::{SerializerOptions, VisualizationMode},
+};
+
+/// Constraining facets in the order they're described, with their Manchester syntax.
+const FACETS: [(NamedNodeRef<'static>, &str); 10] = [
+    (xsd::LENGTH, "length"),
+    (xsd::MIN_LENGTH, "minLength"),
+    (xsd::MAX_LENGTH, "maxLength"),
+    (xsd::PATTERN, "pattern"),
+    (xsd::MIN_INCLUSIVE, ">="),
+    (xsd::MIN_EXCLUSIVE, ">"),
+    (xsd::MAX_INCLUSIVE, "<="),
+    (xsd::MAX_EXCLUSIVE, "<"),
+    (xsd::TOTAL_DIGITS, "totalDigits"),
+    (xsd::FRACTION_DIGITS, "fractionDigits"),
+];
+
+/// Converts an element id, e.g. `<http://example.com#A>` or `_:b0`, back into a term.
+fn element_term(id: &str) -> Term {
+    match id.strip_prefix("_:") {
+        Some(blank) => Term::BlankNode(BlankNode::new_unchecked(blank)),
+        None => Term::NamedNode(NamedNode::new_unchecked(strip_brackets(id))),
+    }
+}
+
+/// The definition of a datatype, collected from the datatype buffer.
+#[derive(Default)]
+struct DatatypeDefinition {
+    /// The restricted datatype, or the datatype this one is an alias of.
+    base: Option<String>,
+    /// Facets as indices into [`FACETS`], with their values.
+    facets: Vec<(usize, String)>,
+    complement: Option<String>,
+    intersection: Vec<String>,
+    union: Vec<String>,
+}
 
 pub struct GraphDisplayDataSolutionSerializer {
     pub resolvable_iris: HashMap<String, (NamedNode, ElementType)>,
+    /// Options controlling the serialization.
+    pub options: SerializerOptions,
+    /// Prefixes used to derive prefixed names.
+    ///
+    /// Each prefix is a tuple of its name and namespace IRI.
+    pub prefixes: Vec<(String, String)>,
+    /// Base IRIs declared by the loaded documents, e.g. using `xml:base`.
+    ///
+    /// Used alongside the ontology IRI to determine external elements.
+    pub base_iris: Vec<String>,
+    /// Problems found in the loaded ontologies, highlighted on the affected elements.
+    pub diagnostics: Vec<Diagnostic>,
+    /// The maximum number of solutions to serialize, if any.
+    ///
+    /// Once reached, the remaining solutions are not consumed,
+    /// which cancels the query, and the graph is marked as truncated.
+    pub max_solutions: Option<usize>,
 }
 
 impl GraphDisplayDataSolutionSerializer {
@@ -48,19 +110,84 @@ impl GraphDisplayDataSolutionSerializer {
         ]);
         Self {
             resolvable_iris: resolvables,
+            options: SerializerOptions::default(),
+            prefixes: VOCABULARY_PREFIXES
+                .iter()
+                .map(|(name, namespace)| (name.to_string(), namespace.to_string()))
+                .collect(),
+            base_iris: Vec::new(),
+            diagnostics: Vec::new(),
+            max_solutions: None,
+        }
+    }
+
+    /// Creates a serializer using `options`.
+    pub fn with_options(options: SerializerOptions) -> Self {
+        Self {
+            options,
+            ..Self::new()
+        }
+    }
+
+    /// Adds the prefixes of the loaded documents, used to derive prefixed names.
+    pub fn with_prefixes(mut self, prefixes: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.prefixes.extend(prefixes);
+        self
+    }
+
+    /// Adds the base IRIs declared by the loaded documents.
+    pub fn with_base_iris(mut self, base_iris: impl IntoIterator<Item = String>) -> Self {
+        self.base_iris.extend(base_iris);
+        self
+    }
+
+    /// Adds the problems found in the loaded ontologies.
+    pub fn with_diagnostics(mut self, diagnostics: impl IntoIterator<Item = Diagnostic>) -> Self {
+        self.diagnostics.extend(diagnostics);
+        self
+    }
+
+    /// Stops serializing after `max_solutions` solutions.
+    pub fn with_max_solutions(mut self, max_solutions: usize) -> Self {
+        self.max_solutions = Some(max_solutions);
+        self
+    }
+
+    /// Derives a label from `iri` using the configured fallback strategy.
+    fn fallback_label(&self, iri: &str) -> Option<String> {
+        fallback_label(iri, self.options.label_fallback, &self.prefixes)
+    }
+
+    /// Derives labels for all nodes which did not get one during serialization.
+    fn fill_missing_labels(&self, data_buffer: &mut SerializationDataBuffer) {
+        for iri in data_buffer.node_element_buffer.keys() {
+            if data_buffer.label_buffer.contains_key(iri) {
+                continue;
+            }
+            if let Some(label) = self.fallback_label(iri) {
+                data_buffer.label_buffer.insert(iri.clone(), label);
+            }
         }
     }
 
     pub async fn serialize_nodes_stream(
         &self,
         data: &mut GraphDisplayData,
+        flags: &mut GraphFlags,
         mut solution_stream: QuerySolutionStream,
-    ) -> Result<(), WebVowlStoreError> {
-        let mut count: u32 = 0;
+    ) -> Result<QueryOutcome, WebVowlStoreError> {
+        let mut count: usize = 0;
+        let mut truncated = false;
+        let mut hidden_individuals = 0;
         info!("Serializing query solution stream...");
         let start_time = Instant::now();
         let mut data_buffer = SerializationDataBuffer::new();
         while let Some(solution) = solution_stream.next().await {
+            if self.max_solutions.is_some_and(|max| count >= max) {
+                warn!("Query has more than {count} solutions, the graph is truncated");
+                truncated = true;
+                break;
+            }
             let solution = solution?;
             let Some(id_term) = solution.get("id") else {
                 continue;
@@ -76,11 +203,34 @@ impl GraphDisplayDataSolutionSerializer {
                 element_type: node_type_term.to_owned(),
                 target: solution.get("target").map(|term| term.to_owned()),
             };
-            self.write_node_triple(&mut data_buffer, triple);
+            if solution.get("inferred").is_some() {
+                data_buffer.inferred_buffer.push(triple.clone());
+            }
+            match self.options.mode {
+                VisualizationMode::Owl => self.write_node_triple(&mut data_buffer, triple),
+                VisualizationMode::Rdf | VisualizationMode::Skos => {
+                    self.write_resource_triple(&mut data_buffer, triple)
+                }
+            }
             count += 1;
         }
-        self.try_resolve_unknown_edges(&mut data_buffer);
-        self.check_all_unknowns(&mut data_buffer);
+        // Cancel the query, in case it was truncated.
+        drop(solution_stream);
+        if self.options.mode == VisualizationMode::Owl {
+            self.try_resolve_unknown_edges(&mut data_buffer);
+            self.check_all_unknowns(&mut data_buffer);
+            hidden_individuals = self.write_individuals(&mut data_buffer);
+            self.write_annotations(&mut data_buffer);
+            self.write_values(&mut data_buffer);
+            self.write_property_hierarchy(&mut data_buffer);
+            self.write_axioms(&mut data_buffer);
+            self.describe_datatypes(&mut data_buffer);
+            self.mark_external_elements(&mut data_buffer);
+            self.mark_deprecated_elements(&mut data_buffer);
+            self.mark_inferred_elements(&mut data_buffer);
+            self.highlight_diagnostics(&mut data_buffer);
+        }
+        self.fill_missing_labels(&mut data_buffer);
 
         let finish_time = Instant::now()
             .checked_duration_since(start_time)
@@ -118,9 +268,13 @@ impl GraphDisplayDataSolutionSerializer {
             error!("Failed to serialize: {}", f);
         }
         debug!("{}", data_buffer);
-        *data = data_buffer.into();
+        (*data, *flags) = data_buffer.into();
         debug!("{}", data);
-        Ok(())
+        Ok(QueryOutcome {
+            solutions: count,
+            truncated,
+            hidden_individuals,
+        })
     }
 
     /// Extract label info from the query solution and store until
@@ -138,51 +292,34 @@ impl GraphDisplayDataSolutionSerializer {
             return;
         }
 
-        match label {
-            // Case 1: Label is a rdfs:label OR rdfs:Resource OR rdf:ID
-            Some(label) => {
-                if label.to_string() != "" {
-                    data_buffer
-                        .label_buffer
-                        .insert(id_term.to_string(), label.to_string());
-                } else {
+        // Literals are stored without quotes and language tags.
+        let label = label
+            .map(|label| match label {
+                Term::Literal(literal) => literal.value().to_string(),
+                other => other.to_string(),
+            })
+            .filter(|label| {
+                if label.is_empty() {
                     debug!("Empty label detected for iri '{iri}'");
                 }
+                !label.is_empty()
+            });
+
+        match label {
+            // Case 1: Label is a rdfs:label OR skos:prefLabel OR rdf:resource OR rdf:ID
+            Some(label) => {
+                data_buffer.label_buffer.insert(id_term.to_string(), label);
             }
-            // Case 2: Try parsing the iri
-            None => {
-                // Remove '<' and '>' from iri string to
-                // comply with https://www.ietf.org/rfc/rfc3987.html (p. 12)
-                let compliant_iri = iri[1..iri.len() - 1].to_string();
-                match Iri::parse(compliant_iri) {
-                    // Case 2.1: Look for fragments in the iri
-                    Ok(id_iri) => match id_iri.fragment() {
-                        Some(frag) => {
-                            data_buffer
-                                .label_buffer
-                                .insert(id_term.to_string(), frag.to_string());
-                        }
-                        // Case 2.2: Look for path in iri
-                        None => {
-                            debug!("No fragment found in iri '{iri}'");
-                            match id_iri.path().rsplit_once('/') {
-                                Some(path) => {
-                                    data_buffer
-                                        .label_buffer
-                                        .insert(id_term.to_string(), path.1.to_string());
-                                }
-                                None => {
-                                    debug!("No path found in iri '{iri}'");
-                                }
-                            }
-                        }
-                    },
-                    Err(e) => {
-                        // Do not make a 'warn!'. A parse error is allowed to happen (e.g. on blank nodes).
-                        debug!("Failed to parse iri '{}':\n{:?}", iri, e);
-                    }
+            // Case 2: Derive a label from the iri (no label or an empty label)
+            None => match self.fallback_label(&iri) {
+                Some(label) => {
+                    data_buffer.label_buffer.insert(iri, label);
                 }
-            }
+                None => {
+                    // Do not make a 'warn!'. This is allowed to happen (e.g. on blank nodes).
+                    debug!("Failed to derive a label from iri '{iri}'");
+                }
+            },
         };
     }
 
@@ -360,12 +497,7 @@ impl GraphDisplayDataSolutionSerializer {
             return;
         }
 
-        let new_type = if self.is_external(data_buffer, &triple.id) {
-            ElementType::Owl(OwlType::Node(OwlNode::ExternalClass))
-        } else {
-            node_type
-        };
-        self.add_to_element_buffer(&mut data_buffer.node_element_buffer, triple, new_type);
+        self.add_to_element_buffer(&mut data_buffer.node_element_buffer, triple, node_type);
         self.check_unknown_buffer(data_buffer, &triple.id.to_string());
     }
 
@@ -374,27 +506,23 @@ impl GraphDisplayDataSolutionSerializer {
     ///
     /// Note that tuples or any triple where the subject is an edge iri,
     /// not present in the element buffer, will NEVER be resolved!
+    ///
+    /// `property` is the IRI of the property drawn by the edge, if any.
     fn insert_edge(
         &self,
         data_buffer: &mut SerializationDataBuffer,
         triple: &Triple,
         edge_type: ElementType,
         label: Option<String>,
+        property: Option<String>,
     ) -> Option<Edge> {
-        // Skip external check for NoDraw edges - they should always retain their type
-        let new_type =
-            if edge_type != ElementType::NoDraw && self.is_external(data_buffer, &triple.id) {
-                ElementType::Owl(OwlType::Edge(OwlEdge::ExternalProperty))
-            } else {
-                edge_type
-            };
-
         match self.resolve_so(data_buffer, &triple) {
             (Some(sub_iri), Some(obj_iri)) => {
                 let edge = Edge {
                     subject: sub_iri.clone(),
-                    element_type: new_type,
+                    element_type: edge_type,
                     object: obj_iri.clone(),
+                    property,
                 };
                 data_buffer.edge_buffer.insert(edge.clone());
                 self.insert_edge_include(data_buffer, sub_iri, edge.clone());
@@ -402,7 +530,7 @@ impl GraphDisplayDataSolutionSerializer {
 
                 data_buffer
                     .edge_label_buffer
-                    .insert(edge.clone(), label.unwrap_or(new_type.to_string()));
+                    .insert(edge.clone(), label.unwrap_or(edge_type.to_string()));
                 return Some(edge);
             }
             (None, Some(_)) => {
@@ -422,561 +550,1867 @@ impl GraphDisplayDataSolutionSerializer {
         None
     }
 
-    fn is_external(&self, data_buffer: &SerializationDataBuffer, iri: &Term) -> bool {
-        !iri.is_blank_node()
-            && match &data_buffer.document_base {
-                Some(base) => !iri.to_string().starts_with(base),
-                None => {
-                    warn!("Cannot determine externals: Missing document base!");
-                    false
+    /// Draws a triple of a plain RDF or SKOS query, see [`VisualizationMode`].
+    ///
+    /// Every resource becomes a node, and every triple an edge labelled with its predicate.
+    /// Each literal gets its own node, while `rdf:type` links a resource to its class.
+    ///
+    /// The SKOS profile draws concepts and concept schemes as classes instead of linking them
+    /// to `skos:Concept`, with `skos:broader` pointing from a concept to its broader concept.
+    fn write_resource_triple(&self, data_buffer: &mut SerializationDataBuffer, triple: Triple) {
+        let Some(target) = triple.target.clone() else {
+            warn!("Skipping triple without an object: {}", triple);
+            return;
+        };
+        let Term::NamedNode(predicate) = &triple.element_type else {
+            return;
+        };
+        let resource = ElementType::Rdfs(RdfsType::Node(RdfsNode::Resource));
+        let class = ElementType::Rdfs(RdfsType::Node(RdfsNode::Class));
+        let skos = self.options.mode == VisualizationMode::Skos;
+        let is_type = predicate.as_ref() == rdf::TYPE;
+
+        if skos && is_type {
+            let node_type = if target == Term::NamedNode(skos::CONCEPT_SCHEME.into_owned()) {
+                class
+            } else {
+                ElementType::Owl(OwlType::Node(OwlNode::Class))
+            };
+            // A concept may already be drawn as a resource, e.g. as the object of `skos:broader`.
+            let id = triple.id.to_string();
+            if data_buffer.node_element_buffer.get(&id) == Some(&resource) {
+                data_buffer.node_element_buffer.remove(&id);
+            }
+            self.insert_node(data_buffer, &triple, node_type);
+            return;
+        }
+
+        if !data_buffer
+            .node_element_buffer
+            .contains_key(&triple.id.to_string())
+        {
+            self.insert_node(data_buffer, &triple, resource);
+        }
+        let object = match &target {
+            Term::Literal(literal) => self.insert_literal_node(data_buffer, literal),
+            _ => {
+                let object_type = if is_type { class } else { resource };
+                match data_buffer
+                    .node_element_buffer
+                    .get(&target.to_string())
+                    .copied()
+                {
+                    None => self.insert_node(
+                        data_buffer,
+                        &Triple::new(target.clone(), triple.element_type.clone(), None),
+                        object_type,
+                    ),
+                    // A resource turns out to be a class once something is an instance of it.
+                    Some(element_type) if element_type == resource && is_type => {
+                        data_buffer
+                            .node_element_buffer
+                            .insert(target.to_string(), class);
+                    }
+                    Some(_) => {}
                 }
+                target
             }
-    }
+        };
 
-    fn merge_nodes(&self, data_buffer: &mut SerializationDataBuffer, old: String, new: String) {
-        debug!("Merging node '{old}' into '{new}'");
-        data_buffer.node_element_buffer.remove(&old);
-        self.update_edges(data_buffer, &old, &new);
-        self.redirect_iri(data_buffer, &old, &new);
+        let property_iri = triple.element_type.to_string();
+        let (edge_type, label, property) = if is_type {
+            (
+                ElementType::Generic(GenericType::Edge(GenericEdge::Generic)),
+                "instance of".to_string(),
+                None,
+            )
+        } else if skos && predicate.as_ref() == skos::BROADER {
+            (
+                ElementType::Rdfs(RdfsType::Edge(RdfsEdge::SubclassOf)),
+                self.property_label(data_buffer, &property_iri),
+                Some(property_iri),
+            )
+        } else {
+            (
+                ElementType::Rdf(RdfType::Edge(RdfEdge::RdfProperty)),
+                self.property_label(data_buffer, &property_iri),
+                Some(property_iri),
+            )
+        };
+        self.insert_edge(
+            data_buffer,
+            &Triple::new(triple.id.clone(), triple.element_type.clone(), Some(object)),
+            edge_type,
+            Some(label),
+            property,
+        );
     }
 
-    fn update_edges(&self, data_buffer: &mut SerializationDataBuffer, old: &String, new: &String) {
-        let old_edges = data_buffer.edges_include_map.remove(old);
-        if let Some(old_edges) = old_edges {
-            debug!("Updating edges from '{}' to '{}'", old, new);
-            // info!("old_edges: ");
-            // for edge in old_edges.iter() {
-            //     info!("edge: {} ", edge);
-            // }
+    /// Draws the individuals and the assertions about them.
+    ///
+    /// Individuals are drawn as generic nodes, linked to their classes and to each other.
+    /// Object property assertions become edges between individuals,
+    /// while data property assertions become edges to literal nodes.
+    ///
+    /// Nothing is drawn if the individuals layer is disabled
+    /// or if there are more individuals than the configured limit.
+    ///
+    /// Returns the number of individuals not drawn because of the limit.
+    fn write_individuals(&self, data_buffer: &mut SerializationDataBuffer) -> usize {
+        info!("Individuals pass: Writing individuals");
 
-            for mut edge in old_edges.into_iter() {
-                data_buffer.edge_buffer.remove(&edge);
-                if edge.object == *old {
-                    edge.object = new.clone();
+        // Annotations are drawn by `write_annotations` instead.
+        let axioms = data_buffer
+            .annotation_buffer
+            .iter()
+            .map(|triple| triple.id.to_string())
+            .collect::<HashSet<_>>();
+        let (annotations, triples): (Vec<_>, Vec<_>) = take(&mut data_buffer.individual_buffer)
+            .into_iter()
+            .partition(|triple| {
+                axioms.contains(&triple.id.to_string())
+                    || self.is_annotation_property(data_buffer, &triple.element_type)
+            });
+        data_buffer.annotation_buffer.extend(annotations);
+        if !self.options.individual_layer {
+            debug!("Skipping {} assertions about individuals", triples.len());
+            return 0;
+        }
+
+        let declaration = Term::NamedNode(owl::NAMED_INDIVIDUAL.into_owned());
+        let (declarations, assertions): (Vec<_>, Vec<_>) = triples
+            .into_iter()
+            .partition(|triple| triple.element_type == declaration);
+        let individuals = declarations
+            .iter()
+            .map(|triple| triple.id.to_string())
+            .collect::<HashSet<_>>();
+        if individuals.len() > self.options.individual_limit {
+            info!(
+                "Skipping {} individuals, as they exceed the limit of {}",
+                individuals.len(),
+                self.options.individual_limit
+            );
+            return individuals.len();
+        }
+
+        for triple in declarations.iter() {
+            self.insert_node(
+                data_buffer,
+                triple,
+                ElementType::Generic(GenericType::Node(GenericNode::Generic)),
+            );
+        }
+
+        for triple in assertions {
+            let Term::NamedNode(uri) = &triple.element_type else {
+                continue;
+            };
+            let subject_iri = triple.id.to_string();
+            if !individuals.contains(&subject_iri) {
+                warn!("Visualization of term '{}' is not supported", uri);
+                continue;
+            }
+            let generic_edge = ElementType::Generic(GenericType::Edge(GenericEdge::Generic));
+            match uri.as_ref() {
+                rdf::TYPE => {
+                    self.insert_individual_edge(
+                        data_buffer,
+                        &triple,
+                        generic_edge,
+                        "instance of",
+                        None,
+                    );
                 }
-                if edge.subject == *old {
-                    edge.subject = new.clone();
+                owl::SAME_AS => {
+                    self.insert_individual_edge(
+                        data_buffer,
+                        &triple,
+                        generic_edge,
+                        "same as",
+                        None,
+                    );
+                }
+                owl::DIFFERENT_FROM => {
+                    self.insert_individual_edge(
+                        data_buffer,
+                        &triple,
+                        generic_edge,
+                        "different from",
+                        None,
+                    );
+                }
+                _ => {
+                    let property_iri = triple.element_type.to_string();
+                    let Some(property_type) =
+                        data_buffer.edge_element_buffer.get(&property_iri).copied()
+                    else {
+                        warn!("Visualization of term '{}' is not supported", uri);
+                        continue;
+                    };
+                    let label = data_buffer
+                        .label_buffer
+                        .get(&property_iri)
+                        .cloned()
+                        .or_else(|| self.fallback_label(&property_iri))
+                        .unwrap_or(property_type.to_string());
+
+                    let triple = match &triple.target {
+                        Some(Term::Literal(literal)) => Triple::new(
+                            triple.id.clone(),
+                            triple.element_type.clone(),
+                            Some(self.insert_literal_node(data_buffer, literal)),
+                        ),
+                        _ => triple,
+                    };
+                    self.insert_individual_edge(
+                        data_buffer,
+                        &triple,
+                        property_type,
+                        &label,
+                        Some(property_iri),
+                    );
                 }
-                data_buffer.edge_buffer.insert(edge.clone());
-                self.insert_edge_include(data_buffer, new.clone(), edge.clone());
             }
-            // info!("new_edges: ");
-            // for edge in data_buffer.edge_buffer.iter() {
-            //     info!("edge: {} ", edge);
-            // }
         }
+        0
     }
 
-    fn upgrade_node_type(
+    /// Returns whether `property` is an annotation property.
+    fn is_annotation_property(
         &self,
-        data_buffer: &mut SerializationDataBuffer,
-        iri: String,
-        new_element: ElementType,
-    ) {
-        let old_elem_opt = data_buffer.node_element_buffer.get(&iri).cloned();
-        match old_elem_opt {
-            Some(old_elem) => {
-                if old_elem == ElementType::Owl(OwlType::Node(OwlNode::Class)) {
-                    data_buffer
-                        .node_element_buffer
-                        .insert(iri.clone(), new_element);
-                }
-                debug!(
-                    "Upgraded subject '{}' from {} to {}",
-                    iri, old_elem, new_element
+        data_buffer: &SerializationDataBuffer,
+        property: &Term,
+    ) -> bool {
+        let Term::NamedNode(property) = property else {
+            return false;
+        };
+        matches!(property.as_ref(), rdfs::SEE_ALSO | rdfs::IS_DEFINED_BY)
+            || data_buffer
+                .annotation_properties
+                .contains(&property.to_string())
+    }
+
+    /// Draws annotations, if the annotation layer is enabled.
+    ///
+    /// Annotations with IRI values become edges from the annotated element to the value,
+    /// which is drawn as a resource unless it's already drawn.
+    /// Annotations of axioms become characteristics of the edges drawn for the axiom,
+    /// e.g. `comment: Added in version 2`.
+    fn write_annotations(&self, data_buffer: &mut SerializationDataBuffer) {
+        info!("Annotations pass: Writing annotations");
+
+        let triples = take(&mut data_buffer.annotation_buffer);
+        if !self.options.annotation_layer {
+            debug!("Skipping {} annotations", triples.len());
+            return;
+        }
+
+        let is_axiom_part = |triple: &Triple| {
+            matches!(
+                &triple.element_type,
+                Term::NamedNode(uri) if matches!(
+                    uri.as_ref(),
+                    owl::ANNOTATED_SOURCE | owl::ANNOTATED_PROPERTY | owl::ANNOTATED_TARGET
                 )
+            )
+        };
+        let (parts, annotations): (Vec<_>, Vec<_>) = triples.into_iter().partition(is_axiom_part);
+
+        // Maps each axiom to its annotated source, property and target.
+        let mut axioms: HashMap<String, [Option<String>; 3]> = HashMap::new();
+        for triple in parts {
+            let (Term::NamedNode(uri), Some(target)) = (&triple.element_type, &triple.target)
+            else {
+                continue;
+            };
+            let index = match uri.as_ref() {
+                owl::ANNOTATED_SOURCE => 0,
+                owl::ANNOTATED_PROPERTY => 1,
+                _ => 2,
+            };
+            axioms.entry(triple.id.to_string()).or_default()[index] = Some(target.to_string());
+        }
+
+        let mut axiom_annotations: HashMap<String, Vec<String>> = HashMap::new();
+        for triple in annotations {
+            let Some(target) = &triple.target else {
+                continue;
+            };
+            let subject = triple.id.to_string();
+            let property = triple.element_type.to_string();
+            if axioms.contains_key(&subject) {
+                let value = match target {
+                    Term::Literal(literal) => literal.value().to_string(),
+                    _ => self.property_label(data_buffer, &target.to_string()),
+                };
+                let annotation =
+                    format!("{}: {}", self.property_label(data_buffer, &property), value);
+                axiom_annotations
+                    .entry(subject)
+                    .or_default()
+                    .push(annotation);
+                continue;
             }
-            None => {
-                warn!("Upgraded unresolved subject '{}' to {}", iri, new_element)
+
+            let Some(subject) = self
+                .resolve(data_buffer, subject)
+                .filter(|subject| data_buffer.node_element_buffer.contains_key(subject))
+            else {
+                debug!(
+                    "Skipping annotation of an element which is not drawn: {}",
+                    triple
+                );
+                continue;
+            };
+            let value = target.to_string();
+            if !self
+                .resolve(data_buffer, value.clone())
+                .is_some_and(|value| data_buffer.node_element_buffer.contains_key(&value))
+            {
+                self.insert_node(
+                    data_buffer,
+                    &Triple::new(target.clone(), triple.element_type.clone(), None),
+                    ElementType::Rdfs(RdfsType::Node(RdfsNode::Resource)),
+                );
+            }
+            let label = self.property_label(data_buffer, &property);
+            self.insert_edge(
+                data_buffer,
+                &Triple::new(
+                    element_term(&subject),
+                    triple.element_type.clone(),
+                    Some(target.clone()),
+                ),
+                ElementType::Rdf(RdfType::Edge(RdfEdge::RdfProperty)),
+                Some(label),
+                Some(property),
+            );
+        }
+
+        for (axiom, annotations) in axiom_annotations {
+            let Some([Some(source), property, Some(target)]) = axioms.get(&axiom) else {
+                continue;
+            };
+            let source = self.resolve(data_buffer, source.clone());
+            let target = self.resolve(data_buffer, target.clone());
+            let edges = data_buffer
+                .edge_buffer
+                .iter()
+                .filter(|edge| {
+                    Some(&edge.subject) == source.as_ref()
+                        && Some(&edge.object) == target.as_ref()
+                        && edge
+                            .property
+                            .as_ref()
+                            .is_none_or(|p| Some(p) == property.as_ref())
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+            if edges.is_empty() {
+                debug!("Skipping annotations of axiom '{}' without an edge", axiom);
+            }
+            for edge in edges {
+                let characteristics = data_buffer.edge_characteristics.entry(edge).or_default();
+                for annotation in annotations.iter() {
+                    if !characteristics.contains(annotation) {
+                        characteristics.push(annotation.clone());
+                    }
+                }
             }
         }
     }
 
-    /// Appends a string to an element's label.
-    fn extend_element_label(
+    /// Inserts a literal node labelled with the value of `literal` and returns its id.
+    ///
+    /// The id is derived from `literal`, so equal literals share a node
+    /// and the same solutions are always serialized into the same graph.
+    fn insert_literal_node(
         &self,
         data_buffer: &mut SerializationDataBuffer,
-        element: String,
-        label_to_append: String,
-    ) {
-        debug!(
-            "Extending element '{}' with label '{}'",
-            element, label_to_append
+        literal: &Literal,
+    ) -> Term {
+        let mut hasher = DefaultHasher::new();
+        literal.hash(&mut hasher);
+        let value = Triple::new(
+            Term::BlankNode(BlankNode::new_from_unique_id(hasher.finish().into())),
+            Term::NamedNode(rdfs::LITERAL.into_owned()),
+            None,
         );
-        if let Some(label) = data_buffer.label_buffer.get_mut(&element) {
-            label.push_str(format!("\n{}", label_to_append).as_str());
-        } else {
+        if !data_buffer
+            .node_element_buffer
+            .contains_key(&value.id.to_string())
+        {
+            self.add_to_element_buffer(
+                &mut data_buffer.node_element_buffer,
+                &value,
+                ElementType::Rdfs(RdfsType::Node(RdfsNode::Literal)),
+            );
             data_buffer
                 .label_buffer
-                .insert(element.clone(), label_to_append.clone());
+                .insert(value.id.to_string(), literal.value().to_string());
         }
+        value.id
     }
 
-    fn create_node(
-        &self,
-        id: String,
-        node_type: NamedNode,
-        object_iri: Option<String>,
-    ) -> Result<Triple, IriParseError> {
-        let subject = NamedNode::new(id)?;
-        let object = match object_iri {
-            Some(iri) => {
-                let obj = NamedNode::new(iri)?;
-                Some(Term::NamedNode(obj))
-            }
-            None => None,
-        };
+    /// Draws enumerations (`owl:oneOf`) and value restrictions (`owl:hasValue`).
+    ///
+    /// An enumeration becomes a set operator node, linked to each of its members.
+    /// A value restriction gets an edge to its value, labelled with the restricted property.
+    /// Members and values that aren't drawn yet, e.g. because the individuals
+    /// exceed the limit, are drawn as generic nodes, while literals get their own node.
+    fn write_values(&self, data_buffer: &mut SerializationDataBuffer) {
+        info!("Values pass: Writing enumerations and value restrictions");
 
-        let t = Triple::new(Term::NamedNode(subject), Term::NamedNode(node_type), object);
-        debug!("Created new triple: {}", t);
-        Ok(t)
-    }
+        for triple in take(&mut data_buffer.value_buffer) {
+            let Some(value) = &triple.target else {
+                continue;
+            };
+            let value = match value {
+                Term::Literal(literal) => self.insert_literal_node(data_buffer, literal),
+                _ => {
+                    if self.resolve(data_buffer, value.to_string()).is_none() {
+                        self.insert_node(
+                            data_buffer,
+                            &Triple::new(value.clone(), triple.element_type.clone(), None),
+                            ElementType::Generic(GenericType::Node(GenericNode::Generic)),
+                        );
+                    }
+                    value.clone()
+                }
+            };
+            let is_literal = matches!(triple.target, Some(Term::Literal(_)));
+            let value_triple =
+                Triple::new(triple.id.clone(), triple.element_type.clone(), Some(value));
 
-    fn try_resolve_unknown_node(
-        &self,
-        node_element_buffer: &mut HashMap<String, ElementType>,
-        label_buffer: &mut HashMap<String, String>,
-        iri: &String,
-        suffix: String,
-    ) -> Result<Triple, String> {
-        match self.resolvable_iris.get(iri) {
-            Some((node_type, element_type)) => {
-                // Remove "<" and ">" from IRI.
-                let mut new_iri = iri[1..iri.len() - 1].to_string();
-                // Add unique identifier to IRI.
-                new_iri.push_str(suffix.as_str());
-                // Create a range node.
-                let triple = self
-                    .create_node(new_iri, node_type.clone(), None)
-                    .map_err(|e| format!("{:#?}", e))?;
-
-                label_buffer.insert(triple.id.to_string(), element_type.to_string());
-                self.add_to_element_buffer(node_element_buffer, &triple, *element_type);
-                debug!("Resolved unknown iri '{}' : {}", iri, element_type);
-                Ok(triple)
+            if triple.element_type == Term::NamedNode(owl::ONE_OF.into_owned()) {
+                // An enumeration {a, b} is the union of the singletons {a} and {b}.
+                let subject = match self.resolve(data_buffer, triple.id.to_string()) {
+                    Some(subject) => subject,
+                    None => {
+                        let node_type = if is_literal {
+                            ElementType::Rdfs(RdfsType::Node(RdfsNode::Datatype))
+                        } else {
+                            ElementType::Owl(OwlType::Node(OwlNode::AnonymousClass))
+                        };
+                        self.insert_node(
+                            data_buffer,
+                            &Triple::new(triple.id.clone(), triple.element_type.clone(), None),
+                            node_type,
+                        );
+                        triple.id.to_string()
+                    }
+                };
+                if matches!(
+                    data_buffer.node_element_buffer.get(&subject),
+                    Some(ElementType::Owl(OwlType::Node(
+                        OwlNode::Class | OwlNode::AnonymousClass
+                    )))
+                ) {
+                    data_buffer
+                        .node_element_buffer
+                        .insert(subject, ElementType::Owl(OwlType::Node(OwlNode::UnionOf)));
+                }
+                self.insert_individual_edge(
+                    data_buffer,
+                    &value_triple,
+                    ElementType::NoDraw,
+                    "one of",
+                    None,
+                );
+            } else {
+                let Some(property_iri) = data_buffer
+                    .restricted_properties
+                    .get(&triple.id.to_string())
+                    .cloned()
+                else {
+                    warn!("Value restriction '{}' has no property", triple.id);
+                    continue;
+                };
+                let property_type = data_buffer
+                    .edge_element_buffer
+                    .get(&property_iri)
+                    .copied()
+                    .unwrap_or(if is_literal {
+                        ElementType::Owl(OwlType::Edge(OwlEdge::DatatypeProperty))
+                    } else {
+                        ElementType::Owl(OwlType::Edge(OwlEdge::ObjectProperty))
+                    });
+                let label = data_buffer
+                    .label_buffer
+                    .get(&property_iri)
+                    .cloned()
+                    .or_else(|| self.fallback_label(&property_iri))
+                    .unwrap_or(property_type.to_string());
+                self.insert_individual_edge(
+                    data_buffer,
+                    &value_triple,
+                    property_type,
+                    &label,
+                    Some(property_iri),
+                );
             }
-            // Case 2.1.2: We're unable to resolve the range with the current serializer logic.
-            None => Err(format!("Cannot resolve '{}'", iri)),
         }
     }
 
-    /// Attempts to resolve edges which couldn't be mapped to a domain and/or range during serialization.
-    ///
-    /// If no domain and/or range is defined for a property, owl:Thing is used as domain and/or range.
-    /// EXCEPT datatype properties without a defined range. Here, rdfs:Literal is used as range instead.
+    /// Returns the label of the property `iri`, deriving one if necessary.
+    fn property_label(&self, data_buffer: &SerializationDataBuffer, iri: &str) -> String {
+        data_buffer
+            .label_buffer
+            .get(iri)
+            .cloned()
+            .or_else(|| self.fallback_label(iri))
+            .unwrap_or(iri.to_string())
+    }
+
+    /// Draws the relations between properties.
     ///
-    /// Procedure
-    /// ---------
-    /// If NOT rdfs:Datatype:
-    /// - Property missing domain OR range:
-    ///   - Create new owl:Thing for this property.
-    /// - Property missing domain AND range:
-    ///   - Create a new owl:Thing (if not created previously) and use this instance for ALL edges in this category.
+    /// The label of an equivalent property is appended in brackets
+    /// to the edges of a property, e.g. `knows [acquainted with]`.
     ///
-    /// If rdfs:Datatype:
-    /// - Property missing domain AND/OR range:
-    ///   - Create new rdfs:Literal for this property
-    fn try_resolve_unknown_edges(&self, data_buffer: &mut SerializationDataBuffer) {
-        info!("Second pass: Resolving unknown edges");
+    /// If the property hierarchy is enabled, properties are also drawn as nodes.
+    /// Equivalent properties share a node, and sub-properties point to their super-properties.
+    /// Properties which aren't declared, e.g. `owl:topObjectProperty`, are not drawn.
+    fn write_property_hierarchy(&self, data_buffer: &mut SerializationDataBuffer) {
+        info!("Property hierarchy pass: Writing the property hierarchy");
 
-        // Creates a node IRI based on the Procedure described in the docstring of this function.
-        let create_iri = |suffix: Option<String>, is_global: bool, edge_type| {
-            match edge_type {
-                ElementType::Owl(OwlType::Edge(OwlEdge::DatatypeProperty)) => {
-                    let mut iri = rdfs::LITERAL.as_str().to_string();
-                    match suffix {
-                        Some(suffix) => {
-                            iri.push_str(suffix.as_str());
-                            Ok((
-                                iri,
-                                rdfs::LITERAL,
-                                ElementType::Rdfs(RdfsType::Node(RdfsNode::Literal)),
-                            ))
-                        }
-                        None => Err(format!(
-                            "An IRI suffix is required when creating node type '{}'",
-                            iri
-                        )),
+        let triples = take(&mut data_buffer.property_hierarchy_buffer);
+        let equivalence = Term::NamedNode(owl::EQUIVALENT_PROPERTY.into_owned());
+        let (equivalences, sub_properties): (Vec<_>, Vec<_>) = triples
+            .into_iter()
+            .partition(|triple| triple.element_type == equivalence);
+
+        for triple in equivalences.iter() {
+            let Some(target) = &triple.target else {
+                continue;
+            };
+            let (subject, object) = (triple.id.to_string(), target.to_string());
+            for (property, equivalent) in [(&subject, &object), (&object, &subject)] {
+                let label = format!("[{}]", self.property_label(data_buffer, equivalent));
+                let edges = data_buffer
+                    .edge_buffer
+                    .iter()
+                    .filter(|edge| edge.property.as_ref() == Some(property))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                for edge in edges {
+                    if let Some(edge_label) = data_buffer.edge_label_buffer.get_mut(&edge) {
+                        edge_label.push_str(format!("\n{}", label).as_str());
                     }
                 }
-                _ => {
-                    let mut iri = owl::THING.as_str().to_string();
-                    let element = owl::THING;
-                    let element_type = ElementType::Owl(OwlType::Node(OwlNode::Thing));
-                    match (suffix, is_global) {
-                        //
-                        (Some(suffix), false) => {
-                            iri.push_str(suffix.as_str());
-                            Ok((iri, element, element_type))
-                        }
-                        (_, true) => Ok((iri, element, element_type)),
-                        _ => Err("Tried to create a local node with a global iri".to_string()),
-                    }
+            }
+        }
+
+        if !self.options.property_hierarchy {
+            return;
+        }
+
+        for triple in equivalences.iter().chain(sub_properties.iter()) {
+            for property in [Some(&triple.id), triple.target.as_ref()]
+                .into_iter()
+                .flatten()
+            {
+                let iri = property.to_string();
+                if data_buffer.node_element_buffer.contains_key(&iri) {
+                    continue;
+                }
+                let Some(property_type) = data_buffer.edge_element_buffer.get(&iri).copied() else {
+                    debug!("Skipping undeclared property '{}' in the hierarchy", iri);
+                    continue;
+                };
+                if !data_buffer.label_buffer.contains_key(&iri)
+                    && let Some(label) = self.fallback_label(&iri)
+                {
+                    data_buffer.label_buffer.insert(iri.clone(), label);
                 }
+                data_buffer.node_element_buffer.insert(iri, property_type);
             }
-        };
+        }
 
-        // Composes functions `create_iri` and `create_node`.
-        let create_node_from_suffix = |suffix: Option<String>, is_global: bool, edge_type| {
-            create_iri(suffix, is_global, edge_type).and_then(|(iri, node_type, element_type)| {
-                self.create_node(iri, node_type.into_owned(), None)
-                    .map(|triple| (triple, element_type))
-                    .map_err(|e: IriParseError| format!("{:#?}", e))
-            })
+        // Properties are also in the edge element buffer, so they resolve to themselves.
+        // Merged properties are therefore tracked here instead of being redirected.
+        let mut merged: HashMap<String, String> = HashMap::new();
+        let representative = |merged: &HashMap<String, String>, iri: String| {
+            let mut iri = iri;
+            while let Some(next) = merged.get(&iri) {
+                iri = next.clone();
+            }
+            iri
         };
 
-        for (i, (edge_iri, edge_type)) in data_buffer.edge_element_buffer.iter().enumerate() {
-            let resolved_edge = data_buffer.resolved_edge_map.get(edge_iri);
-            let unknown_edge = data_buffer.unknown_edge_buffer.get(edge_iri);
-            let unknown = data_buffer.unknown_buffer.get(edge_iri);
+        for triple in equivalences.iter() {
+            let Some(target) = &triple.target else {
+                continue;
+            };
+            let property = representative(&merged, triple.id.to_string());
+            let equivalent = representative(&merged, target.to_string());
+            if property == equivalent
+                || !data_buffer.node_element_buffer.contains_key(&property)
+                || !data_buffer.node_element_buffer.contains_key(&equivalent)
+            {
+                continue;
+            }
+            let label = format!("[{}]", self.property_label(data_buffer, &equivalent));
+            data_buffer.node_element_buffer.remove(&equivalent);
+            self.update_edges(data_buffer, &equivalent, &property);
+            self.extend_element_label(data_buffer, property.clone(), label);
+            merged.insert(equivalent, property);
+        }
 
-            match (resolved_edge, unknown_edge, unknown) {
-                // TODO: Handle case where the domain triple is missing from input file.
-                // Maybe compare size of domains and ranges maps.
+        for triple in sub_properties.iter() {
+            let Some(target) = &triple.target else {
+                continue;
+            };
+            let sub_property = representative(&merged, triple.id.to_string());
+            let super_property = representative(&merged, target.to_string());
+            if sub_property == super_property
+                || !data_buffer.node_element_buffer.contains_key(&sub_property)
+                || !data_buffer
+                    .node_element_buffer
+                    .contains_key(&super_property)
+            {
+                continue;
+            }
+            self.insert_edge(
+                data_buffer,
+                &Triple::new(
+                    element_term(&sub_property),
+                    triple.element_type.clone(),
+                    Some(element_term(&super_property)),
+                ),
+                ElementType::Rdfs(RdfsType::Edge(RdfsEdge::SubclassOf)),
+                Some("subproperty of".to_string()),
+                Some(rdfs::SUB_PROPERTY_OF.to_string()),
+            );
+        }
+    }
 
-                // TODO: Handle case where the range triple is missing from input file.
-                // Maybe compare size of domains and ranges maps.
+    /// Adds a characteristic to the edges of `property`, looked up in `property_edges`,
+    /// and to its node if the property hierarchy is drawn.
+    fn add_property_characteristic(
+        &self,
+        data_buffer: &mut SerializationDataBuffer,
+        property_edges: &HashMap<String, Vec<Edge>>,
+        property: &String,
+        characteristic: String,
+    ) {
+        for edge in property_edges.get(property).into_iter().flatten() {
+            data_buffer
+                .edge_characteristics
+                .entry(edge.clone())
+                .or_default()
+                .push(characteristic.clone());
+        }
+        if self.options.property_hierarchy && data_buffer.node_element_buffer.contains_key(property)
+        {
+            data_buffer
+                .node_characteristics
+                .entry(property.clone())
+                .or_default()
+                .push(characteristic);
+        }
+    }
 
-                // Case 1: Missing domain AND range.
-                (None, None, None) => {
-                    let domain = create_node_from_suffix(None, true, *edge_type);
-                    let range = create_node_from_suffix(None, true, *edge_type);
-                    match (&domain, &range) {
-                        (Ok((domain, domain_type)), Ok((range, range_type))) => {
-                            // We can resolve the edge now.
-                            self.add_to_element_buffer(
-                                &mut data_buffer.node_element_buffer,
-                                domain,
-                                *domain_type,
-                            );
-                            data_buffer
-                                .label_buffer
-                                .insert(domain.id.to_string(), domain_type.to_string());
-                            self.add_to_element_buffer(
-                                &mut data_buffer.node_element_buffer,
-                                range,
-                                *range_type,
-                            );
-                            data_buffer
-                                .label_buffer
-                                .insert(range.id.to_string(), range_type.to_string());
-                        }
-                        (Err(d), Err(r)) => {
-                            let d_msg = format!(
-                                "Failed to create global domain for edge '{}' : {}",
-                                edge_iri, d
-                            );
-                            let r_msg = format!(
-                                "Failed to create global range for edge '{}' : {}",
-                                edge_iri, r
-                            );
-                            data_buffer
-                                .failed_buffer
-                                .extend([(None, d_msg), (None, r_msg)]);
-                        }
-                        (Err(d), _) => {
-                            let d_msg = format!(
-                                "Failed to create global domain for edge '{}' : {}",
-                                edge_iri, d
-                            );
-                            data_buffer.failed_buffer.push((None, d_msg));
-                        }
-                        (_, Err(r)) => {
-                            let r_msg = format!(
-                                "Failed to create global range for edge '{}' : {}",
-                                edge_iri, r
-                            );
-                            data_buffer.failed_buffer.push((None, r_msg));
-                        }
-                    }
+    /// Draws property chains, disjoint properties and n-ary axioms.
+    ///
+    /// Property chains and disjoint properties become characteristics of the edges
+    /// of their properties, e.g. `chain: hasParent o hasBrother`.
+    /// If the property hierarchy is drawn, disjoint properties are linked instead.
+    ///
+    /// N-ary disjointness and difference axioms with more than two members are drawn
+    /// as a single hub linked to each drawn member, instead of an edge between every pair.
+    fn write_axioms(&self, data_buffer: &mut SerializationDataBuffer) {
+        info!("Axioms pass: Writing property chains and n-ary axioms");
+
+        let mut firsts: HashMap<String, String> = HashMap::new();
+        let mut rests: HashMap<String, String> = HashMap::new();
+        let mut inverses: HashMap<String, String> = HashMap::new();
+        let mut chains: Vec<(String, String)> = Vec::new();
+        let mut disjoint_properties: Vec<(String, String)> = Vec::new();
+        let mut axioms: Vec<(Term, NamedNode)> = Vec::new();
+        let mut members: HashMap<String, Vec<String>> = HashMap::new();
+        for triple in take(&mut data_buffer.axiom_buffer) {
+            let Term::NamedNode(uri) = &triple.element_type else {
+                continue;
+            };
+            let id = triple.id.to_string();
+            match (uri.as_ref(), &triple.target) {
+                (
+                    owl::ALL_DIFFERENT | owl::ALL_DISJOINT_CLASSES | owl::ALL_DISJOINT_PROPERTIES,
+                    _,
+                ) => {
+                    axioms.push((triple.id.clone(), uri.clone()));
+                }
+                (_, None) => {}
+                (rdf::FIRST, Some(target)) => {
+                    firsts.insert(id, target.to_string());
+                }
+                (rdf::REST, Some(target)) => {
+                    rests.insert(id, target.to_string());
                 }
+                (owl::INVERSE_OF, Some(target)) => {
+                    inverses.insert(id, target.to_string());
+                }
+                (owl::PROPERTY_CHAIN_AXIOM, Some(target)) => {
+                    chains.push((id, target.to_string()));
+                }
+                (owl::PROPERTY_DISJOINT_WITH, Some(target)) => {
+                    disjoint_properties.push((id, target.to_string()));
+                }
+                // The members of n-ary axioms.
+                (_, Some(target)) => members.entry(id).or_default().push(target.to_string()),
+            }
+        }
+        // The edges inserted below don't draw a property, so the index stays complete.
+        let property_edges = self.edges_by_property(data_buffer);
 
-                // Case 2: Unresolved domain OR range.
-                (None, Some(direction), None) => {
-                    // Clone domains/ranges as we're updating the map in the for-loop.
-                    let domains = direction.domains.clone();
-                    let ranges = direction.ranges.clone();
+        for (property, chain) in chains {
+            let mut links = Vec::new();
+            let mut cell = chain;
+            let mut visited = HashSet::new();
+            while visited.insert(cell.clone())
+                && let Some(link) = firsts.get(&cell)
+            {
+                links.push(match inverses.get(link) {
+                    Some(inverse) => {
+                        format!("inverse {}", self.property_label(data_buffer, inverse))
+                    }
+                    None => self.property_label(data_buffer, link),
+                });
+                let Some(rest) = rests.get(&cell) else {
+                    break;
+                };
+                cell = rest.clone();
+            }
+            self.add_property_characteristic(
+                data_buffer,
+                &property_edges,
+                &property,
+                format!("chain: {}", links.join(" o ")),
+            );
+        }
 
-                    for mut domain_triple in domains {
-                        match &domain_triple.target {
-                            Some(target_term) => {
-                                match self.resolve(data_buffer, target_term.to_string()) {
-                                    // Case 2.1: Domain is unresolved. Range is still unknown.
-                                    None => {
-                                        let target_iri = target_term.to_string();
-                                        let resolved = self.try_resolve_unknown_node(
-                                            &mut data_buffer.node_element_buffer,
-                                            &mut data_buffer.label_buffer,
-                                            &target_iri,
-                                            format!("_d{}", i),
-                                        );
-                                        match resolved {
-                                            // Case 2.1.1: We know how to resolve the domain.
-                                            // By adding the unresolved domain as element_type in a triple and calling
-                                            // write_node_triple() with it.
-                                            Ok(new_domain_triple) => {
-                                                // SAFETY: unwrap is safe because we checked beforehand.
-                                                // (it's impossible to reach this point if the value doesn't exist)
-                                                let update_direction = data_buffer
-                                                    .unknown_edge_buffer
-                                                    .get_mut(edge_iri)
-                                                    .unwrap();
+        for (property, disjoint) in disjoint_properties {
+            if self.options.property_hierarchy
+                && data_buffer.node_element_buffer.contains_key(&property)
+                && data_buffer.node_element_buffer.contains_key(&disjoint)
+            {
+                self.insert_edge(
+                    data_buffer,
+                    &Triple::new(
+                        element_term(&property),
+                        Term::NamedNode(owl::PROPERTY_DISJOINT_WITH.into_owned()),
+                        Some(element_term(&disjoint)),
+                    ),
+                    ElementType::Owl(OwlType::Edge(OwlEdge::DisjointWith)),
+                    None,
+                    None,
+                );
+                continue;
+            }
+            for (property, other) in [(&property, &disjoint), (&disjoint, &property)] {
+                let characteristic =
+                    format!("disjoint with: {}", self.property_label(data_buffer, other));
+                self.add_property_characteristic(
+                    data_buffer,
+                    &property_edges,
+                    property,
+                    characteristic,
+                );
+            }
+        }
 
-                                                // Update the IRI of the domain to the new domain's IRI.
-                                                // Step 1: Remove old domain triple.
-                                                update_direction.domains.remove(&domain_triple);
-                                                // Step 2: Override target of old triple.
-                                                domain_triple.target = Some(new_domain_triple.id);
-                                                // Step 3: Insert overridden old triple.
-                                                update_direction.domains.insert(domain_triple);
-                                            }
-                                            Err(e) => {
-                                                let msg = format!(
-                                                    "Failed to create local domain for edge '{}' : {}",
-                                                    edge_iri, e
-                                                );
-                                                data_buffer
-                                                    .failed_buffer
-                                                    .push((Some(domain_triple.clone()), msg));
-                                            }
-                                        }
-                                    }
-                                    // Case 2.2: We know the domain (do nothing here). Range must be unresolved or missing.
-                                    _ => {}
-                                }
-                            }
-                            // Case 2.3: A domain tuple is illegal. We must have a triple.
-                            None => {
-                                let msg = format!(
-                                    "Failed to create local domain for edge '{}' : object not found",
-                                    edge_iri
-                                );
-                                data_buffer
-                                    .failed_buffer
-                                    .push((Some(domain_triple.clone()), msg));
-                            }
-                        }
-                    }
+        for (axiom, kind) in axioms {
+            let members = members.remove(&axiom.to_string()).unwrap_or_default();
+            if kind == owl::ALL_DISJOINT_PROPERTIES && !self.options.property_hierarchy {
+                for property in members.iter() {
+                    let others = members
+                        .iter()
+                        .filter(|&other| other != property)
+                        .map(|other| self.property_label(data_buffer, other))
+                        .collect::<Vec<_>>();
+                    let characteristic = format!("disjoint with: {}", others.join(", "));
+                    self.add_property_characteristic(
+                        data_buffer,
+                        &property_edges,
+                        property,
+                        characteristic,
+                    );
+                }
+                continue;
+            }
 
-                    for mut range_triple in ranges {
-                        match &range_triple.target {
-                            Some(target_term) => {
-                                match self.resolve(data_buffer, target_term.to_string()) {
-                                    // Case 2.1: range is unresolved. Range is still unknown.
-                                    None => {
-                                        let target_iri = target_term.to_string();
-                                        let resolved = self.try_resolve_unknown_node(
-                                            &mut data_buffer.node_element_buffer,
-                                            &mut data_buffer.label_buffer,
-                                            &target_iri,
-                                            format!("_r{}", i),
-                                        );
-                                        match resolved {
-                                            Ok(new_range_triple) => {
-                                                // SAFETY: unwrap is safe because we checked beforehand.
-                                                // (it's impossible to reach this point if the value doesn't exist)
-                                                let update_direction = data_buffer
-                                                    .unknown_edge_buffer
-                                                    .get_mut(edge_iri)
-                                                    .unwrap();
+            // There is no element type for disjointness hubs, so they're flagged by their kind.
+            let (hub_type, hub_kind, hub_label, edge_type, edge_label) =
+                if kind == owl::ALL_DIFFERENT {
+                    (
+                        ElementType::Generic(GenericType::Node(GenericNode::Generic)),
+                        None,
+                        "all different",
+                        ElementType::Generic(GenericType::Edge(GenericEdge::Generic)),
+                        Some("different from".to_string()),
+                    )
+                } else {
+                    (
+                        ElementType::Rdfs(RdfsType::Node(RdfsNode::Resource)),
+                        Some(ElementKind::DisjointnessHub),
+                        "disjoint",
+                        ElementType::Owl(OwlType::Edge(OwlEdge::DisjointWith)),
+                        None,
+                    )
+                };
+            let mut drawn = Vec::new();
+            for member in members {
+                if let Some(node) = self.resolve(data_buffer, member)
+                    && data_buffer.node_element_buffer.contains_key(&node)
+                    && !drawn.contains(&node)
+                {
+                    drawn.push(node);
+                }
+            }
 
-                                                // Update the IRI of the range to the new range's IRI.
-                                                // Step 1: Remove old range triple.
-                                                update_direction.ranges.remove(&range_triple);
-                                                // Step 2: Override target of old triple.
-                                                range_triple.target = Some(new_range_triple.id);
-                                                // Step 3: Insert overridden old triple.
-                                                update_direction.ranges.insert(range_triple);
-                                            }
-                                            Err(e) => {
-                                                let msg = format!(
-                                                    "Failed to create local range for edge '{}' : {}",
-                                                    edge_iri, e
-                                                );
-                                                data_buffer
-                                                    .failed_buffer
-                                                    .push((Some(range_triple.clone()), msg));
-                                            }
-                                        }
-                                    }
-                                    // Case 2.2: We know the range (do nothing here). Range must be unresolved or missing.
-                                    _ => {}
-                                }
-                            }
-                            // Case 2.3: A range tuple is illegal. We must have a triple.
-                            None => {
-                                let msg = format!(
-                                    "Failed to create local range for edge '{}' : object not found",
-                                    edge_iri
-                                );
-                                data_buffer
-                                    .failed_buffer
-                                    .push((Some(range_triple.clone()), msg));
-                            }
-                        }
+            let kind = Term::NamedNode(kind);
+            match drawn.as_slice() {
+                [] | [_] => debug!(
+                    "Skipping axiom '{}' with less than two drawn members",
+                    axiom
+                ),
+                [subject, object] => {
+                    self.insert_edge(
+                        data_buffer,
+                        &Triple::new(element_term(subject), kind, Some(element_term(object))),
+                        edge_type,
+                        edge_label,
+                        None,
+                    );
+                }
+                _ => {
+                    let hub = Triple::new(axiom.clone(), kind.clone(), None);
+                    self.insert_node(data_buffer, &hub, hub_type);
+                    if let Some(hub_kind) = hub_kind {
+                        data_buffer.node_kinds.insert(axiom.to_string(), hub_kind);
+                    }
+                    data_buffer
+                        .label_buffer
+                        .insert(axiom.to_string(), hub_label.to_string());
+                    for member in drawn.iter() {
+                        self.insert_edge(
+                            data_buffer,
+                            &Triple::new(axiom.clone(), kind.clone(), Some(element_term(member))),
+                            edge_type,
+                            edge_label.clone(),
+                            None,
+                        );
                     }
                 }
+            }
+        }
+    }
 
-                // Case 3: Unresolved subject AND/OR object
-                (None, None, Some(triples)) => {
-                    // Clone as we're updating the set's values in the for-loop.
-                    for mut triple in triples.clone() {
-                        match self.resolve_so(data_buffer, &triple) {
-                            // Case 3.1: Unresolved subject AND object
-                            (None, None) => {
-                                // TODO
-                                data_buffer.failed_buffer.push((
-                                    Some(triple.clone()),
-                                    "Failed to resolve triple : Someone implement Case 3.1"
-                                        .to_string(),
-                                ));
-                            }
+    /// Returns whether `id` is drawn as a datatype.
+    fn is_datatype(&self, data_buffer: &SerializationDataBuffer, id: &Term) -> bool {
+        self.resolve(data_buffer, id.to_string())
+            .and_then(|iri| data_buffer.node_element_buffer.get(&iri))
+            == Some(&ElementType::Rdfs(RdfsType::Node(RdfsNode::Datatype)))
+    }
 
-                            // Case 3.2: Unresolved subject
-                            (None, Some(_)) => {
-                                let resolved = self.try_resolve_unknown_node(
-                                    &mut data_buffer.node_element_buffer,
-                                    &mut data_buffer.label_buffer,
-                                    &triple.id.to_string(),
-                                    format!("_s{}", i),
-                                );
-                                match resolved {
-                                    Ok(resolved_triple) => {
-                                        // SAFETY: unwrap is safe because we checked beforehand.
-                                        // (it's impossible to reach this point if the value doesn't exist)
-                                        let update_triples =
-                                            data_buffer.unknown_buffer.get_mut(edge_iri).unwrap();
+    /// Describes the definitions of datatypes in their labels.
+    ///
+    /// Definitions are written in the Manchester syntax,
+    /// e.g. `xsd:integer[> 10, < 20]`, `not owl:rational` or `owl:rational or owl:real`.
+    /// Anonymous datatypes which are only part of another definition are removed,
+    /// as they're already described by the label of that definition.
+    fn describe_datatypes(&self, data_buffer: &mut SerializationDataBuffer) {
+        info!("Datatypes pass: Describing datatypes");
 
-                                        // Update the IRI of the domain to the new domain's IRI.
-                                        // Step 1: Remove old domain triple.
-                                        update_triples.remove(&triple);
-                                        // Step 2: Override id of old triple.
-                                        triple.id = resolved_triple.id;
-                                        // Step 3: Insert overridden old triple.
-                                        update_triples.insert(triple);
-                                    }
-                                    Err(e) => {
-                                        let msg = format!("Failed to resolve subject : {}", e);
-                                        data_buffer.failed_buffer.push((Some(triple.clone()), msg));
-                                    }
-                                }
-                            }
+        let mut definitions: HashMap<String, DatatypeDefinition> = HashMap::new();
+        for triple in take(&mut data_buffer.datatype_buffer) {
+            let (Term::NamedNode(uri), Some(target)) = (&triple.element_type, &triple.target)
+            else {
+                continue;
+            };
+            let definition = definitions.entry(triple.id.to_string()).or_default();
+            let target_str = target.to_string();
+            match uri.as_ref() {
+                owl::EQUIVALENT_CLASS | owl::ON_DATATYPE => definition.base = Some(target_str),
+                owl::DATATYPE_COMPLEMENT_OF => definition.complement = Some(target_str),
+                owl::INTERSECTION_OF => definition.intersection.push(target_str),
+                owl::UNION_OF => definition.union.push(target_str),
+                facet => {
+                    let Some(index) = FACETS.iter().position(|&(iri, _)| iri == facet) else {
+                        warn!("Visualization of facet '{}' is not supported", facet);
+                        continue;
+                    };
+                    let value = match target {
+                        Term::Literal(literal) => literal.value().to_string(),
+                        _ => target_str,
+                    };
+                    definition.facets.push((index, value));
+                }
+            }
+        }
 
-                            // Case 3.3: Unresolved object
-                            (Some(_), None) => match &triple.target {
-                                Some(obj_term) => {
-                                    let resolved = self.try_resolve_unknown_node(
-                                        &mut data_buffer.node_element_buffer,
-                                        &mut data_buffer.label_buffer,
-                                        &obj_term.to_string(),
-                                        format!("_s{}", i),
-                                    );
-                                    match resolved {
-                                        Ok(resolved_triple) => {
-                                            // SAFETY: unwrap is safe because we checked beforehand.
-                                            // (it's impossible to reach this point if the value doesn't exist)
-                                            let update_triples = data_buffer
-                                                .unknown_buffer
-                                                .get_mut(edge_iri)
-                                                .unwrap();
+        let descriptions = definitions
+            .keys()
+            .map(|iri| {
+                let description =
+                    self.describe_datatype(data_buffer, &definitions, iri, &mut HashSet::new());
+                (iri.clone(), description)
+            })
+            .collect::<Vec<_>>();
 
-                                            // Update the IRI of the domain to the new domain's IRI.
-                                            // Step 1: Remove old domain triple.
-                                            update_triples.remove(&triple);
-                                            // Step 2: Override target of old triple.
-                                            triple.target = Some(resolved_triple.id);
-                                            // Step 3: Insert overridden old triple.
-                                            update_triples.insert(triple);
-                                        }
-                                        Err(e) => {
-                                            let msg = format!("Failed to resolve object : {}", e);
-                                            data_buffer
-                                                .failed_buffer
-                                                .push((Some(triple.clone()), msg));
-                                        }
-                                    }
-                                }
-                                None => {
-                                    data_buffer.failed_buffer.push((
-                                        Some(triple.clone()),
-                                        "Failed to resolve object : object not found".to_string(),
-                                    ));
-                                }
-                            },
-                            // Case 3.4: A triple in the unknown buffer was somehow resolvable (do nothing).
-                            (Some(_), Some(_)) => {}
-                        }
-                    }
-                }
+        let parts = definitions
+            .values()
+            .flat_map(|definition| {
+                definition
+                    .base
+                    .iter()
+                    .chain(definition.complement.iter())
+                    .chain(definition.intersection.iter())
+                    .chain(definition.union.iter())
+            })
+            .filter(|iri| iri.starts_with("_:"))
+            .collect::<HashSet<_>>();
 
-                _ => {
-                    warn!("Check if this should happen : {}", edge_iri);
-                }
+        for (iri, description) in descriptions {
+            let Some(node) = self.resolve(data_buffer, iri.clone()) else {
+                continue;
+            };
+            let has_edges = data_buffer
+                .edges_include_map
+                .get(&node)
+                .is_some_and(|edges| !edges.is_empty());
+            if node == iri && parts.contains(&iri) && !has_edges {
+                debug!("Removing datatype '{}' described by another datatype", iri);
+                data_buffer.node_element_buffer.remove(&iri);
+                continue;
+            }
+            // Keep the name of a named datatype in front of its definition.
+            if !node.starts_with("_:")
+                && !data_buffer.label_buffer.contains_key(&node)
+                && let Some(label) = self.fallback_label(&node)
+            {
+                data_buffer.label_buffer.insert(node.clone(), label);
             }
+            self.extend_element_label(data_buffer, node, description);
         }
     }
 
-    fn check_all_unknowns(&self, data_buffer: &mut SerializationDataBuffer) {
-        info!("Third pass: Resolving all possible unknowns");
-
-        let unknowns = take(&mut data_buffer.unknown_buffer);
-        for (_, triples) in unknowns {
-            for triple in triples {
-                self.write_node_triple(data_buffer, triple);
-            }
+    /// Describes the datatype `iri` in the Manchester syntax.
+    ///
+    /// Datatypes without a definition are described by their label.
+    /// `visited` guards against cyclic definitions.
+    fn describe_datatype(
+        &self,
+        data_buffer: &SerializationDataBuffer,
+        definitions: &HashMap<String, DatatypeDefinition>,
+        iri: &str,
+        visited: &mut HashSet<String>,
+    ) -> String {
+        let name = data_buffer
+            .label_buffer
+            .get(iri)
+            .cloned()
+            .or_else(|| self.fallback_label(iri))
+            .unwrap_or(iri.to_string());
+        let Some(definition) = definitions.get(iri) else {
+            return name;
+        };
+        if !visited.insert(iri.to_string()) {
+            return name;
         }
 
-        let unknown_edges = take(&mut data_buffer.unknown_edge_buffer);
-        for (_, directions) in unknown_edges {
-            for triple in directions.domains {
-                self.write_node_triple(data_buffer, triple);
+        // Nested definitions are parenthesized to keep the description unambiguous.
+        let mut describe = |part: &String| {
+            let description = self.describe_datatype(data_buffer, definitions, part, visited);
+            if definitions.contains_key(part) && description.contains(' ') {
+                format!("({})", description)
+            } else {
+                description
             }
-            for triple in directions.ranges {
-                self.write_node_triple(data_buffer, triple);
+        };
+
+        let mut descriptions = Vec::new();
+        let base = definition.base.as_ref().map(&mut describe);
+        if definition.facets.is_empty() {
+            descriptions.extend(base);
+        } else {
+            let mut facets = definition.facets.clone();
+            facets.sort();
+            let facets = facets
+                .into_iter()
+                .map(|(index, value)| match FACETS[index] {
+                    (xsd::PATTERN, symbol) => format!("{} \"{}\"", symbol, value),
+                    (_, symbol) => format!("{} {}", symbol, value),
+                })
+                .collect::<Vec<_>>();
+            descriptions.push(format!(
+                "{}[{}]",
+                base.unwrap_or_default(),
+                facets.join(", ")
+            ));
+        }
+        if let Some(complement) = &definition.complement {
+            descriptions.push(format!("not {}", describe(complement)));
+        }
+        for (members, operator) in [
+            (&definition.intersection, " and "),
+            (&definition.union, " or "),
+        ] {
+            if !members.is_empty() {
+                let mut members = members.iter().map(&mut describe).collect::<Vec<_>>();
+                members.sort();
+                descriptions.push(members.join(operator));
             }
         }
+        descriptions.join(" and ")
     }
 
-    /// Serialize a triple to `data_buffer`.
-    fn write_node_triple(&self, data_buffer: &mut SerializationDataBuffer, triple: Triple) {
-        // TODO: Collect errors and show to frontend
-        debug!("{}", triple);
-        match &triple.element_type {
-            Term::BlankNode(bnode) => {
-                // The query must never put blank nodes in the ?nodeType variable
-                let msg = format!(
-                    "Illegal blank node during serialization: '{}'",
-                    bnode.to_string()
+    /// Inserts an edge from an individual, if both of its ends are drawn.
+    fn insert_individual_edge(
+        &self,
+        data_buffer: &mut SerializationDataBuffer,
+        triple: &Triple,
+        edge_type: ElementType,
+        label: &str,
+        property: Option<String>,
+    ) {
+        match self.resolve_so(data_buffer, triple) {
+            (Some(_), Some(_)) => {
+                self.insert_edge(
+                    data_buffer,
+                    triple,
+                    edge_type,
+                    Some(label.to_string()),
+                    property,
                 );
-                data_buffer.failed_buffer.push((Some(triple), msg));
-                return;
             }
-            Term::Literal(literal) => {
-                // NOTE: Any string literal goes here, e.g. 'EquivalentClass'.
-                // That is, every BIND("someString" AS ?nodeType)
-                let value = literal.value();
-                match value {
-                    "blanknode" => {
-                        info!("Visualizing blank node: {}", triple.id);
-                        self.insert_node(
-                            data_buffer,
-                            &triple,
-                            ElementType::Owl(OwlType::Node(OwlNode::AnonymousClass)),
-                        );
-                    }
-                    &_ => {
-                        warn!("Visualization of literal '{value}' is not supported");
-                    }
-                }
+            _ => {
+                debug!("Skipping assertion with an unresolved end: {}", triple);
             }
-            Term::NamedNode(uri) => {
-                // NOTE: Only supports RDF 1.1
-                match uri.as_ref() {
-                    // ----------- RDF ----------- //
+        }
+    }
+
+    /// Replaces the type of `edge`, keeping its label, characteristics and references.
+    fn retype_edge(
+        &self,
+        data_buffer: &mut SerializationDataBuffer,
+        edge: &Edge,
+        element_type: ElementType,
+    ) {
+        let retyped = Edge {
+            element_type,
+            ..edge.clone()
+        };
+        if !data_buffer.edge_buffer.remove(edge) {
+            return;
+        }
+        data_buffer.edge_buffer.insert(retyped.clone());
+        if let Some(label) = data_buffer.edge_label_buffer.remove(edge) {
+            data_buffer.edge_label_buffer.insert(retyped.clone(), label);
+        }
+        if let Some(characteristics) = data_buffer.edge_characteristics.remove(edge) {
+            data_buffer
+                .edge_characteristics
+                .insert(retyped.clone(), characteristics);
+        }
+        for iri in [&edge.subject, &edge.object] {
+            if let Some(edges) = data_buffer.edges_include_map.get_mut(iri) {
+                edges.remove(edge);
+                edges.insert(retyped.clone());
+            }
+        }
+    }
+
+    /// Marks classes and properties outside the namespace of the visualized ontology as external.
+    ///
+    /// The namespace is derived from the ontology IRIs, version IRIs and document base IRIs.
+    /// See [`ExternalClassifier::new`] for how it's chosen.
+    fn mark_external_elements(&self, data_buffer: &mut SerializationDataBuffer) {
+        info!("External elements pass: Marking external elements");
+
+        let bases = [
+            data_buffer.ontology_iris.as_slice(),
+            self.base_iris.as_slice(),
+        ]
+        .concat();
+        let named_classes = data_buffer
+            .node_element_buffer
+            .iter()
+            .filter(|&(iri, element)| {
+                *element == ElementType::Owl(OwlType::Node(OwlNode::Class)) && iri.starts_with('<')
+            })
+            .map(|(iri, _)| iri.clone())
+            .collect::<Vec<_>>();
+        let properties = data_buffer
+            .edge_buffer
+            .iter()
+            .filter(|edge| {
+                matches!(
+                    edge.element_type,
+                    ElementType::Owl(OwlType::Edge(
+                        OwlEdge::ObjectProperty | OwlEdge::DatatypeProperty
+                    )) | ElementType::Rdf(RdfType::Edge(RdfEdge::RdfProperty))
+                )
+            })
+            .filter(|edge| edge.property.is_some())
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let classifier = ExternalClassifier::new(
+            &bases,
+            &data_buffer.imported_ontologies,
+            named_classes.iter().map(String::as_str).chain(
+                properties
+                    .iter()
+                    .filter_map(|edge| edge.property.as_deref()),
+            ),
+            &self.options,
+        );
+
+        for iri in named_classes {
+            if classifier.is_external(&iri) {
+                debug!("Marking class '{iri}' as external");
+                data_buffer
+                    .node_element_buffer
+                    .insert(iri, ElementType::Owl(OwlType::Node(OwlNode::ExternalClass)));
+            }
+        }
+        for edge in properties {
+            if edge
+                .property
+                .as_deref()
+                .is_some_and(|property| classifier.is_external(property))
+            {
+                debug!("Marking {edge} as external");
+                self.retype_edge(
+                    data_buffer,
+                    &edge,
+                    ElementType::Owl(OwlType::Edge(OwlEdge::ExternalProperty)),
+                );
+            }
+        }
+    }
+
+    /// Marks classes and properties in the deprecated element buffer as deprecated.
+    ///
+    /// Deprecation takes precedence over external elements.
+    /// The edges of deprecated elements are kept.
+    fn mark_deprecated_elements(&self, data_buffer: &mut SerializationDataBuffer) {
+        info!("Deprecated elements pass: Marking deprecated elements");
+
+        for iri in data_buffer.deprecated_elements.iter() {
+            let Some(element) = data_buffer.node_element_buffer.get_mut(iri) else {
+                continue;
+            };
+            if matches!(
+                element,
+                ElementType::Owl(OwlType::Node(OwlNode::Class | OwlNode::ExternalClass))
+                    | ElementType::Rdfs(RdfsType::Node(RdfsNode::Class))
+            ) {
+                debug!("Marking class '{iri}' as deprecated");
+                *element = ElementType::Owl(OwlType::Node(OwlNode::DeprecatedClass));
+            }
+        }
+
+        let deprecated_edges = data_buffer
+            .edge_buffer
+            .iter()
+            .filter(|edge| {
+                edge.property
+                    .as_ref()
+                    .is_some_and(|property| data_buffer.deprecated_elements.contains(property))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        for edge in deprecated_edges {
+            debug!("Marking {edge} as deprecated");
+            self.retype_edge(
+                data_buffer,
+                &edge,
+                ElementType::Owl(OwlType::Edge(OwlEdge::DeprecatedProperty)),
+            );
+        }
+    }
+
+    /// Returns the edges drawn for each property, keyed by the IRI of the property,
+    /// so passes looking up the edges of many properties don't scan all edges for each.
+    fn edges_by_property(
+        &self,
+        data_buffer: &SerializationDataBuffer,
+    ) -> HashMap<String, Vec<Edge>> {
+        let mut edges: HashMap<String, Vec<Edge>> = HashMap::new();
+        for edge in data_buffer.edge_buffer.iter() {
+            if let Some(property) = &edge.property {
+                edges
+                    .entry(property.clone())
+                    .or_default()
+                    .push(edge.clone());
+            }
+        }
+        edges
+    }
+
+    /// Flags the edges drawn from inferred triples, see [`GraphFlags::inferred`],
+    /// so they can be told apart from asserted ones.
+    ///
+    /// An inferred domain or range marks the edges of its property,
+    /// while other triples mark the edges between their subject and object.
+    fn mark_inferred_elements(&self, data_buffer: &mut SerializationDataBuffer) {
+        info!("Inference pass: Marking inferred elements");
+
+        let triples = take(&mut data_buffer.inferred_buffer);
+        if triples.is_empty() {
+            return;
+        }
+        let property_edges = self.edges_by_property(data_buffer);
+        let mut node_edges: HashMap<(&str, &str), Vec<&Edge>> = HashMap::new();
+        for edge in data_buffer.edge_buffer.iter() {
+            node_edges
+                .entry((edge.subject.as_str(), edge.object.as_str()))
+                .or_default()
+                .push(edge);
+        }
+        let has_label = |edge: &Edge, label: &str| {
+            data_buffer
+                .edge_label_buffer
+                .get(edge)
+                .is_some_and(|edge_label| edge_label == label)
+        };
+
+        let mut inferred_edges = HashSet::new();
+        for triple in triples {
+            let (Term::NamedNode(predicate), Some(target)) = (&triple.element_type, &triple.target)
+            else {
+                continue;
+            };
+            let (Some(subject), Some(object)) = (
+                self.resolve(data_buffer, triple.id.to_string()),
+                self.resolve(data_buffer, target.to_string()),
+            ) else {
+                continue;
+            };
+            let property = predicate.to_string();
+            let edges = match predicate.as_ref() {
+                rdfs::DOMAIN => property_edges
+                    .get(&subject)
+                    .into_iter()
+                    .flatten()
+                    .filter(|edge| edge.subject == object)
+                    .collect::<Vec<_>>(),
+                rdfs::RANGE => property_edges
+                    .get(&subject)
+                    .into_iter()
+                    .flatten()
+                    .filter(|edge| edge.object == object)
+                    .collect(),
+                _ => node_edges
+                    .get(&(subject.as_str(), object.as_str()))
+                    .into_iter()
+                    .flatten()
+                    .copied()
+                    .filter(|edge| match &edge.property {
+                        Some(edge_property) => *edge_property == property,
+                        // Edges which don't draw a property are told apart by type and label.
+                        None => match predicate.as_ref() {
+                            rdfs::SUB_CLASS_OF => {
+                                edge.element_type
+                                    == ElementType::Rdfs(RdfsType::Edge(RdfsEdge::SubclassOf))
+                            }
+                            rdf::TYPE => has_label(edge, "instance of"),
+                            owl::SAME_AS => has_label(edge, "same as"),
+                            _ => false,
+                        },
+                    })
+                    .collect(),
+            };
+            for edge in edges {
+                debug!("Marking {edge} as inferred");
+                inferred_edges.insert(edge.clone());
+            }
+        }
+        data_buffer.inferred_edges.extend(inferred_edges);
+    }
+
+    /// Highlights the elements affected by a diagnostic with its characteristic.
+    ///
+    /// Classes are highlighted on their node, properties on all their edges.
+    fn highlight_diagnostics(&self, data_buffer: &mut SerializationDataBuffer) {
+        info!("Diagnostics pass: Highlighting diagnostics");
+
+        if self.diagnostics.is_empty() {
+            return;
+        }
+        let property_edges = self.edges_by_property(data_buffer);
+        for diagnostic in self.diagnostics.iter() {
+            let characteristic = diagnostic.kind.characteristic().to_string();
+            for element in diagnostic.elements.iter() {
+                let Some(element) = self.resolve(data_buffer, element.clone()) else {
+                    continue;
+                };
+                debug!("Highlighting '{element}': {diagnostic}");
+                if data_buffer.node_element_buffer.contains_key(&element) {
+                    data_buffer
+                        .node_characteristics
+                        .entry(element.clone())
+                        .or_default()
+                        .push(characteristic.clone());
+                }
+                for edge in property_edges.get(&element).into_iter().flatten() {
+                    data_buffer
+                        .edge_characteristics
+                        .entry(edge.clone())
+                        .or_default()
+                        .push(characteristic.clone());
+                }
+            }
+        }
+    }
+
+    fn merge_nodes(&self, data_buffer: &mut SerializationDataBuffer, old: String, new: String) {
+        debug!("Merging node '{old}' into '{new}'");
+        data_buffer.node_element_buffer.remove(&old);
+        self.update_edges(data_buffer, &old, &new);
+        self.redirect_iri(data_buffer, &old, &new);
+    }
+
+    fn update_edges(&self, data_buffer: &mut SerializationDataBuffer, old: &String, new: &String) {
+        let old_edges = data_buffer.edges_include_map.remove(old);
+        if let Some(old_edges) = old_edges {
+            debug!("Updating edges from '{}' to '{}'", old, new);
+            // info!("old_edges: ");
+            // for edge in old_edges.iter() {
+            //     info!("edge: {} ", edge);
+            // }
+
+            for mut edge in old_edges.into_iter() {
+                data_buffer.edge_buffer.remove(&edge);
+                if edge.object == *old {
+                    edge.object = new.clone();
+                }
+                if edge.subject == *old {
+                    edge.subject = new.clone();
+                }
+                data_buffer.edge_buffer.insert(edge.clone());
+                self.insert_edge_include(data_buffer, new.clone(), edge.clone());
+            }
+            // info!("new_edges: ");
+            // for edge in data_buffer.edge_buffer.iter() {
+            //     info!("edge: {} ", edge);
+            // }
+        }
+    }
+
+    fn upgrade_node_type(
+        &self,
+        data_buffer: &mut SerializationDataBuffer,
+        iri: String,
+        new_element: ElementType,
+    ) {
+        let old_elem_opt = data_buffer.node_element_buffer.get(&iri).cloned();
+        match old_elem_opt {
+            Some(old_elem) => {
+                if old_elem == ElementType::Owl(OwlType::Node(OwlNode::Class)) {
+                    data_buffer
+                        .node_element_buffer
+                        .insert(iri.clone(), new_element);
+                }
+                debug!(
+                    "Upgraded subject '{}' from {} to {}",
+                    iri, old_elem, new_element
+                )
+            }
+            None => {
+                warn!("Upgraded unresolved subject '{}' to {}", iri, new_element)
+            }
+        }
+    }
+
+    /// Appends a string to an element's label.
+    fn extend_element_label(
+        &self,
+        data_buffer: &mut SerializationDataBuffer,
+        element: String,
+        label_to_append: String,
+    ) {
+        debug!(
+            "Extending element '{}' with label '{}'",
+            element, label_to_append
+        );
+        if let Some(label) = data_buffer.label_buffer.get_mut(&element) {
+            label.push_str(format!("\n{}", label_to_append).as_str());
+        } else {
+            data_buffer
+                .label_buffer
+                .insert(element.clone(), label_to_append.clone());
+        }
+    }
+
+    fn create_node(
+        &self,
+        id: String,
+        node_type: NamedNode,
+        object_iri: Option<String>,
+    ) -> Result<Triple, IriParseError> {
+        let subject = NamedNode::new(id)?;
+        let object = match object_iri {
+            Some(iri) => {
+                let obj = NamedNode::new(iri)?;
+                Some(Term::NamedNode(obj))
+            }
+            None => None,
+        };
+
+        let t = Triple::new(Term::NamedNode(subject), Term::NamedNode(node_type), object);
+        debug!("Created new triple: {}", t);
+        Ok(t)
+    }
+
+    fn try_resolve_unknown_node(
+        &self,
+        node_element_buffer: &mut HashMap<String, ElementType>,
+        label_buffer: &mut HashMap<String, String>,
+        iri: &String,
+        suffix: String,
+    ) -> Result<Triple, String> {
+        match self.resolvable_iris.get(iri) {
+            Some((node_type, element_type)) => {
+                // Remove "<" and ">" from IRI.
+                let mut new_iri = iri[1..iri.len() - 1].to_string();
+                // Add unique identifier to IRI.
+                new_iri.push_str(suffix.as_str());
+                // Create a range node.
+                let triple = self
+                    .create_node(new_iri, node_type.clone(), None)
+                    .map_err(|e| format!("{:#?}", e))?;
+
+                label_buffer.insert(triple.id.to_string(), element_type.to_string());
+                self.add_to_element_buffer(node_element_buffer, &triple, *element_type);
+                debug!("Resolved unknown iri '{}' : {}", iri, element_type);
+                Ok(triple)
+            }
+            // Case 2.1.2: We're unable to resolve the range with the current serializer logic.
+            None => Err(format!("Cannot resolve '{}'", iri)),
+        }
+    }
+
+    /// Attempts to resolve edges which couldn't be mapped to a domain and/or range during serialization.
+    ///
+    /// If no domain and/or range is defined for a property, owl:Thing is used as domain and/or range.
+    /// EXCEPT datatype properties without a defined range. Here, rdfs:Literal is used as range instead.
+    ///
+    /// Procedure
+    /// ---------
+    /// If NOT rdfs:Datatype:
+    /// - Property missing domain OR range:
+    ///   - Create new owl:Thing for this property.
+    /// - Property missing domain AND range:
+    ///   - Create a new owl:Thing (if not created previously) and use this instance for ALL edges in this category.
+    ///
+    /// If rdfs:Datatype:
+    /// - Property missing domain AND/OR range:
+    ///   - Create new rdfs:Literal for this property
+    fn try_resolve_unknown_edges(&self, data_buffer: &mut SerializationDataBuffer) {
+        info!("Unknown edges pass: Resolving unknown edges");
+
+        // Creates a node IRI based on the Procedure described in the docstring of this function.
+        let create_iri = |suffix: Option<String>, is_global: bool, edge_type| {
+            match edge_type {
+                ElementType::Owl(OwlType::Edge(OwlEdge::DatatypeProperty)) => {
+                    let mut iri = rdfs::LITERAL.as_str().to_string();
+                    match suffix {
+                        Some(suffix) => {
+                            iri.push_str(suffix.as_str());
+                            Ok((
+                                iri,
+                                rdfs::LITERAL,
+                                ElementType::Rdfs(RdfsType::Node(RdfsNode::Literal)),
+                            ))
+                        }
+                        None => Err(format!(
+                            "An IRI suffix is required when creating node type '{}'",
+                            iri
+                        )),
+                    }
+                }
+                _ => {
+                    let mut iri = owl::THING.as_str().to_string();
+                    let element = owl::THING;
+                    let element_type = ElementType::Owl(OwlType::Node(OwlNode::Thing));
+                    match (suffix, is_global) {
+                        //
+                        (Some(suffix), false) => {
+                            iri.push_str(suffix.as_str());
+                            Ok((iri, element, element_type))
+                        }
+                        (_, true) => Ok((iri, element, element_type)),
+                        _ => Err("Tried to create a local node with a global iri".to_string()),
+                    }
+                }
+            }
+        };
+
+        // Composes functions `create_iri` and `create_node`.
+        let create_node_from_suffix = |suffix: Option<String>, is_global: bool, edge_type| {
+            create_iri(suffix, is_global, edge_type).and_then(|(iri, node_type, element_type)| {
+                self.create_node(iri, node_type.into_owned(), None)
+                    .map(|triple| (triple, element_type))
+                    .map_err(|e: IriParseError| format!("{:#?}", e))
+            })
+        };
+
+        for (i, (edge_iri, edge_type)) in data_buffer.edge_element_buffer.iter().enumerate() {
+            let resolved_edge = data_buffer.resolved_edge_map.get(edge_iri);
+            let unknown_edge = data_buffer.unknown_edge_buffer.get(edge_iri);
+            let unknown = data_buffer.unknown_buffer.get(edge_iri);
+
+            match (resolved_edge, unknown_edge, unknown) {
+                // TODO: Handle case where the domain triple is missing from input file.
+                // Maybe compare size of domains and ranges maps.
+
+                // TODO: Handle case where the range triple is missing from input file.
+                // Maybe compare size of domains and ranges maps.
+
+                // Case 1: Missing domain AND range.
+                (None, None, None) => {
+                    let domain = create_node_from_suffix(None, true, *edge_type);
+                    let range = create_node_from_suffix(None, true, *edge_type);
+                    match (&domain, &range) {
+                        (Ok((domain, domain_type)), Ok((range, range_type))) => {
+                            // We can resolve the edge now.
+                            self.add_to_element_buffer(
+                                &mut data_buffer.node_element_buffer,
+                                domain,
+                                *domain_type,
+                            );
+                            data_buffer
+                                .label_buffer
+                                .insert(domain.id.to_string(), domain_type.to_string());
+                            self.add_to_element_buffer(
+                                &mut data_buffer.node_element_buffer,
+                                range,
+                                *range_type,
+                            );
+                            data_buffer
+                                .label_buffer
+                                .insert(range.id.to_string(), range_type.to_string());
+                        }
+                        (Err(d), Err(r)) => {
+                            let d_msg = format!(
+                                "Failed to create global domain for edge '{}' : {}",
+                                edge_iri, d
+                            );
+                            let r_msg = format!(
+                                "Failed to create global range for edge '{}' : {}",
+                                edge_iri, r
+                            );
+                            data_buffer
+                                .failed_buffer
+                                .extend([(None, d_msg), (None, r_msg)]);
+                        }
+                        (Err(d), _) => {
+                            let d_msg = format!(
+                                "Failed to create global domain for edge '{}' : {}",
+                                edge_iri, d
+                            );
+                            data_buffer.failed_buffer.push((None, d_msg));
+                        }
+                        (_, Err(r)) => {
+                            let r_msg = format!(
+                                "Failed to create global range for edge '{}' : {}",
+                                edge_iri, r
+                            );
+                            data_buffer.failed_buffer.push((None, r_msg));
+                        }
+                    }
+                }
+
+                // Case 2: Unresolved domain OR range.
+                (None, Some(direction), None) => {
+                    // Clone domains/ranges as we're updating the map in the for-loop.
+                    let domains = direction.domains.clone();
+                    let ranges = direction.ranges.clone();
+
+                    for mut domain_triple in domains {
+                        match &domain_triple.target {
+                            Some(target_term) => {
+                                match self.resolve(data_buffer, target_term.to_string()) {
+                                    // Case 2.1: Domain is unresolved. Range is still unknown.
+                                    None => {
+                                        let target_iri = target_term.to_string();
+                                        let resolved = self.try_resolve_unknown_node(
+                                            &mut data_buffer.node_element_buffer,
+                                            &mut data_buffer.label_buffer,
+                                            &target_iri,
+                                            format!("_d{}", i),
+                                        );
+                                        match resolved {
+                                            // Case 2.1.1: We know how to resolve the domain.
+                                            // By adding the unresolved domain as element_type in a triple and calling
+                                            // write_node_triple() with it.
+                                            Ok(new_domain_triple) => {
+                                                // SAFETY: unwrap is safe because we checked beforehand.
+                                                // (it's impossible to reach this point if the value doesn't exist)
+                                                let update_direction = data_buffer
+                                                    .unknown_edge_buffer
+                                                    .get_mut(edge_iri)
+                                                    .unwrap();
+
+                                                // Update the IRI of the domain to the new domain's IRI.
+                                                // Step 1: Remove old domain triple.
+                                                update_direction.domains.remove(&domain_triple);
+                                                // Step 2: Override target of old triple.
+                                                domain_triple.target = Some(new_domain_triple.id);
+                                                // Step 3: Insert overridden old triple.
+                                                update_direction.domains.insert(domain_triple);
+                                            }
+                                            Err(e) => {
+                                                let msg = format!(
+                                                    "Failed to create local domain for edge '{}' : {}",
+                                                    edge_iri, e
+                                                );
+                                                data_buffer
+                                                    .failed_buffer
+                                                    .push((Some(domain_triple.clone()), msg));
+                                            }
+                                        }
+                                    }
+                                    // Case 2.2: We know the domain (do nothing here). Range must be unresolved or missing.
+                                    _ => {}
+                                }
+                            }
+                            // Case 2.3: A domain tuple is illegal. We must have a triple.
+                            None => {
+                                let msg = format!(
+                                    "Failed to create local domain for edge '{}' : object not found",
+                                    edge_iri
+                                );
+                                data_buffer
+                                    .failed_buffer
+                                    .push((Some(domain_triple.clone()), msg));
+                            }
+                        }
+                    }
+
+                    for mut range_triple in ranges {
+                        match &range_triple.target {
+                            Some(target_term) => {
+                                match self.resolve(data_buffer, target_term.to_string()) {
+                                    // Case 2.1: range is unresolved. Range is still unknown.
+                                    None => {
+                                        let target_iri = target_term.to_string();
+                                        let resolved = self.try_resolve_unknown_node(
+                                            &mut data_buffer.node_element_buffer,
+                                            &mut data_buffer.label_buffer,
+                                            &target_iri,
+                                            format!("_r{}", i),
+                                        );
+                                        match resolved {
+                                            Ok(new_range_triple) => {
+                                                // SAFETY: unwrap is safe because we checked beforehand.
+                                                // (it's impossible to reach this point if the value doesn't exist)
+                                                let update_direction = data_buffer
+                                                    .unknown_edge_buffer
+                                                    .get_mut(edge_iri)
+                                                    .unwrap();
+
+                                                // Update the IRI of the range to the new range's IRI.
+                                                // Step 1: Remove old range triple.
+                                                update_direction.ranges.remove(&range_triple);
+                                                // Step 2: Override target of old triple.
+                                                range_triple.target = Some(new_range_triple.id);
+                                                // Step 3: Insert overridden old triple.
+                                                update_direction.ranges.insert(range_triple);
+                                            }
+                                            Err(e) => {
+                                                let msg = format!(
+                                                    "Failed to create local range for edge '{}' : {}",
+                                                    edge_iri, e
+                                                );
+                                                data_buffer
+                                                    .failed_buffer
+                                                    .push((Some(range_triple.clone()), msg));
+                                            }
+                                        }
+                                    }
+                                    // Case 2.2: We know the range (do nothing here). Range must be unresolved or missing.
+                                    _ => {}
+                                }
+                            }
+                            // Case 2.3: A range tuple is illegal. We must have a triple.
+                            None => {
+                                let msg = format!(
+                                    "Failed to create local range for edge '{}' : object not found",
+                                    edge_iri
+                                );
+                                data_buffer
+                                    .failed_buffer
+                                    .push((Some(range_triple.clone()), msg));
+                            }
+                        }
+                    }
+                }
+
+                // Case 3: Unresolved subject AND/OR object
+                (None, None, Some(triples)) => {
+                    // Clone as we're updating the set's values in the for-loop.
+                    for mut triple in triples.clone() {
+                        match self.resolve_so(data_buffer, &triple) {
+                            // Case 3.1: Unresolved subject AND object
+                            (None, None) => {
+                                // TODO
+                                data_buffer.failed_buffer.push((
+                                    Some(triple.clone()),
+                                    "Failed to resolve triple : Someone implement Case 3.1"
+                                        .to_string(),
+                                ));
+                            }
+
+                            // Case 3.2: Unresolved subject
+                            (None, Some(_)) => {
+                                let resolved = self.try_resolve_unknown_node(
+                                    &mut data_buffer.node_element_buffer,
+                                    &mut data_buffer.label_buffer,
+                                    &triple.id.to_string(),
+                                    format!("_s{}", i),
+                                );
+                                match resolved {
+                                    Ok(resolved_triple) => {
+                                        // SAFETY: unwrap is safe because we checked beforehand.
+                                        // (it's impossible to reach this point if the value doesn't exist)
+                                        let update_triples =
+                                            data_buffer.unknown_buffer.get_mut(edge_iri).unwrap();
+
+                                        // Update the IRI of the domain to the new domain's IRI.
+                                        // Step 1: Remove old domain triple.
+                                        update_triples.remove(&triple);
+                                        // Step 2: Override id of old triple.
+                                        triple.id = resolved_triple.id;
+                                        // Step 3: Insert overridden old triple.
+                                        update_triples.insert(triple);
+                                    }
+                                    Err(e) => {
+                                        let msg = format!("Failed to resolve subject : {}", e);
+                                        data_buffer.failed_buffer.push((Some(triple.clone()), msg));
+                                    }
+                                }
+                            }
+
+                            // Case 3.3: Unresolved object
+                            (Some(_), None) => match &triple.target {
+                                Some(obj_term) => {
+                                    let resolved = self.try_resolve_unknown_node(
+                                        &mut data_buffer.node_element_buffer,
+                                        &mut data_buffer.label_buffer,
+                                        &obj_term.to_string(),
+                                        format!("_s{}", i),
+                                    );
+                                    match resolved {
+                                        Ok(resolved_triple) => {
+                                            // SAFETY: unwrap is safe because we checked beforehand.
+                                            // (it's impossible to reach this point if the value doesn't exist)
+                                            let update_triples = data_buffer
+                                                .unknown_buffer
+                                                .get_mut(edge_iri)
+                                                .unwrap();
+
+                                            // Update the IRI of the domain to the new domain's IRI.
+                                            // Step 1: Remove old domain triple.
+                                            update_triples.remove(&triple);
+                                            // Step 2: Override target of old triple.
+                                            triple.target = Some(resolved_triple.id);
+                                            // Step 3: Insert overridden old triple.
+                                            update_triples.insert(triple);
+                                        }
+                                        Err(e) => {
+                                            let msg = format!("Failed to resolve object : {}", e);
+                                            data_buffer
+                                                .failed_buffer
+                                                .push((Some(triple.clone()), msg));
+                                        }
+                                    }
+                                }
+                                None => {
+                                    data_buffer.failed_buffer.push((
+                                        Some(triple.clone()),
+                                        "Failed to resolve object : object not found".to_string(),
+                                    ));
+                                }
+                            },
+                            // Case 3.4: A triple in the unknown buffer was somehow resolvable (do nothing).
+                            (Some(_), Some(_)) => {}
+                        }
+                    }
+                }
+
+                _ => {
+                    warn!("Check if this should happen : {}", edge_iri);
+                }
+            }
+        }
+    }
+
+    fn check_all_unknowns(&self, data_buffer: &mut SerializationDataBuffer) {
+        info!("Unknowns pass: Resolving all possible unknowns");
+
+        let unknowns = take(&mut data_buffer.unknown_buffer);
+        for (_, triples) in unknowns {
+            for triple in triples {
+                self.write_node_triple(data_buffer, triple);
+            }
+        }
+
+        let unknown_edges = take(&mut data_buffer.unknown_edge_buffer);
+        for (_, directions) in unknown_edges {
+            for triple in directions.domains {
+                self.write_node_triple(data_buffer, triple);
+            }
+            for triple in directions.ranges {
+                self.write_node_triple(data_buffer, triple);
+            }
+        }
+    }
+
+    /// Serialize a triple to `data_buffer`.
+    fn write_node_triple(&self, data_buffer: &mut SerializationDataBuffer, triple: Triple) {
+        // TODO: Collect errors and show to frontend
+        debug!("{}", triple);
+        match &triple.element_type {
+            Term::BlankNode(bnode) => {
+                // The query must never put blank nodes in the ?nodeType variable
+                let msg = format!(
+                    "Illegal blank node during serialization: '{}'",
+                    bnode.to_string()
+                );
+                data_buffer.failed_buffer.push((Some(triple), msg));
+                return;
+            }
+            Term::Literal(literal) => {
+                // NOTE: Any string literal goes here, e.g. 'EquivalentClass'.
+                // That is, every BIND("someString" AS ?nodeType)
+                let value = literal.value();
+                match value {
+                    "blanknode" => {
+                        info!("Visualizing blank node: {}", triple.id);
+                        self.insert_node(
+                            data_buffer,
+                            &triple,
+                            ElementType::Owl(OwlType::Node(OwlNode::AnonymousClass)),
+                        );
+                    }
+                    &_ => {
+                        warn!("Visualization of literal '{value}' is not supported");
+                    }
+                }
+            }
+            Term::NamedNode(uri) => {
+                // NOTE: Only supports RDF 1.1
+                match uri.as_ref() {
+                    // ----------- RDF ----------- //
 
                     // rdf::ALT => {}
                     // rdf::BAG => {}
-                    // rdf::FIRST => {}
+                    rdf::FIRST => data_buffer.axiom_buffer.push(triple),
                     // rdf::HTML => {}
                     // rdf::LANG_STRING => {}
                     // rdf::LIST => {}
@@ -989,13 +2423,17 @@ impl GraphDisplayDataSolutionSerializer {
                             &triple,
                             ElementType::Rdf(RdfType::Edge(RdfEdge::RdfProperty)),
                             None,
+                            Some(triple.id.to_string()),
                         );
                     }
-                    // rdf::REST => {}
+                    rdf::REST => data_buffer.axiom_buffer.push(triple),
                     // rdf::SEQ => {}
                     // rdf::STATEMENT => {}
                     // rdf::SUBJECT => {}
-                    // rdf::TYPE => {}
+                    rdf::TYPE => {
+                        // Class assertions of individuals.
+                        data_buffer.individual_buffer.push(triple);
+                    }
                     // rdf::VALUE => {}
                     // rdf::XML_LITERAL => {}
 
@@ -1077,6 +2515,7 @@ impl GraphDisplayDataSolutionSerializer {
                                                                 &edge_triple,
                                                                 element_type,
                                                                 wrapped_label.clone(),
+                                                                Some(subj_iri.clone()),
                                                             );
                                                         }
                                                         (None, _) => {
@@ -1133,7 +2572,7 @@ impl GraphDisplayDataSolutionSerializer {
                         }
                     }
 
-                    // rdfs::IS_DEFINED_BY => {}
+                    rdfs::IS_DEFINED_BY => data_buffer.annotation_buffer.push(triple),
                     // rdfs::LABEL => {}
                     rdfs::LITERAL => {
                         self.insert_node(
@@ -1205,6 +2644,7 @@ impl GraphDisplayDataSolutionSerializer {
                                                                 &edge_triple,
                                                                 element_type,
                                                                 wrapped_label.clone(),
+                                                                Some(subj_iri.clone()),
                                                             );
                                                         }
                                                         (None, _) => {
@@ -1267,28 +2707,32 @@ impl GraphDisplayDataSolutionSerializer {
                             ElementType::Rdfs(RdfsType::Node(RdfsNode::Resource)),
                         );
                     }
-                    // rdfs::SEE_ALSO => {}
+                    rdfs::SEE_ALSO => data_buffer.annotation_buffer.push(triple),
                     rdfs::SUB_CLASS_OF => {
                         self.insert_edge(
                             data_buffer,
                             &triple,
                             ElementType::Rdfs(RdfsType::Edge(RdfsEdge::SubclassOf)),
                             None,
+                            None,
                         );
                     }
-                    // rdfs::SUB_PROPERTY_OF => {},
+                    rdfs::SUB_PROPERTY_OF => data_buffer.property_hierarchy_buffer.push(triple),
 
                     // ----------- OWL 2 ----------- //
-
-                    // owl::ALL_DIFFERENT => {},
-                    // owl::ALL_DISJOINT_CLASSES => {},
-                    // owl::ALL_DISJOINT_PROPERTIES => {},
+                    owl::ALL_DIFFERENT
+                    | owl::ALL_DISJOINT_CLASSES
+                    | owl::ALL_DISJOINT_PROPERTIES => data_buffer.axiom_buffer.push(triple),
                     // owl::ALL_VALUES_FROM => {}
-                    // owl::ANNOTATED_PROPERTY => {},
-                    // owl::ANNOTATED_SOURCE => {},
-                    // owl::ANNOTATED_TARGET => {},
+                    owl::ANNOTATED_PROPERTY | owl::ANNOTATED_SOURCE | owl::ANNOTATED_TARGET => {
+                        data_buffer.annotation_buffer.push(triple)
+                    }
                     // owl::ANNOTATION => {},
-                    // owl::ANNOTATION_PROPERTY => {},
+                    owl::ANNOTATION_PROPERTY => {
+                        data_buffer
+                            .annotation_properties
+                            .insert(triple.id.to_string());
+                    }
                     // owl::ASSERTION_PROPERTY => {},
                     // owl::ASYMMETRIC_PROPERTY => {},
                     // owl::AXIOM => {},
@@ -1302,7 +2746,7 @@ impl GraphDisplayDataSolutionSerializer {
                         ElementType::Owl(OwlType::Node(OwlNode::Class)),
                     ),
                     owl::COMPLEMENT_OF => {
-                        self.insert_edge(data_buffer, &triple, ElementType::NoDraw, None);
+                        self.insert_edge(data_buffer, &triple, ElementType::NoDraw, None, None);
                         if let Some(_) = triple.target {
                             if let Some(index) = self.resolve(data_buffer, triple.id.to_string()) {
                                 self.upgrade_node_type(
@@ -1313,33 +2757,48 @@ impl GraphDisplayDataSolutionSerializer {
                             }
                         }
                     }
-                    // TODO owl::DATATYPE_COMPLEMENT_OF => {}
+                    owl::DATATYPE_COMPLEMENT_OF => data_buffer.datatype_buffer.push(triple),
                     owl::DATATYPE_PROPERTY => {
-                        self.insert_edge(
-                            data_buffer,
+                        let e = ElementType::Owl(OwlType::Edge(OwlEdge::DatatypeProperty));
+                        self.add_to_element_buffer(
+                            &mut data_buffer.edge_element_buffer,
                             &triple,
-                            ElementType::Owl(OwlType::Edge(OwlEdge::DatatypeProperty)),
-                            None,
+                            e,
                         );
                     }
                     // owl::DATA_RANGE => {}
-                    // owl::DEPRECATED => {}
-                    owl::DEPRECATED_CLASS => self.insert_node(
-                        data_buffer,
-                        &triple,
-                        ElementType::Owl(OwlType::Node(OwlNode::DeprecatedClass)),
-                    ),
-                    owl::DEPRECATED_PROPERTY => {
-                        self.insert_edge(
+                    owl::DEPRECATED => {
+                        // The query only matches `owl:deprecated true`.
+                        data_buffer
+                            .deprecated_elements
+                            .insert(triple.id.to_string());
+                    }
+                    owl::DEPRECATED_CLASS => {
+                        // The class may already be registered with another type.
+                        data_buffer
+                            .deprecated_elements
+                            .insert(triple.id.to_string());
+                        self.insert_node(
                             data_buffer,
                             &triple,
+                            ElementType::Owl(OwlType::Node(OwlNode::DeprecatedClass)),
+                        );
+                    }
+                    owl::DEPRECATED_PROPERTY => {
+                        // The edges of the property are created from its domain and range.
+                        // If it's registered with another type, it's marked as deprecated later.
+                        data_buffer
+                            .deprecated_elements
+                            .insert(triple.id.to_string());
+                        self.add_to_element_buffer(
+                            &mut data_buffer.edge_element_buffer,
+                            &triple,
                             ElementType::Owl(OwlType::Edge(OwlEdge::DeprecatedProperty)),
-                            None,
                         );
                     }
-                    // owl::DIFFERENT_FROM => {}
+                    owl::DIFFERENT_FROM => data_buffer.individual_buffer.push(triple),
                     owl::DISJOINT_UNION_OF => {
-                        self.insert_edge(data_buffer, &triple, ElementType::NoDraw, None);
+                        self.insert_edge(data_buffer, &triple, ElementType::NoDraw, None, None);
                         if let Some(_) = triple.target {
                             if let Some(index) = self.resolve(data_buffer, triple.id.to_string()) {
                                 self.upgrade_node_type(
@@ -1356,10 +2815,19 @@ impl GraphDisplayDataSolutionSerializer {
                             &triple,
                             ElementType::Owl(OwlType::Edge(OwlEdge::DisjointWith)),
                             None,
+                            None,
                         );
                     }
-                    // owl::DISTINCT_MEMBERS => {}
+                    owl::DISTINCT_MEMBERS => data_buffer.axiom_buffer.push(triple),
                     owl::EQUIVALENT_CLASS => {
+                        // A datatype defined as another datatype, e.g. `DatatypeDefinition(:D owl:real)`.
+                        if let Some(target @ Term::NamedNode(_)) = &triple.target
+                            && self.is_datatype(data_buffer, &triple.id)
+                            && self.resolve(data_buffer, target.to_string()).is_none()
+                        {
+                            data_buffer.datatype_buffer.push(triple);
+                            return;
+                        }
                         match &triple.target {
                             Some(target) => {
                                 if target.is_named_node() {
@@ -1456,16 +2924,37 @@ impl GraphDisplayDataSolutionSerializer {
                             }
                         }
                     }
-                    // owl::EQUIVALENT_PROPERTY => {}
+                    owl::EQUIVALENT_PROPERTY => data_buffer.property_hierarchy_buffer.push(triple),
                     // TODO owl::FUNCTIONAL_PROPERTY => {}
                     // owl::HAS_KEY => {}
                     // owl::HAS_SELF => {}
-                    // owl::HAS_VALUE => {}
-                    // owl::IMPORTS => {}
+                    owl::HAS_VALUE => {
+                        // The restriction is drawn as an anonymous class,
+                        // unless it's already merged into an equivalent class.
+                        if self.resolve(data_buffer, triple.id.to_string()).is_none() {
+                            self.insert_node(
+                                data_buffer,
+                                &Triple::new(triple.id.clone(), triple.element_type.clone(), None),
+                                ElementType::Owl(OwlType::Node(OwlNode::AnonymousClass)),
+                            );
+                        }
+                        data_buffer.value_buffer.push(triple);
+                    }
+                    owl::IMPORTS => {
+                        if let Some(import) = &triple.target {
+                            info!("Found imported ontology: '{}'", import);
+                            data_buffer.imported_ontologies.push(import.to_string());
+                        }
+                    }
                     // owl::INCOMPATIBLE_WITH => {}
+                    owl::INTERSECTION_OF | owl::UNION_OF
+                        if self.is_datatype(data_buffer, &triple.id) =>
+                    {
+                        data_buffer.datatype_buffer.push(triple)
+                    }
                     owl::INTERSECTION_OF => {
                         let edge =
-                            self.insert_edge(data_buffer, &triple, ElementType::NoDraw, None);
+                            self.insert_edge(data_buffer, &triple, ElementType::NoDraw, None, None);
                         if let Some(edge) = edge {
                             self.upgrade_node_type(
                                 data_buffer,
@@ -1481,14 +2970,18 @@ impl GraphDisplayDataSolutionSerializer {
                     //     // Characteristic::InverseFunctionalProperty)
                     //     // TODO: Implement
                     // }
+                    // Anonymous inverse properties are only drawn as part of property chains.
+                    owl::INVERSE_OF if triple.id.is_blank_node() => {
+                        data_buffer.axiom_buffer.push(triple)
+                    }
                     // TODO owl::INVERSE_OF => {}
                     // owl::IRREFLEXIVE_PROPERTY => {}
                     // owl::MAX_CARDINALITY => {}
                     // owl::MAX_QUALIFIED_CARDINALITY => {}
-                    // owl::MEMBERS => {}
+                    owl::MEMBERS => data_buffer.axiom_buffer.push(triple),
                     // owl::MIN_CARDINALITY => {}
                     // owl::MIN_QUALIFIED_CARDINALITY => {}
-                    // owl::NAMED_INDIVIDUAL => {}
+                    owl::NAMED_INDIVIDUAL => data_buffer.individual_buffer.push(triple),
                     // owl::NEGATIVE_PROPERTY_ASSERTION => {}
                     // TODO owl::NOTHING => {}
                     owl::OBJECT_PROPERTY => {
@@ -1499,35 +2992,32 @@ impl GraphDisplayDataSolutionSerializer {
                             e,
                         );
                     }
-                    // owl::ONE_OF => {}
+                    owl::ONE_OF => data_buffer.value_buffer.push(triple),
                     owl::ONTOLOGY => {
-                        if let Some(base) = &data_buffer.document_base {
-                            warn!(
-                                "Attempting to override document base '{}' with new base '{}'. Skipping",
-                                base,
-                                triple.id.to_string()
-                            );
-                        } else {
-                            // Remove ">" to enable substring matching
-                            let id = triple.id.to_string();
-                            let base = id[0..id.len() - 1].to_string();
-                            info!("Using document base: '{}'", base);
-                            data_buffer.document_base = Some(base);
+                        if triple.id.is_named_node() {
+                            info!("Found ontology: '{}'", triple.id);
+                            data_buffer.ontology_iris.push(triple.id.to_string());
                         }
                     }
                     // owl::ONTOLOGY_PROPERTY => {}
                     // owl::ON_CLASS => {}
                     // owl::ON_DATARANGE => {}
-                    // owl::ON_DATATYPE => {}
+                    owl::ON_DATATYPE => data_buffer.datatype_buffer.push(triple),
                     // owl::ON_PROPERTIES => {}
-                    // owl::ON_PROPERTY => {}
+                    owl::ON_PROPERTY => {
+                        if let Some(property) = &triple.target {
+                            data_buffer
+                                .restricted_properties
+                                .insert(triple.id.to_string(), property.to_string());
+                        }
+                    }
                     // owl::PRIOR_VERSION => {}
-                    // owl::PROPERTY_CHAIN_AXIOM => {}
-                    // owl::PROPERTY_DISJOINT_WITH => {}
+                    owl::PROPERTY_CHAIN_AXIOM => data_buffer.axiom_buffer.push(triple),
+                    owl::PROPERTY_DISJOINT_WITH => data_buffer.axiom_buffer.push(triple),
                     // owl::QUALIFIED_CARDINALITY => {}
                     // owl::REFLEXIVE_PROPERTY => {}
                     // owl::RESTRICTION => {}
-                    // owl::SAME_AS => {}
+                    owl::SAME_AS => data_buffer.individual_buffer.push(triple),
                     // owl::SOME_VALUES_FROM => {}
                     // owl::SOURCE_INDIVIDUAL => {}
                     // owl::SYMMETRIC_PROPERTY => {}
@@ -1552,7 +3042,7 @@ impl GraphDisplayDataSolutionSerializer {
                     // }
                     owl::UNION_OF => {
                         let edge =
-                            self.insert_edge(data_buffer, &triple, ElementType::NoDraw, None);
+                            self.insert_edge(data_buffer, &triple, ElementType::NoDraw, None, None);
                         if let Some(edge) = edge {
                             self.upgrade_node_type(
                                 data_buffer,
@@ -1562,12 +3052,39 @@ impl GraphDisplayDataSolutionSerializer {
                         }
                     }
                     // owl::VERSION_INFO => {}
-                    // owl::VERSION_IRI => {}
-                    // owl::WITH_RESTRICTIONS => {}
-                    _ => {
-                        // Visualization of this element is not supported
-                        warn!("Visualization of term '{}' is not supported", uri);
+                    owl::VERSION_IRI => {
+                        if let Some(version_iri) = &triple.target {
+                            info!("Found ontology version: '{}'", version_iri);
+                            data_buffer.ontology_iris.push(version_iri.to_string());
+                        }
                     }
+                    // owl::WITH_RESTRICTIONS => {}
+
+                    // ----------- XSD ----------- //
+                    xsd::LENGTH
+                    | xsd::MIN_LENGTH
+                    | xsd::MAX_LENGTH
+                    | xsd::PATTERN
+                    | xsd::MIN_INCLUSIVE
+                    | xsd::MIN_EXCLUSIVE
+                    | xsd::MAX_INCLUSIVE
+                    | xsd::MAX_EXCLUSIVE
+                    | xsd::TOTAL_DIGITS
+                    | xsd::FRACTION_DIGITS => data_buffer.datatype_buffer.push(triple),
+                    _ => match (&triple.id, &triple.target) {
+                        // Annotations of axioms, which are reified as blank nodes.
+                        (Term::BlankNode(_), Some(_)) => data_buffer.annotation_buffer.push(triple),
+                        // Assertions about individuals use the properties of the ontology,
+                        // which are only known once all solutions are read.
+                        (Term::NamedNode(_), Some(_))
+                            if !VOCABULARY_PREFIXES
+                                .iter()
+                                .any(|(_, namespace)| uri.as_str().starts_with(namespace)) =>
+                        {
+                            data_buffer.individual_buffer.push(triple);
+                        }
+                        _ => warn!("Visualization of term '{}' is not supported", uri),
+                    },
                 };
             }
         }
@@ -1579,6 +3096,50 @@ impl GraphDisplayDataSolutionSerializer {
 mod test {
     use super::*;
     use oxrdf::{BlankNode, Literal, NamedNode};
+    use vowlr_util::diagnostics::DiagnosticKind;
+
+    /// The term of the IRI `iri`.
+    fn named(iri: &str) -> Term {
+        Term::NamedNode(NamedNode::new(iri).unwrap())
+    }
+
+    /// The term of the blank node `id`.
+    fn blank(id: &str) -> Term {
+        Term::BlankNode(BlankNode::new(id).unwrap())
+    }
+
+    /// A term of a triple written by [`serialize_triples`], either an IRI or a term.
+    trait TripleTerm: Clone {
+        fn term(self) -> Term;
+    }
+
+    impl TripleTerm for &str {
+        fn term(self) -> Term {
+            named(self)
+        }
+    }
+
+    impl TripleTerm for Term {
+        fn term(self) -> Term {
+            self
+        }
+    }
+
+    /// Writes `triples` with `serializer` into a new buffer,
+    /// each a tuple of its element, the IRI of its type and its target, if any.
+    fn serialize_triples<I: TripleTerm, T: TripleTerm>(
+        serializer: &GraphDisplayDataSolutionSerializer,
+        triples: &[(I, &str, Option<T>)],
+    ) -> SerializationDataBuffer {
+        let mut data_buffer = SerializationDataBuffer::new();
+        for (id, element_type, target) in triples.iter().cloned() {
+            serializer.write_node_triple(
+                &mut data_buffer,
+                Triple::new(id.term(), named(element_type), target.map(TripleTerm::term)),
+            );
+        }
+        data_buffer
+    }
 
     #[test]
     fn test_replace_node() {
@@ -1595,199 +3156,1019 @@ mod test {
                 target: None,
             },
         );
-        serializer.write_node_triple(
-            &mut data_buffer,
-            Triple {
-                id: Term::NamedNode(NamedNode::new("http://example.com#Parent").unwrap()),
-                element_type: Term::NamedNode(
-                    NamedNode::new("http://www.w3.org/2002/07/owl#Class").unwrap(),
-                ),
-                target: None,
-            },
+        serializer.write_node_triple(
+            &mut data_buffer,
+            Triple {
+                id: Term::NamedNode(NamedNode::new("http://example.com#Parent").unwrap()),
+                element_type: Term::NamedNode(
+                    NamedNode::new("http://www.w3.org/2002/07/owl#Class").unwrap(),
+                ),
+                target: None,
+            },
+        );
+        serializer.write_node_triple(
+            &mut data_buffer,
+            Triple {
+                id: Term::NamedNode(NamedNode::new("http://example.com#Mother").unwrap()),
+                element_type: Term::NamedNode(
+                    NamedNode::new("http://www.w3.org/2002/07/owl#Class").unwrap(),
+                ),
+                target: None,
+            },
+        );
+        serializer.write_node_triple(
+            &mut data_buffer,
+            Triple {
+                id: Term::NamedNode(NamedNode::new("http://example.com#Guardian").unwrap()),
+                element_type: Term::NamedNode(
+                    NamedNode::new("http://www.w3.org/2002/07/owl#Class").unwrap(),
+                ),
+                target: None,
+            },
+        );
+        serializer.write_node_triple(
+            &mut data_buffer,
+            Triple {
+                id: Term::NamedNode(NamedNode::new("http://example.com#Warden").unwrap()),
+                element_type: Term::NamedNode(
+                    NamedNode::new("http://www.w3.org/2002/07/owl#Class").unwrap(),
+                ),
+                target: None,
+            },
+        );
+        serializer.write_node_triple(
+            &mut data_buffer,
+            Triple {
+                id: Term::NamedNode(NamedNode::new("http://example.com#Warden1").unwrap()),
+                element_type: Term::NamedNode(
+                    NamedNode::new("http://www.w3.org/2002/07/owl#Class").unwrap(),
+                ),
+                target: None,
+            },
+        );
+        serializer.write_node_triple(
+            &mut data_buffer,
+            Triple {
+                id: Term::NamedNode(NamedNode::new("http://example.com#Warden").unwrap()),
+                element_type: Term::NamedNode(
+                    NamedNode::new("http://www.w3.org/2000/01/rdf-schema#subClassOf").unwrap(),
+                ),
+                target: Some(Term::NamedNode(
+                    NamedNode::new("http://example.com#Guardian").unwrap(),
+                )),
+            },
+        );
+        serializer.write_node_triple(
+            &mut data_buffer,
+            Triple {
+                id: Term::NamedNode(NamedNode::new("http://example.com#Mother").unwrap()),
+                element_type: Term::NamedNode(
+                    NamedNode::new("http://www.w3.org/2000/01/rdf-schema#subClassOf").unwrap(),
+                ),
+                target: Some(Term::NamedNode(
+                    NamedNode::new("http://example.com#Parent").unwrap(),
+                )),
+            },
+        );
+        serializer.write_node_triple(
+            &mut data_buffer,
+            Triple {
+                id: Term::BlankNode(BlankNode::new("e1013e66f734c508511575854b0c9396").unwrap()),
+                element_type: Term::Literal(Literal::new_simple_literal("blanknode".to_string())),
+                target: None,
+            },
+        );
+        serializer.write_node_triple(
+            &mut data_buffer,
+            Triple {
+                id: Term::NamedNode(NamedNode::new("http://example.com#Warden1").unwrap()),
+                element_type: Term::NamedNode(
+                    NamedNode::new("http://www.w3.org/2002/07/owl#unionOf").unwrap(),
+                ),
+                target: Some(Term::NamedNode(
+                    NamedNode::new("http://example.com#Warden").unwrap(),
+                )),
+            },
+        );
+
+        print_graph_display_data(&data_buffer);
+        println!("--------------------------------");
+
+        let triple = Triple {
+            id: Term::NamedNode(NamedNode::new("http://example.com#Guardian").unwrap()),
+            element_type: Term::NamedNode(
+                NamedNode::new("http://www.w3.org/2002/07/owl#equivalentClass").unwrap(),
+            ),
+            target: Some(Term::NamedNode(
+                NamedNode::new("http://example.com#Warden").unwrap(),
+            )),
+        };
+        serializer.write_node_triple(&mut data_buffer, triple);
+        for (k, v) in data_buffer.node_element_buffer.iter() {
+            println!("element_buffer: {} -> {}", k, v);
+        }
+        for (k, v) in data_buffer.edges_include_map.iter() {
+            println!("edges_include_map: {} -> {:?}", k, v);
+        }
+        for (k, v) in data_buffer.edge_redirection.iter() {
+            println!("edge_redirection: {} -> {}", k, v);
+        }
+        assert!(
+            data_buffer
+                .node_element_buffer
+                .contains_key("<http://example.com#Guardian>")
+        );
+        assert!(
+            !data_buffer
+                .node_element_buffer
+                .contains_key("<http://example.com#Warden>")
         );
-        serializer.write_node_triple(
-            &mut data_buffer,
-            Triple {
-                id: Term::NamedNode(NamedNode::new("http://example.com#Mother").unwrap()),
-                element_type: Term::NamedNode(
-                    NamedNode::new("http://www.w3.org/2002/07/owl#Class").unwrap(),
-                ),
-                target: None,
-            },
+        assert!(
+            data_buffer
+                .node_element_buffer
+                .contains_key("<http://example.com#Warden1>")
+        );
+        assert!(
+            data_buffer
+                .edges_include_map
+                .contains_key("<http://example.com#Warden1>")
+        );
+        assert!(
+            *data_buffer
+                .edge_redirection
+                .get("<http://example.com#Warden>")
+                .unwrap()
+                == "<http://example.com#Guardian>".to_string()
+        );
+        assert!(data_buffer.edge_buffer.contains(&Edge {
+            subject: "<http://example.com#Warden1>".to_string(),
+            element_type: ElementType::NoDraw,
+            object: "<http://example.com#Guardian>".to_string(),
+            property: None,
+        }));
+        assert!(
+            data_buffer
+                .edge_redirection
+                .contains_key("<http://example.com#Warden>")
+        );
+        assert_eq!(
+            data_buffer
+                .edge_redirection
+                .get("<http://example.com#Warden>")
+                .unwrap(),
+            "<http://example.com#Guardian>"
         );
         serializer.write_node_triple(
             &mut data_buffer,
             Triple {
                 id: Term::NamedNode(NamedNode::new("http://example.com#Guardian").unwrap()),
                 element_type: Term::NamedNode(
-                    NamedNode::new("http://www.w3.org/2002/07/owl#Class").unwrap(),
+                    NamedNode::new("http://www.w3.org/2002/07/owl#equivalentClass").unwrap(),
                 ),
-                target: None,
+                target: Some(Term::BlankNode(
+                    BlankNode::new("e1013e66f734c508511575854b0c9396").unwrap(),
+                )),
             },
         );
-        serializer.write_node_triple(
+        let s = serializer.resolve(
             &mut data_buffer,
-            Triple {
-                id: Term::NamedNode(NamedNode::new("http://example.com#Warden").unwrap()),
-                element_type: Term::NamedNode(
-                    NamedNode::new("http://www.w3.org/2002/07/owl#Class").unwrap(),
-                ),
-                target: None,
-            },
+            "_:e1013e66f734c508511575854b0c9396".to_string(),
+        );
+        assert!(s.is_some());
+        for (k, v) in data_buffer.node_element_buffer.iter() {
+            println!("element_buffer: {} -> {}", k, v);
+        }
+        for (k, v) in data_buffer.edge_redirection.iter() {
+            println!("edge_redirection: {} -> {}", k, v);
+        }
+        assert!(s.unwrap() == "<http://example.com#Guardian>".to_string());
+        assert!(
+            !data_buffer
+                .edges_include_map
+                .contains_key("_:e1013e66f734c508511575854b0c9396")
+        );
+        assert!(!data_buffer.edges_include_map.contains_key("Warden"));
+        print_graph_display_data(&data_buffer);
+        println!("data_buffer: {}", data_buffer);
+    }
+
+    #[test]
+    fn test_mark_external_elements() {
+        let serializer = GraphDisplayDataSolutionSerializer::new();
+        let triples = [
+            (
+                "http://example.com/onto",
+                "http://www.w3.org/2002/07/owl#Ontology",
+                None,
+            ),
+            (
+                "http://example.com/onto#Parent",
+                "http://www.w3.org/2002/07/owl#Class",
+                None,
+            ),
+            (
+                "http://xmlns.com/foaf/0.1/Person",
+                "http://www.w3.org/2002/07/owl#Class",
+                None,
+            ),
+            (
+                "http://xmlns.com/foaf/0.1/knows",
+                "http://www.w3.org/2002/07/owl#ObjectProperty",
+                None,
+            ),
+            (
+                "http://xmlns.com/foaf/0.1/knows",
+                "http://www.w3.org/2000/01/rdf-schema#domain",
+                Some("http://example.com/onto#Parent"),
+            ),
+            (
+                "http://xmlns.com/foaf/0.1/knows",
+                "http://www.w3.org/2000/01/rdf-schema#range",
+                Some("http://xmlns.com/foaf/0.1/Person"),
+            ),
+        ];
+        let mut data_buffer = serialize_triples(&serializer, &triples);
+        serializer.mark_external_elements(&mut data_buffer);
+
+        assert_eq!(
+            data_buffer
+                .node_element_buffer
+                .get("<http://example.com/onto#Parent>"),
+            Some(&ElementType::Owl(OwlType::Node(OwlNode::Class)))
+        );
+        assert_eq!(
+            data_buffer
+                .node_element_buffer
+                .get("<http://xmlns.com/foaf/0.1/Person>"),
+            Some(&ElementType::Owl(OwlType::Node(OwlNode::ExternalClass)))
+        );
+        assert!(data_buffer.edge_buffer.contains(&Edge {
+            subject: "<http://example.com/onto#Parent>".to_string(),
+            element_type: ElementType::Owl(OwlType::Edge(OwlEdge::ExternalProperty)),
+            object: "<http://xmlns.com/foaf/0.1/Person>".to_string(),
+            property: Some("<http://xmlns.com/foaf/0.1/knows>".to_string()),
+        }));
+    }
+
+    #[test]
+    fn test_mark_deprecated_elements() {
+        let serializer = GraphDisplayDataSolutionSerializer::new();
+        let triples = [
+            (
+                "http://example.com/onto#Parent",
+                "http://www.w3.org/2002/07/owl#Class",
+                None,
+            ),
+            (
+                "http://example.com/onto#Child",
+                "http://www.w3.org/2002/07/owl#Class",
+                None,
+            ),
+            (
+                "http://example.com/onto#Parent",
+                "http://www.w3.org/2002/07/owl#deprecated",
+                None,
+            ),
+            (
+                "http://example.com/onto#hasChild",
+                "http://www.w3.org/2002/07/owl#ObjectProperty",
+                None,
+            ),
+            (
+                "http://example.com/onto#hasChild",
+                "http://www.w3.org/2002/07/owl#deprecated",
+                None,
+            ),
+            (
+                "http://example.com/onto#hasChild",
+                "http://www.w3.org/2000/01/rdf-schema#domain",
+                Some("http://example.com/onto#Parent"),
+            ),
+            (
+                "http://example.com/onto#hasChild",
+                "http://www.w3.org/2000/01/rdf-schema#range",
+                Some("http://example.com/onto#Child"),
+            ),
+        ];
+        let mut data_buffer = serialize_triples(&serializer, &triples);
+        serializer.mark_deprecated_elements(&mut data_buffer);
+
+        assert_eq!(
+            data_buffer
+                .node_element_buffer
+                .get("<http://example.com/onto#Parent>"),
+            Some(&ElementType::Owl(OwlType::Node(OwlNode::DeprecatedClass)))
+        );
+        assert_eq!(
+            data_buffer
+                .node_element_buffer
+                .get("<http://example.com/onto#Child>"),
+            Some(&ElementType::Owl(OwlType::Node(OwlNode::Class)))
+        );
+        let edge = Edge {
+            subject: "<http://example.com/onto#Parent>".to_string(),
+            element_type: ElementType::Owl(OwlType::Edge(OwlEdge::DeprecatedProperty)),
+            object: "<http://example.com/onto#Child>".to_string(),
+            property: Some("<http://example.com/onto#hasChild>".to_string()),
+        };
+        assert!(data_buffer.edge_buffer.contains(&edge));
+        assert!(data_buffer.edge_label_buffer.contains_key(&edge));
+    }
+
+    #[test]
+    fn test_deprecated_property_keeps_edges() {
+        let serializer = GraphDisplayDataSolutionSerializer::new();
+        let triples = [
+            (
+                "http://example.com/onto#Parent",
+                "http://www.w3.org/2002/07/owl#Class",
+                None,
+            ),
+            (
+                "http://example.com/onto#Child",
+                "http://www.w3.org/2002/07/owl#Class",
+                None,
+            ),
+            (
+                "http://example.com/onto#hadChild",
+                "http://www.w3.org/2002/07/owl#DeprecatedProperty",
+                None,
+            ),
+            (
+                "http://example.com/onto#hadChild",
+                "http://www.w3.org/2000/01/rdf-schema#domain",
+                Some("http://example.com/onto#Parent"),
+            ),
+            (
+                "http://example.com/onto#hadChild",
+                "http://www.w3.org/2000/01/rdf-schema#range",
+                Some("http://example.com/onto#Child"),
+            ),
+        ];
+        let mut data_buffer = serialize_triples(&serializer, &triples);
+        serializer.mark_deprecated_elements(&mut data_buffer);
+
+        assert_eq!(
+            data_buffer
+                .edge_element_buffer
+                .get("<http://example.com/onto#hadChild>"),
+            Some(&ElementType::Owl(OwlType::Edge(
+                OwlEdge::DeprecatedProperty
+            )))
+        );
+        assert!(data_buffer.edge_buffer.contains(&Edge {
+            subject: "<http://example.com/onto#Parent>".to_string(),
+            element_type: ElementType::Owl(OwlType::Edge(OwlEdge::DeprecatedProperty)),
+            object: "<http://example.com/onto#Child>".to_string(),
+            property: Some("<http://example.com/onto#hadChild>".to_string()),
+        }));
+    }
+
+    #[test]
+    fn test_literal_nodes_are_derived_from_values() {
+        let serializer = GraphDisplayDataSolutionSerializer::new();
+        let mut data_buffer = SerializationDataBuffer::new();
+        let first =
+            serializer.insert_literal_node(&mut data_buffer, &Literal::new_simple_literal("42"));
+        let second =
+            serializer.insert_literal_node(&mut data_buffer, &Literal::new_simple_literal("42"));
+        let other =
+            serializer.insert_literal_node(&mut data_buffer, &Literal::new_simple_literal("43"));
+
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+        assert_eq!(data_buffer.node_element_buffer.len(), 2);
+        assert_eq!(
+            data_buffer.label_buffer.get(&first.to_string()),
+            Some(&"42".to_string())
+        );
+    }
+
+    #[test]
+    fn test_write_individuals() {
+        let serializer = GraphDisplayDataSolutionSerializer::new();
+        let triples = [
+            (
+                "http://example.com#A",
+                "http://www.w3.org/2002/07/owl#Class",
+                None,
+            ),
+            (
+                "http://example.com#dp",
+                "http://www.w3.org/2002/07/owl#DatatypeProperty",
+                None,
+            ),
+            (
+                "http://example.com#I",
+                "http://www.w3.org/2002/07/owl#NamedIndividual",
+                None,
+            ),
+            (
+                "http://example.com#J",
+                "http://www.w3.org/2002/07/owl#NamedIndividual",
+                None,
+            ),
+            (
+                "http://example.com#I",
+                "http://www.w3.org/1999/02/22-rdf-syntax-ns#type",
+                Some(named("http://example.com#A")),
+            ),
+            (
+                "http://example.com#I",
+                "http://www.w3.org/2002/07/owl#sameAs",
+                Some(named("http://example.com#J")),
+            ),
+            (
+                "http://example.com#I",
+                "http://example.com#dp",
+                Some(Term::Literal(Literal::new_simple_literal("A literal"))),
+            ),
+        ];
+        let mut data_buffer = serialize_triples(&serializer, &triples);
+        serializer.write_individuals(&mut data_buffer);
+
+        let individual = ElementType::Generic(GenericType::Node(GenericNode::Generic));
+        assert_eq!(
+            data_buffer
+                .node_element_buffer
+                .get("<http://example.com#I>"),
+            Some(&individual)
+        );
+        assert!(data_buffer.edge_buffer.contains(&Edge {
+            subject: "<http://example.com#I>".to_string(),
+            element_type: ElementType::Generic(GenericType::Edge(GenericEdge::Generic)),
+            object: "<http://example.com#A>".to_string(),
+            property: None,
+        }));
+        assert!(data_buffer.edge_buffer.contains(&Edge {
+            subject: "<http://example.com#I>".to_string(),
+            element_type: ElementType::Generic(GenericType::Edge(GenericEdge::Generic)),
+            object: "<http://example.com#J>".to_string(),
+            property: None,
+        }));
+        let literal_edge = data_buffer
+            .edge_buffer
+            .iter()
+            .find(|edge| edge.property.as_deref() == Some("<http://example.com#dp>"))
+            .expect("data property assertion should be drawn");
+        assert_eq!(
+            data_buffer.label_buffer.get(&literal_edge.object),
+            Some(&"A literal".to_string())
+        );
+    }
+
+    #[test]
+    fn test_individual_limit() {
+        let mut serializer = GraphDisplayDataSolutionSerializer::new();
+        serializer.options.individual_limit = 1;
+        let individual = "http://www.w3.org/2002/07/owl#NamedIndividual";
+        let triples = [
+            ("http://example.com#I", individual, None::<&str>),
+            ("http://example.com#J", individual, None),
+        ];
+        let mut data_buffer = serialize_triples(&serializer, &triples);
+        assert_eq!(serializer.write_individuals(&mut data_buffer), 2);
+        assert!(data_buffer.node_element_buffer.is_empty());
+    }
+
+    #[test]
+    fn test_write_values() {
+        let serializer = GraphDisplayDataSolutionSerializer::new();
+        let enumeration = blank("e");
+        let restriction = blank("r");
+        let triples = [
+            (
+                enumeration.clone(),
+                "http://www.w3.org/2002/07/owl#oneOf",
+                Some(named("http://example.com#I")),
+            ),
+            (
+                enumeration.clone(),
+                "http://www.w3.org/2002/07/owl#oneOf",
+                Some(named("http://example.com#J")),
+            ),
+            (
+                named("http://example.com#d"),
+                "http://www.w3.org/2002/07/owl#DatatypeProperty",
+                None,
+            ),
+            (
+                restriction.clone(),
+                "http://www.w3.org/2002/07/owl#hasValue",
+                Some(Term::Literal(Literal::new_simple_literal("A literal"))),
+            ),
+            (
+                restriction.clone(),
+                "http://www.w3.org/2002/07/owl#onProperty",
+                Some(named("http://example.com#d")),
+            ),
+        ];
+        let mut data_buffer = serialize_triples(&serializer, &triples);
+        serializer.write_values(&mut data_buffer);
+
+        assert_eq!(
+            data_buffer
+                .node_element_buffer
+                .get(&enumeration.to_string()),
+            Some(&ElementType::Owl(OwlType::Node(OwlNode::UnionOf)))
+        );
+        for member in ["<http://example.com#I>", "<http://example.com#J>"] {
+            assert!(data_buffer.edge_buffer.contains(&Edge {
+                subject: enumeration.to_string(),
+                element_type: ElementType::NoDraw,
+                object: member.to_string(),
+                property: None,
+            }));
+        }
+        let value_edge = data_buffer
+            .edge_buffer
+            .iter()
+            .find(|edge| edge.subject == restriction.to_string())
+            .expect("value restriction should be drawn");
+        assert_eq!(
+            value_edge.element_type,
+            ElementType::Owl(OwlType::Edge(OwlEdge::DatatypeProperty))
+        );
+        assert_eq!(
+            value_edge.property.as_deref(),
+            Some("<http://example.com#d>")
+        );
+        assert_eq!(
+            data_buffer.label_buffer.get(&value_edge.object),
+            Some(&"A literal".to_string())
+        );
+    }
+
+    #[test]
+    fn test_describe_datatypes() {
+        let serializer = GraphDisplayDataSolutionSerializer::new();
+        let datatype = "http://www.w3.org/2000/01/rdf-schema#Datatype";
+        let triples = [
+            (named("http://example.com#D"), datatype, None),
+            (named("http://example.com#A"), datatype, None),
+            (blank("union"), datatype, None),
+            (blank("restriction"), datatype, None),
+            (
+                named("http://example.com#D"),
+                "http://www.w3.org/2002/07/owl#equivalentClass",
+                Some(blank("union")),
+            ),
+            (
+                named("http://example.com#A"),
+                "http://www.w3.org/2002/07/owl#equivalentClass",
+                Some(named("http://www.w3.org/2002/07/owl#rational")),
+            ),
+            (
+                blank("union"),
+                "http://www.w3.org/2002/07/owl#unionOf",
+                Some(named("http://www.w3.org/2002/07/owl#real")),
+            ),
+            (
+                blank("union"),
+                "http://www.w3.org/2002/07/owl#unionOf",
+                Some(blank("restriction")),
+            ),
+            (
+                blank("restriction"),
+                "http://www.w3.org/2002/07/owl#onDatatype",
+                Some(named("http://www.w3.org/2001/XMLSchema#integer")),
+            ),
+            (
+                blank("restriction"),
+                "http://www.w3.org/2001/XMLSchema#maxExclusive",
+                Some(Term::Literal(Literal::new_simple_literal("20"))),
+            ),
+            (
+                blank("restriction"),
+                "http://www.w3.org/2001/XMLSchema#minExclusive",
+                Some(Term::Literal(Literal::new_simple_literal("10"))),
+            ),
+        ];
+        let mut data_buffer = serialize_triples(&serializer, &triples);
+        serializer.describe_datatypes(&mut data_buffer);
+
+        assert_eq!(
+            data_buffer.label_buffer.get("<http://example.com#D>"),
+            Some(&"D\n(integer[> 10, < 20]) or real".to_string())
         );
-        serializer.write_node_triple(
-            &mut data_buffer,
-            Triple {
-                id: Term::NamedNode(NamedNode::new("http://example.com#Warden1").unwrap()),
-                element_type: Term::NamedNode(
-                    NamedNode::new("http://www.w3.org/2002/07/owl#Class").unwrap(),
-                ),
-                target: None,
-            },
+        assert_eq!(
+            data_buffer.label_buffer.get("<http://example.com#A>"),
+            Some(&"A\nrational".to_string())
         );
-        serializer.write_node_triple(
-            &mut data_buffer,
-            Triple {
-                id: Term::NamedNode(NamedNode::new("http://example.com#Warden").unwrap()),
-                element_type: Term::NamedNode(
-                    NamedNode::new("http://www.w3.org/2000/01/rdf-schema#subClassOf").unwrap(),
-                ),
-                target: Some(Term::NamedNode(
-                    NamedNode::new("http://example.com#Guardian").unwrap(),
-                )),
-            },
+        // The restriction is described by the label of the union.
+        assert!(
+            !data_buffer
+                .node_element_buffer
+                .contains_key(&blank("restriction").to_string())
         );
-        serializer.write_node_triple(
-            &mut data_buffer,
-            Triple {
-                id: Term::NamedNode(NamedNode::new("http://example.com#Mother").unwrap()),
-                element_type: Term::NamedNode(
-                    NamedNode::new("http://www.w3.org/2000/01/rdf-schema#subClassOf").unwrap(),
-                ),
-                target: Some(Term::NamedNode(
-                    NamedNode::new("http://example.com#Parent").unwrap(),
-                )),
-            },
+    }
+
+    #[test]
+    fn test_write_property_hierarchy() {
+        let mut serializer = GraphDisplayDataSolutionSerializer::new();
+        serializer.options.property_hierarchy = true;
+        let object_property = "http://www.w3.org/2002/07/owl#ObjectProperty";
+        let triples = [
+            ("http://example.com#knows", object_property, None),
+            ("http://example.com#acquaintedWith", object_property, None),
+            ("http://example.com#relatedTo", object_property, None),
+            (
+                "http://example.com#knows",
+                "http://www.w3.org/2002/07/owl#equivalentProperty",
+                Some(named("http://example.com#acquaintedWith")),
+            ),
+            (
+                "http://example.com#acquaintedWith",
+                "http://www.w3.org/2000/01/rdf-schema#subPropertyOf",
+                Some(named("http://example.com#relatedTo")),
+            ),
+        ];
+        let mut data_buffer = serialize_triples(&serializer, &triples);
+        serializer.write_property_hierarchy(&mut data_buffer);
+
+        // Equivalent properties share a node.
+        assert_eq!(
+            data_buffer
+                .node_element_buffer
+                .get("<http://example.com#knows>"),
+            Some(&ElementType::Owl(OwlType::Edge(OwlEdge::ObjectProperty)))
         );
-        serializer.write_node_triple(
-            &mut data_buffer,
-            Triple {
-                id: Term::BlankNode(BlankNode::new("e1013e66f734c508511575854b0c9396").unwrap()),
-                element_type: Term::Literal(Literal::new_simple_literal("blanknode".to_string())),
-                target: None,
-            },
+        assert!(
+            !data_buffer
+                .node_element_buffer
+                .contains_key("<http://example.com#acquaintedWith>")
         );
-        serializer.write_node_triple(
-            &mut data_buffer,
-            Triple {
-                id: Term::NamedNode(NamedNode::new("http://example.com#Warden1").unwrap()),
-                element_type: Term::NamedNode(
-                    NamedNode::new("http://www.w3.org/2002/07/owl#unionOf").unwrap(),
-                ),
-                target: Some(Term::NamedNode(
-                    NamedNode::new("http://example.com#Warden").unwrap(),
-                )),
-            },
+        assert_eq!(
+            data_buffer.label_buffer.get("<http://example.com#knows>"),
+            Some(&"knows\n[acquaintedWith]".to_string())
         );
+        let sub_property_of = Edge {
+            subject: "<http://example.com#knows>".to_string(),
+            element_type: ElementType::Rdfs(RdfsType::Edge(RdfsEdge::SubclassOf)),
+            object: "<http://example.com#relatedTo>".to_string(),
+            property: Some("<http://www.w3.org/2000/01/rdf-schema#subPropertyOf>".to_string()),
+        };
+        assert!(data_buffer.edge_buffer.contains(&sub_property_of));
 
-        print_graph_display_data(&data_buffer);
-        println!("--------------------------------");
+        // Sub-property edges are drawn as subclass edges, but flagged as sub-property edges.
+        serializer.fill_missing_labels(&mut data_buffer);
+        let (data, flags): (GraphDisplayData, GraphFlags) = data_buffer.into();
+        assert_eq!(
+            flags.kinds.values().collect::<Vec<_>>(),
+            vec![&ElementKind::SubPropertyOf]
+        );
+        assert_eq!(
+            flags
+                .kinds
+                .keys()
+                .map(|index| &data.labels[*index])
+                .collect::<Vec<_>>(),
+            vec!["subproperty of"]
+        );
+    }
 
-        let triple = Triple {
-            id: Term::NamedNode(NamedNode::new("http://example.com#Guardian").unwrap()),
-            element_type: Term::NamedNode(
-                NamedNode::new("http://www.w3.org/2002/07/owl#equivalentClass").unwrap(),
+    #[test]
+    fn test_write_axioms() {
+        let serializer = GraphDisplayDataSolutionSerializer::new();
+        let triples = [
+            (
+                named("http://example.com#A"),
+                "http://www.w3.org/2002/07/owl#Class",
+                None,
             ),
-            target: Some(Term::NamedNode(
-                NamedNode::new("http://example.com#Warden").unwrap(),
-            )),
+            (
+                named("http://example.com#B"),
+                "http://www.w3.org/2002/07/owl#Class",
+                None,
+            ),
+            (
+                named("http://example.com#C"),
+                "http://www.w3.org/2002/07/owl#Class",
+                None,
+            ),
+            (
+                blank("disjoint"),
+                "http://www.w3.org/2002/07/owl#AllDisjointClasses",
+                None,
+            ),
+            (
+                blank("disjoint"),
+                "http://www.w3.org/2002/07/owl#members",
+                Some(named("http://example.com#A")),
+            ),
+            (
+                blank("disjoint"),
+                "http://www.w3.org/2002/07/owl#members",
+                Some(named("http://example.com#B")),
+            ),
+            (
+                blank("disjoint"),
+                "http://www.w3.org/2002/07/owl#members",
+                Some(named("http://example.com#C")),
+            ),
+            (
+                named("http://example.com#t"),
+                "http://www.w3.org/2002/07/owl#propertyChainAxiom",
+                Some(blank("first")),
+            ),
+            (
+                blank("first"),
+                "http://www.w3.org/1999/02/22-rdf-syntax-ns#first",
+                Some(named("http://example.com#r")),
+            ),
+            (
+                blank("first"),
+                "http://www.w3.org/1999/02/22-rdf-syntax-ns#rest",
+                Some(blank("second")),
+            ),
+            (
+                blank("second"),
+                "http://www.w3.org/1999/02/22-rdf-syntax-ns#first",
+                Some(blank("inverse")),
+            ),
+            (
+                blank("inverse"),
+                "http://www.w3.org/2002/07/owl#inverseOf",
+                Some(named("http://example.com#s")),
+            ),
+        ];
+        let mut data_buffer = serialize_triples(&serializer, &triples);
+        let chained = Edge {
+            subject: "<http://example.com#A>".to_string(),
+            element_type: ElementType::Owl(OwlType::Edge(OwlEdge::ObjectProperty)),
+            object: "<http://example.com#B>".to_string(),
+            property: Some("<http://example.com#t>".to_string()),
         };
-        serializer.write_node_triple(&mut data_buffer, triple);
-        for (k, v) in data_buffer.node_element_buffer.iter() {
-            println!("element_buffer: {} -> {}", k, v);
-        }
-        for (k, v) in data_buffer.edges_include_map.iter() {
-            println!("edges_include_map: {} -> {:?}", k, v);
-        }
-        for (k, v) in data_buffer.edge_redirection.iter() {
-            println!("edge_redirection: {} -> {}", k, v);
+        data_buffer.edge_buffer.insert(chained.clone());
+        serializer.write_axioms(&mut data_buffer);
+
+        // The disjointness of three classes is drawn as a hub instead of three edges.
+        let hub = blank("disjoint").to_string();
+        assert_eq!(
+            data_buffer.node_element_buffer.get(&hub),
+            Some(&ElementType::Rdfs(RdfsType::Node(RdfsNode::Resource)))
+        );
+        assert_eq!(
+            data_buffer.node_kinds.get(&hub),
+            Some(&ElementKind::DisjointnessHub)
+        );
+        for member in ["A", "B", "C"] {
+            assert!(data_buffer.edge_buffer.contains(&Edge {
+                subject: hub.clone(),
+                element_type: ElementType::Owl(OwlType::Edge(OwlEdge::DisjointWith)),
+                object: format!("<http://example.com#{}>", member),
+                property: None,
+            }));
         }
-        assert!(
+        assert_eq!(
+            data_buffer.edge_characteristics.get(&chained),
+            Some(&vec!["chain: r o inverse s".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_write_annotations() {
+        let mut serializer = GraphDisplayDataSolutionSerializer::new();
+        serializer.options.annotation_layer = true;
+        let axiom = blank("axiom");
+        let triples = [
+            (
+                named("http://example.com#A"),
+                "http://www.w3.org/2002/07/owl#Class",
+                None,
+            ),
+            (
+                named("http://example.com#B"),
+                "http://www.w3.org/2002/07/owl#Class",
+                None,
+            ),
+            (
+                named("http://example.com#A"),
+                "http://www.w3.org/2000/01/rdf-schema#subClassOf",
+                Some(named("http://example.com#B")),
+            ),
+            (
+                named("http://example.com#A"),
+                "http://www.w3.org/2000/01/rdf-schema#seeAlso",
+                Some(named("http://example.org/A")),
+            ),
+            (
+                axiom.clone(),
+                "http://www.w3.org/2002/07/owl#annotatedSource",
+                Some(named("http://example.com#A")),
+            ),
+            (
+                axiom.clone(),
+                "http://www.w3.org/2002/07/owl#annotatedProperty",
+                Some(named("http://www.w3.org/2000/01/rdf-schema#subClassOf")),
+            ),
+            (
+                axiom.clone(),
+                "http://www.w3.org/2002/07/owl#annotatedTarget",
+                Some(named("http://example.com#B")),
+            ),
+            (
+                axiom.clone(),
+                "http://www.w3.org/2000/01/rdf-schema#comment",
+                Some(Term::Literal(Literal::new_simple_literal("Obviously"))),
+            ),
+        ];
+        let mut data_buffer = serialize_triples(&serializer, &triples);
+        serializer.write_individuals(&mut data_buffer);
+        serializer.write_annotations(&mut data_buffer);
+
+        assert_eq!(
             data_buffer
                 .node_element_buffer
-                .contains_key("<http://example.com#Guardian>")
+                .get("<http://example.org/A>"),
+            Some(&ElementType::Rdfs(RdfsType::Node(RdfsNode::Resource)))
         );
-        assert!(
-            !data_buffer
-                .node_element_buffer
-                .contains_key("<http://example.com#Warden>")
+        assert!(data_buffer.edge_buffer.contains(&Edge {
+            subject: "<http://example.com#A>".to_string(),
+            element_type: ElementType::Rdf(RdfType::Edge(RdfEdge::RdfProperty)),
+            object: "<http://example.org/A>".to_string(),
+            property: Some("<http://www.w3.org/2000/01/rdf-schema#seeAlso>".to_string()),
+        }));
+        let subclass = Edge {
+            subject: "<http://example.com#A>".to_string(),
+            element_type: ElementType::Rdfs(RdfsType::Edge(RdfsEdge::SubclassOf)),
+            object: "<http://example.com#B>".to_string(),
+            property: None,
+        };
+        assert_eq!(
+            data_buffer.edge_characteristics.get(&subclass),
+            Some(&vec!["comment: Obviously".to_string()])
         );
+    }
+
+    #[test]
+    fn test_mark_inferred_elements() {
+        let serializer = GraphDisplayDataSolutionSerializer::new();
+        let class = "http://www.w3.org/2002/07/owl#Class";
+        let sub_class_of = "http://www.w3.org/2000/01/rdf-schema#subClassOf";
+        let triples = [
+            ("http://example.com#A", class, None),
+            ("http://example.com#B", class, None),
+            ("http://example.com#C", class, None),
+            (
+                "http://example.com#A",
+                sub_class_of,
+                Some(named("http://example.com#B")),
+            ),
+            (
+                "http://example.com#B",
+                sub_class_of,
+                Some(named("http://example.com#C")),
+            ),
+        ];
+        let mut data_buffer = serialize_triples(&serializer, &triples);
+        data_buffer.inferred_buffer.push(Triple::new(
+            named("http://example.com#A"),
+            named(sub_class_of),
+            Some(named("http://example.com#B")),
+        ));
+        serializer.mark_inferred_elements(&mut data_buffer);
+
+        let edge = |subject: &str, object: &str| Edge {
+            subject: subject.to_string(),
+            element_type: ElementType::Rdfs(RdfsType::Edge(RdfsEdge::SubclassOf)),
+            object: object.to_string(),
+            property: None,
+        };
         assert!(
             data_buffer
-                .node_element_buffer
-                .contains_key("<http://example.com#Warden1>")
+                .inferred_edges
+                .contains(&edge("<http://example.com#A>", "<http://example.com#B>"))
         );
         assert!(
+            !data_buffer
+                .inferred_edges
+                .contains(&edge("<http://example.com#B>", "<http://example.com#C>"))
+        );
+        assert!(data_buffer.edge_characteristics.is_empty());
+    }
+
+    #[test]
+    fn test_highlight_diagnostics() {
+        let serializer = GraphDisplayDataSolutionSerializer::new().with_diagnostics([Diagnostic {
+            kind: DiagnosticKind::UnsatisfiableClass,
+            elements: vec!["<http://example.com#A>".to_string()],
+            message: "Class A is unsatisfiable".to_string(),
+        }]);
+        let class = "http://www.w3.org/2002/07/owl#Class";
+        let triples = [
+            ("http://example.com#A", class, None::<&str>),
+            ("http://example.com#B", class, None),
+        ];
+        let mut data_buffer = serialize_triples(&serializer, &triples);
+        serializer.highlight_diagnostics(&mut data_buffer);
+
+        assert_eq!(
             data_buffer
-                .edges_include_map
-                .contains_key("<http://example.com#Warden1>")
+                .node_characteristics
+                .get("<http://example.com#A>"),
+            Some(&vec!["unsatisfiable".to_string()])
         );
         assert!(
-            *data_buffer
-                .edge_redirection
-                .get("<http://example.com#Warden>")
-                .unwrap()
-                == "<http://example.com#Guardian>".to_string()
+            !data_buffer
+                .node_characteristics
+                .contains_key("<http://example.com#B>")
+        );
+    }
+
+    #[test]
+    fn test_write_resource_triples() {
+        let rdf_type = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+        let triples = [
+            (
+                "http://example.com#a",
+                rdf_type,
+                named("http://www.w3.org/2004/02/skos/core#Concept"),
+            ),
+            (
+                "http://example.com#a",
+                "http://www.w3.org/2004/02/skos/core#broader",
+                named("http://example.com#b"),
+            ),
+            (
+                "http://example.com#a",
+                "http://www.w3.org/2004/02/skos/core#definition",
+                Term::Literal(Literal::new_simple_literal("The first concept")),
+            ),
+        ];
+
+        let mut serializer = GraphDisplayDataSolutionSerializer::new();
+        serializer.options.mode = VisualizationMode::Rdf;
+        let mut data_buffer = SerializationDataBuffer::new();
+        for (id, element_type, target) in triples.clone() {
+            serializer.write_resource_triple(
+                &mut data_buffer,
+                Triple::new(named(id), named(element_type), Some(target)),
+            );
+        }
+        assert_eq!(
+            data_buffer
+                .node_element_buffer
+                .get("<http://www.w3.org/2004/02/skos/core#Concept>"),
+            Some(&ElementType::Rdfs(RdfsType::Node(RdfsNode::Class)))
         );
         assert!(data_buffer.edge_buffer.contains(&Edge {
-            subject: "<http://example.com#Warden1>".to_string(),
-            element_type: ElementType::NoDraw,
-            object: "<http://example.com#Guardian>".to_string()
+            subject: "<http://example.com#a>".to_string(),
+            element_type: ElementType::Rdf(RdfType::Edge(RdfEdge::RdfProperty)),
+            object: "<http://example.com#b>".to_string(),
+            property: Some("<http://www.w3.org/2004/02/skos/core#broader>".to_string()),
         }));
+        // Each triple is an edge, including the one to the literal.
+        assert_eq!(data_buffer.edge_buffer.len(), 3);
         assert!(
             data_buffer
-                .edge_redirection
-                .contains_key("<http://example.com#Warden>")
+                .label_buffer
+                .values()
+                .any(|label| label == "The first concept")
         );
+
+        let mut serializer = GraphDisplayDataSolutionSerializer::new();
+        serializer.options.mode = VisualizationMode::Skos;
+        let mut data_buffer = SerializationDataBuffer::new();
+        for (id, element_type, target) in triples.into_iter().take(2) {
+            serializer.write_resource_triple(
+                &mut data_buffer,
+                Triple::new(named(id), named(element_type), Some(target)),
+            );
+        }
         assert_eq!(
             data_buffer
-                .edge_redirection
-                .get("<http://example.com#Warden>")
-                .unwrap(),
-            "<http://example.com#Guardian>"
+                .node_element_buffer
+                .get("<http://example.com#a>"),
+            Some(&ElementType::Owl(OwlType::Node(OwlNode::Class)))
         );
-        serializer.write_node_triple(
-            &mut data_buffer,
-            Triple {
-                id: Term::NamedNode(NamedNode::new("http://example.com#Guardian").unwrap()),
-                element_type: Term::NamedNode(
-                    NamedNode::new("http://www.w3.org/2002/07/owl#equivalentClass").unwrap(),
-                ),
-                target: Some(Term::BlankNode(
-                    BlankNode::new("e1013e66f734c508511575854b0c9396").unwrap(),
-                )),
-            },
+        assert!(
+            !data_buffer
+                .node_element_buffer
+                .contains_key("<http://www.w3.org/2004/02/skos/core#Concept>")
         );
-        let s = serializer.resolve(
+        assert!(data_buffer.edge_buffer.contains(&Edge {
+            subject: "<http://example.com#a>".to_string(),
+            element_type: ElementType::Rdfs(RdfsType::Edge(RdfsEdge::SubclassOf)),
+            object: "<http://example.com#b>".to_string(),
+            property: Some("<http://www.w3.org/2004/02/skos/core#broader>".to_string()),
+        }));
+
+        // A concept first drawn as the object of `skos:broader` is a concept once typed.
+        serializer.write_resource_triple(
             &mut data_buffer,
-            "_:e1013e66f734c508511575854b0c9396".to_string(),
+            Triple::new(
+                named("http://example.com#b"),
+                named(rdf_type),
+                Some(named("http://www.w3.org/2004/02/skos/core#Concept")),
+            ),
         );
-        assert!(s.is_some());
-        for (k, v) in data_buffer.node_element_buffer.iter() {
-            println!("element_buffer: {} -> {}", k, v);
-        }
-        for (k, v) in data_buffer.edge_redirection.iter() {
-            println!("edge_redirection: {} -> {}", k, v);
-        }
-        assert!(s.unwrap() == "<http://example.com#Guardian>".to_string());
-        assert!(
-            !data_buffer
-                .edges_include_map
-                .contains_key("_:e1013e66f734c508511575854b0c9396")
+        assert_eq!(
+            data_buffer
+                .node_element_buffer
+                .get("<http://example.com#b>"),
+            Some(&ElementType::Owl(OwlType::Node(OwlNode::Class)))
         );
-        assert!(!data_buffer.edges_include_map.contains_key("Warden"));
-        print_graph_display_data(&data_buffer);
-        println!("data_buffer: {}", data_buffer);
     }
 
     pub fn print_graph_display_data(data_buffer: &SerializationDataBuffer) {
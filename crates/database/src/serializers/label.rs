@@ -0,0 +1,131 @@
+//! Derives labels from IRIs for elements which have no label in the ontology.
+
+use vowlr_util::options::LabelFallback;
+
+/// Returns the fragment of `iri` or, if it has none, its last path segment.
+///
+/// Returns `None` if both are empty, e.g. for `http://example.com/onto#`.
+pub fn local_name(iri: &str) -> Option<&str> {
+    let name = match iri.rsplit_once('#') {
+        Some((_, fragment)) => fragment,
+        None => iri.trim_end_matches('/').rsplit_once('/')?.1,
+    };
+    (!name.is_empty()).then_some(name)
+}
+
+/// Splits `name` into words at camelCase boundaries, underscores and hyphens.
+///
+/// Acronyms are kept together, e.g. `HTTPServer` becomes `HTTP Server`.
+pub fn split_camel_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut words = String::with_capacity(name.len());
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' || c == '-' {
+            if !words.ends_with(' ') {
+                words.push(' ');
+            }
+            continue;
+        }
+        if i > 0 && c.is_uppercase() && !words.ends_with(' ') {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_is_lower) {
+                words.push(' ');
+            }
+        }
+        words.push(c);
+    }
+    words.trim().to_string()
+}
+
+/// Abbreviates `iri` using the longest matching namespace in `prefixes`.
+///
+/// Each prefix is a tuple of its name and namespace IRI.
+pub fn curie(iri: &str, prefixes: &[(String, String)]) -> Option<String> {
+    prefixes
+        .iter()
+        .filter(|(_, namespace)| !namespace.is_empty() && iri.starts_with(namespace.as_str()))
+        // Named prefixes are preferred over the empty prefix for the same namespace.
+        .max_by_key(|(name, namespace)| (namespace.len(), !name.is_empty()))
+        .map(|(name, namespace)| (name, &iri[namespace.len()..]))
+        .filter(|(_, local)| !local.is_empty() && !local.contains(['/', '#']))
+        .map(|(name, local)| format!("{name}:{local}"))
+}
+
+/// Derives a label for `iri` using `strategy`.
+///
+/// `iri` may be enclosed in angle brackets, as produced by formatting a term.
+/// Prefixed names fall back to the local name if no prefix matches.
+pub fn fallback_label(
+    iri: &str,
+    strategy: LabelFallback,
+    prefixes: &[(String, String)],
+) -> Option<String> {
+    let iri = iri
+        .strip_prefix('<')
+        .and_then(|iri| iri.strip_suffix('>'))
+        .unwrap_or(iri);
+    // Blank nodes have no meaningful name.
+    if iri.starts_with("_:") {
+        return None;
+    }
+    match strategy {
+        LabelFallback::LocalName => local_name(iri).map(str::to_string),
+        LabelFallback::SplitCamelCase => local_name(iri).map(split_camel_case),
+        LabelFallback::Curie => {
+            curie(iri, prefixes).or_else(|| local_name(iri).map(str::to_string))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_local_name() {
+        assert_eq!(local_name("http://example.com/onto#Parent"), Some("Parent"));
+        assert_eq!(local_name("http://example.com/onto/Parent"), Some("Parent"));
+        assert_eq!(
+            local_name("http://example.com/onto/Parent/"),
+            Some("Parent")
+        );
+        assert_eq!(local_name("http://example.com/onto#"), None);
+    }
+
+    #[test]
+    fn test_split_camel_case() {
+        assert_eq!(split_camel_case("hasParent"), "has Parent");
+        assert_eq!(split_camel_case("HTTPServer"), "HTTP Server");
+        assert_eq!(split_camel_case("part_of"), "part of");
+        assert_eq!(split_camel_case("Class"), "Class");
+    }
+
+    #[test]
+    fn test_fallback_label() {
+        let prefixes = vec![
+            ("ex".to_string(), "http://example.com/".to_string()),
+            ("onto".to_string(), "http://example.com/onto#".to_string()),
+        ];
+        assert_eq!(
+            fallback_label(
+                "<http://example.com/onto#hasParent>",
+                LabelFallback::Curie,
+                &prefixes
+            ),
+            Some("onto:hasParent".to_string())
+        );
+        assert_eq!(
+            fallback_label(
+                "<http://other.org/a/hasParent>",
+                LabelFallback::Curie,
+                &prefixes
+            ),
+            Some("hasParent".to_string())
+        );
+        assert_eq!(
+            fallback_label("_:b0", LabelFallback::LocalName, &prefixes),
+            None
+        );
+    }
+}
@@ -0,0 +1,315 @@
+//! Selects parts of an already serialized graph, without querying the store again.
+
+use std::collections::{HashMap, HashSet};
+
+use grapher::prelude::{ElementType, GraphDisplayData, OwlEdge, OwlNode, OwlType};
+use log::warn;
+use regex::Regex;
+use vowlr_util::{
+    flags::{ElementKind, GraphFlags},
+    options::ElementFilter,
+};
+
+/// Returns the subgraph of `data` containing the elements kept by `keep`,
+/// given the type of each element and its kind in `flags`, if any.
+///
+/// Nodes are kept by their type. Edges are kept by their type and only if both of
+/// their nodes are kept. Labels, characteristics, cardinalities and flags follow their element.
+pub fn select_subgraph(
+    data: &GraphDisplayData,
+    flags: &GraphFlags,
+    keep: impl Fn(&ElementType, Option<ElementKind>) -> bool,
+) -> (GraphDisplayData, GraphFlags) {
+    select_elements(data, flags, |index| {
+        keep(&data.elements[index], flags.kinds.get(&index).copied())
+    })
+}
+
+/// Returns the subgraph of `data` containing the elements drawn by `filter`.
+///
+/// Namespaces and labels apply to named elements, i.e. classes on their node
+/// and properties on all their edges. Nodes are then removed by their degree,
+/// counting the edges left by the other criteria.
+pub fn filter_elements(
+    data: &GraphDisplayData,
+    flags: &GraphFlags,
+    filter: &ElementFilter,
+) -> (GraphDisplayData, GraphFlags) {
+    if filter.is_empty() {
+        return (data.clone(), flags.clone());
+    }
+    let label_pattern = filter
+        .label_pattern
+        .as_deref()
+        .filter(|pattern| !pattern.is_empty())
+        .and_then(|pattern| {
+            Regex::new(pattern)
+                .inspect_err(|e| warn!("Ignoring invalid label pattern '{pattern}': {e}"))
+                .ok()
+        });
+    let in_namespace = |iri: &str, namespaces: &[String]| {
+        namespaces
+            .iter()
+            .any(|namespace| !namespace.is_empty() && iri.starts_with(namespace.as_str()))
+    };
+    let (data, flags) = select_elements(data, flags, |index| {
+        let is_hidden = match data.elements[index] {
+            ElementType::Owl(OwlType::Node(OwlNode::DeprecatedClass))
+            | ElementType::Owl(OwlType::Edge(OwlEdge::DeprecatedProperty)) => !filter.deprecated,
+            ElementType::Owl(OwlType::Node(OwlNode::ExternalClass))
+            | ElementType::Owl(OwlType::Edge(OwlEdge::ExternalProperty)) => !filter.external,
+            _ => false,
+        };
+        let Some(iri) = flags
+            .iris
+            .get(&index)
+            .or(flags.properties.get(&index))
+            .and_then(|iri| iri.strip_prefix('<')?.strip_suffix('>'))
+        else {
+            return !is_hidden;
+        };
+        !is_hidden
+            && (filter.include_namespaces.is_empty()
+                || in_namespace(iri, &filter.include_namespaces))
+            && !in_namespace(iri, &filter.exclude_namespaces)
+            && label_pattern
+                .as_ref()
+                .is_none_or(|pattern| pattern.is_match(&data.labels[index]))
+    });
+
+    if filter.min_degree == 0 && filter.max_degree.is_none() {
+        return (data, flags);
+    }
+    let mut degrees: HashMap<usize, usize> = HashMap::new();
+    for [subject, _, object] in data.edges.iter() {
+        *degrees.entry(*subject).or_default() += 1;
+        if object != subject {
+            *degrees.entry(*object).or_default() += 1;
+        }
+    }
+    let edge_elements = data
+        .edges
+        .iter()
+        .map(|[_, edge, _]| *edge)
+        .collect::<HashSet<_>>();
+    select_elements(&data, &flags, |index| {
+        let degree = degrees.get(&index).copied().unwrap_or_default();
+        edge_elements.contains(&index)
+            || (degree >= filter.min_degree && filter.max_degree.is_none_or(|max| degree <= max))
+    })
+}
+
+/// Returns the subgraph of `data` containing the elements whose index is kept by `keep`.
+///
+/// Edges are only kept if both of their nodes are kept.
+fn select_elements(
+    data: &GraphDisplayData,
+    flags: &GraphFlags,
+    keep: impl Fn(usize) -> bool,
+) -> (GraphDisplayData, GraphFlags) {
+    let edge_elements = data
+        .edges
+        .iter()
+        .map(|[_, edge, _]| *edge)
+        .collect::<HashSet<_>>();
+    let kept_nodes = (0..data.elements.len())
+        .filter(|index| !edge_elements.contains(index) && keep(*index))
+        .collect::<HashSet<_>>();
+    let kept_edges = data
+        .edges
+        .iter()
+        .filter(|[subject, edge, object]| {
+            keep(*edge) && kept_nodes.contains(subject) && kept_nodes.contains(object)
+        })
+        .collect::<Vec<_>>();
+    let kept_edge_elements = kept_edges
+        .iter()
+        .map(|[_, edge, _]| *edge)
+        .collect::<HashSet<_>>();
+
+    // Elements keep their relative order, so the layout of the subgraph resembles the graph.
+    let mut subgraph = GraphDisplayData::new();
+    let mut indices = HashMap::new();
+    for (index, (element, label)) in data.elements.iter().zip(data.labels.iter()).enumerate() {
+        if kept_nodes.contains(&index) || kept_edge_elements.contains(&index) {
+            indices.insert(index, subgraph.elements.len());
+            subgraph.elements.push(*element);
+            subgraph.labels.push(label.clone());
+        }
+    }
+    subgraph.edges = kept_edges
+        .into_iter()
+        .map(|[subject, edge, object]| [indices[subject], indices[edge], indices[object]])
+        .collect();
+    subgraph.characteristics = data
+        .characteristics
+        .iter()
+        .filter_map(|(index, characteristics)| {
+            Some((*indices.get(index)?, characteristics.clone()))
+        })
+        .collect();
+    subgraph.cardinalities = data
+        .cardinalities
+        .iter()
+        .filter_map(|(index, cardinality)| Some((*indices.get(index)?, cardinality.clone())))
+        .collect();
+    (subgraph, flags.remap(&indices))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use grapher::prelude::{RdfsEdge, RdfsType};
+
+    #[test]
+    fn test_select_subgraph() {
+        let class = ElementType::Owl(OwlType::Node(OwlNode::Class));
+        let external = ElementType::Owl(OwlType::Node(OwlNode::ExternalClass));
+        let subclass_of = ElementType::Rdfs(RdfsType::Edge(RdfsEdge::SubclassOf));
+        let property = ElementType::Owl(OwlType::Edge(OwlEdge::ObjectProperty));
+
+        let mut data = GraphDisplayData::new();
+        for (element, label) in [
+            (class, "A"),
+            (external, "B"),
+            (class, "C"),
+            (subclass_of, "Subclass of"),
+            (property, "knows"),
+        ] {
+            data.elements.push(element);
+            data.labels.push(label.to_string());
+        }
+        data.edges = vec![[2, 3, 0], [0, 4, 1]];
+        data.characteristics.insert(2, "unsatisfiable".to_string());
+        data.characteristics.insert(4, "functional".to_string());
+
+        let mut flags = GraphFlags::default();
+        flags.kinds.insert(3, ElementKind::SubPropertyOf);
+        flags.inferred.extend([3, 4]);
+        flags.iris.insert(2, "http://example.com#C".to_string());
+
+        let (subgraph, subgraph_flags) =
+            select_subgraph(&data, &flags, |element, _| *element != external);
+        assert_eq!(subgraph.elements, vec![class, class, subclass_of]);
+        assert_eq!(subgraph.labels, vec!["A", "C", "Subclass of"]);
+        assert_eq!(subgraph.edges, vec![[1, 2, 0]]);
+        assert_eq!(
+            subgraph.characteristics,
+            HashMap::from([(1, "unsatisfiable".to_string())])
+        );
+        assert_eq!(
+            subgraph_flags.kinds,
+            HashMap::from([(2, ElementKind::SubPropertyOf)])
+        );
+        assert_eq!(subgraph_flags.inferred, HashSet::from([2]));
+        assert_eq!(
+            subgraph_flags.iris,
+            HashMap::from([(1, "http://example.com#C".to_string())])
+        );
+
+        // Elements of a kind are kept by their kind, not by the type they're drawn as.
+        let (subgraph, _) = select_subgraph(&data, &flags, |element, kind| match kind {
+            Some(kind) => kind != ElementKind::SubPropertyOf,
+            None => *element != subclass_of,
+        });
+        assert_eq!(subgraph.edges, vec![[0, 3, 1]]);
+    }
+
+    #[test]
+    fn test_filter_elements() {
+        let class = ElementType::Owl(OwlType::Node(OwlNode::Class));
+        let external = ElementType::Owl(OwlType::Node(OwlNode::ExternalClass));
+        let subclass_of = ElementType::Rdfs(RdfsType::Edge(RdfsEdge::SubclassOf));
+        let property = ElementType::Owl(OwlType::Edge(OwlEdge::ObjectProperty));
+
+        let mut data = GraphDisplayData::new();
+        for (element, label) in [
+            (class, "Animal"),
+            (class, "Being"),
+            (class, "Cat"),
+            (external, "Person"),
+            (subclass_of, "Subclass of"),
+            (subclass_of, "Subclass of"),
+            (property, "knows"),
+        ] {
+            data.elements.push(element);
+            data.labels.push(label.to_string());
+        }
+        data.edges = vec![[0, 4, 1], [2, 5, 0], [0, 6, 3]];
+        let mut flags = GraphFlags::default();
+        for (index, iri) in [
+            "<http://example.com#A>",
+            "<http://example.com#B>",
+            "<http://example.com#C>",
+            "<http://xmlns.com/foaf/0.1/Person>",
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            flags.iris.insert(index, iri.to_string());
+        }
+        flags
+            .properties
+            .insert(6, "<http://example.com#knows>".to_string());
+
+        // Removing the external class leaves B and C as leaves.
+        let filter = ElementFilter {
+            external: false,
+            min_degree: 2,
+            ..ElementFilter::default()
+        };
+        let (filtered, _) = filter_elements(&data, &flags, &filter);
+        assert_eq!(filtered.labels, vec!["Animal"]);
+        assert!(filtered.edges.is_empty());
+
+        let filter = ElementFilter {
+            exclude_namespaces: vec!["http://xmlns.com/".to_string()],
+            label_pattern: Some("^(Animal|Cat)$".to_string()),
+            ..ElementFilter::default()
+        };
+        let (filtered, _) = filter_elements(&data, &flags, &filter);
+        assert_eq!(filtered.labels, vec!["Animal", "Cat", "Subclass of"]);
+        assert_eq!(filtered.edges, vec![[1, 2, 0]]);
+    }
+
+    #[test]
+    fn test_filter_elements_leaves_nothing_behind() {
+        let class = ElementType::Owl(OwlType::Node(OwlNode::Class));
+        let property = ElementType::Owl(OwlType::Edge(OwlEdge::ObjectProperty));
+
+        let mut data = GraphDisplayData::new();
+        for (element, label) in [(class, "A"), (class, "B"), (property, "knows")] {
+            data.elements.push(element);
+            data.labels.push(label.to_string());
+        }
+        data.edges = vec![[0, 2, 1]];
+        data.characteristics.insert(1, "unsatisfiable".to_string());
+        data.characteristics.insert(2, "functional".to_string());
+        data.cardinalities
+            .push((2, ("1".to_string(), Some("2".to_string()))));
+        let mut flags = GraphFlags::default();
+        flags.iris.insert(0, "<http://example.com#A>".to_string());
+        flags.iris.insert(1, "<http://example.org#B>".to_string());
+        flags
+            .properties
+            .insert(2, "<http://example.com#knows>".to_string());
+        flags.inferred.insert(2);
+
+        let filter = ElementFilter {
+            exclude_namespaces: vec!["http://example.org#".to_string()],
+            ..ElementFilter::default()
+        };
+        let (filtered, filtered_flags) = filter_elements(&data, &flags, &filter);
+        assert_eq!(filtered.elements, vec![class]);
+        assert_eq!(filtered.labels, vec!["A"]);
+        assert!(filtered.edges.is_empty());
+        assert!(filtered.characteristics.is_empty());
+        assert!(filtered.cardinalities.is_empty());
+        assert_eq!(
+            filtered_flags.iris,
+            HashMap::from([(0, "<http://example.com#A>".to_string())])
+        );
+        assert!(filtered_flags.properties.is_empty());
+        assert!(filtered_flags.inferred.is_empty());
+    }
+}
@@ -1,46 +1,294 @@
-use futures::{StreamExt, stream::BoxStream};
+use futures::{
+    Stream, StreamExt,
+    future::{Either, select},
+    stream::BoxStream,
+};
+use grapher::prelude::{ElementType, GraphDisplayData};
 use log::{info, warn};
-use rdf_fusion::store::Store;
+use rdf_fusion::{
+    execution::results::{QuadStream, QueryResults},
+    model::{GraphName, GraphNameRef, NamedNodeRef, NamedOrBlankNode, Quad, Term},
+    store::Store,
+};
 use std::path::Path;
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::Duration;
-use std::{fs::File, time::Instant};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fs::File,
+    time::Instant,
+};
+use tokio::sync::{Notify, watch};
 
+use crate::{
+    cache::{CacheKey, CacheMetrics, GraphCache},
+    consistency::check,
+    inference::{Fact, infer},
+    results::{result_term, write_results},
+    serializers::{
+        frontend::GraphDisplayDataSolutionSerializer,
+        subgraph::{filter_elements, select_subgraph},
+    },
+};
 use vowlr_parser::{
-    errors::WebVowlStoreError,
-    parser_util::{parse_stream_to, parser_from_format},
+    errors::{WebVowlStoreError, WebVowlStoreErrorKind},
+    parser_util::{PreparedDocument, parse_stream_to, prepare_document},
+    profiles::validate_rdf_xml,
+};
+use vowlr_sparql_queries::prelude::{INFERRED_GRAPH, QueryAssembler};
+use vowlr_util::{
+    datatypes::DataType,
+    diagnostics::{Diagnostic, ProfileViolation},
+    flags::{ElementKind, GraphFlags},
+    limits::{GraphRequest, QueryLimits, QueryOutcome},
+    options::{ElementFilter, SerializerOptions, VisualizationMode},
+    results::{ResultFormat, ResultTerm, SolutionTable, SparqlResults},
 };
-use vowlr_util::datatypes::DataType;
+
+/// The prefix declarations of the documents loaded into each graph.
+///
+/// - Key = The graph the documents were loaded into.
+/// - Value = A map from prefix name to namespace IRI.
+type GraphPrefixes = Arc<RwLock<HashMap<GraphName, BTreeMap<String, String>>>>;
+
+/// The base IRIs declared by the documents loaded into each graph.
+///
+/// - Key = The graph the documents were loaded into.
+/// - Value = The declared base IRIs, e.g. from `xml:base` or `@base`.
+type GraphBaseIris = Arc<RwLock<HashMap<GraphName, BTreeSet<String>>>>;
+
+/// The recently serialized graphs.
+type SharedGraphCache = Arc<Mutex<GraphCache>>;
+
+/// The generation whose consequences the inferred graph holds, if any.
+///
+/// Held while materializing, so concurrent requests wait for a single materialization.
+type MaterializedGeneration = Arc<tokio::sync::Mutex<Option<u64>>>;
+
+/// A value computed from the documents of a generation, along with that generation.
+///
+/// Held while computing, so concurrent requests wait for a single computation.
+type GenerationCache<T> = Arc<tokio::sync::Mutex<Option<(u64, T)>>>;
+
+/// The latest graph request of each client, along with a notification cancelling it.
+///
+/// - Key = [`GraphRequest::client`].
+/// - Value = [`GraphRequest::sequence`] and the notification cancelling that request.
+type LatestRequests = Arc<Mutex<HashMap<u64, (u64, Arc<Notify>)>>>;
+
+/// The number of inferred triples inserted into the store at once.
+const INFERENCE_BATCH_SIZE: usize = 10_000;
 
 static GLOBAL_STORE: std::sync::OnceLock<Store> = std::sync::OnceLock::new();
+static GLOBAL_PREFIXES: std::sync::OnceLock<GraphPrefixes> = std::sync::OnceLock::new();
+static GLOBAL_BASE_IRIS: std::sync::OnceLock<GraphBaseIris> = std::sync::OnceLock::new();
+static GLOBAL_INFERRED: std::sync::OnceLock<MaterializedGeneration> = std::sync::OnceLock::new();
+static GLOBAL_GENERATION: std::sync::OnceLock<watch::Sender<u64>> = std::sync::OnceLock::new();
+static GLOBAL_GRAPH_CACHE: std::sync::OnceLock<SharedGraphCache> = std::sync::OnceLock::new();
+static GLOBAL_REQUESTS: std::sync::OnceLock<LatestRequests> = std::sync::OnceLock::new();
+static GLOBAL_DIAGNOSTICS: std::sync::OnceLock<GenerationCache<Vec<Diagnostic>>> =
+    std::sync::OnceLock::new();
+static GLOBAL_PROFILE_VIOLATIONS: std::sync::OnceLock<GenerationCache<Vec<ProfileViolation>>> =
+    std::sync::OnceLock::new();
+
+/// The profile violations of the empty store at its first generation.
+fn no_profile_violations() -> GenerationCache<Vec<ProfileViolation>> {
+    Arc::new(tokio::sync::Mutex::new(Some((0, Vec::new()))))
+}
 
 pub struct VOWLRStore {
     pub session: Store,
+    prefixes: GraphPrefixes,
+    base_iris: GraphBaseIris,
+    /// The generation whose consequences the inferred graph holds.
+    inferred: MaterializedGeneration,
+    /// Increments whenever documents are loaded, updated or cleared,
+    /// so graphs serialized before can be told apart.
+    generation: watch::Sender<u64>,
+    graph_cache: SharedGraphCache,
+    requests: LatestRequests,
+    /// The diagnostics of the latest generation checked.
+    diagnostics: GenerationCache<Vec<Diagnostic>>,
+    /// The profile violations of the latest generation validated.
+    profile_violations: GenerationCache<Vec<ProfileViolation>>,
+    limits: QueryLimits,
     upload_handle: Option<tempfile::NamedTempFile>,
 }
 impl VOWLRStore {
     pub fn new(session: Store) -> Self {
         Self {
             session,
+            prefixes: GraphPrefixes::default(),
+            base_iris: GraphBaseIris::default(),
+            inferred: Arc::default(),
+            generation: watch::channel(0).0,
+            graph_cache: SharedGraphCache::default(),
+            requests: LatestRequests::default(),
+            diagnostics: GenerationCache::default(),
+            profile_violations: no_profile_violations(),
+            limits: QueryLimits::default(),
             upload_handle: None,
         }
     }
 
     pub fn default() -> Self {
         let session = GLOBAL_STORE.get_or_init(Store::default).clone();
+        let prefixes = GLOBAL_PREFIXES.get_or_init(GraphPrefixes::default).clone();
+        let base_iris = GLOBAL_BASE_IRIS.get_or_init(GraphBaseIris::default).clone();
+        let inferred = GLOBAL_INFERRED.get_or_init(Arc::default).clone();
+        let generation = GLOBAL_GENERATION
+            .get_or_init(|| watch::channel(0).0)
+            .clone();
+        let graph_cache = GLOBAL_GRAPH_CACHE
+            .get_or_init(SharedGraphCache::default)
+            .clone();
+        let requests = GLOBAL_REQUESTS.get_or_init(LatestRequests::default).clone();
+        let diagnostics = GLOBAL_DIAGNOSTICS
+            .get_or_init(GenerationCache::default)
+            .clone();
+        let profile_violations = GLOBAL_PROFILE_VIOLATIONS
+            .get_or_init(no_profile_violations)
+            .clone();
         Self {
             session,
+            prefixes,
+            base_iris,
+            inferred,
+            generation,
+            graph_cache,
+            requests,
+            diagnostics,
+            profile_violations,
+            limits: QueryLimits::default(),
             upload_handle: None,
         }
     }
 
+    /// Returns the prefix declarations of all loaded documents.
+    ///
+    /// If graphs declare the same prefix name, an arbitrary declaration is used.
+    pub fn prefixes(&self) -> BTreeMap<String, String> {
+        self.prefixes
+            .read()
+            .map(|graphs| {
+                graphs
+                    .values()
+                    .flatten()
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the prefix declarations of the documents loaded into `graph`.
+    pub fn graph_prefixes(&self, graph: &GraphName) -> BTreeMap<String, String> {
+        self.prefixes
+            .read()
+            .ok()
+            .and_then(|graphs| graphs.get(graph).cloned())
+            .unwrap_or_default()
+    }
+
+    /// Remembers the prefix declarations of a document loaded into `graph`.
+    ///
+    /// Later declarations override earlier ones with the same prefix name.
+    fn add_prefixes(&self, graph: GraphName, prefixes: BTreeMap<String, String>) {
+        match self.prefixes.write() {
+            Ok(mut graphs) => graphs.entry(graph).or_default().extend(prefixes),
+            Err(e) => warn!("Failed to store prefixes: {e}"),
+        }
+    }
+
+    /// Returns the base IRIs declared by all loaded documents.
+    pub fn base_iris(&self) -> BTreeSet<String> {
+        self.base_iris
+            .read()
+            .map(|graphs| graphs.values().flatten().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Remembers the base IRIs declared by a document loaded into `graph`.
+    fn add_base_iris(&self, graph: GraphName, base_iris: Vec<String>) {
+        match self.base_iris.write() {
+            Ok(mut graphs) => graphs.entry(graph).or_default().extend(base_iris),
+            Err(e) => warn!("Failed to store base IRIs: {e}"),
+        }
+    }
+
     // TTL format -> (oxittl) RDF XML quads -> (horned_owl) Normalize OWL/RDF -> Quads -> Insert into Oxigraph
     pub async fn insert_file(&self, fs: &Path, lenient: bool) -> Result<(), WebVowlStoreError> {
-        let parser = parser_from_format(fs, lenient)?;
+        let document = prepare_document(fs, lenient)?;
+        self.load_prepared(document).await
+    }
+
+    /// Loads `data` of `format` into the default graph, as with a Graph Store Protocol `POST`.
+    ///
+    /// With `replace`, the loaded documents are replaced, as with a `PUT`.
+    /// The document is prepared before the loaded documents are cleared, so documents which
+    /// can't be prepared, e.g. of an unsupported type, leave the loaded documents unchanged.
+    /// Should the document fail to load after clearing, the cleared documents are loaded again.
+    pub async fn insert_document(
+        &self,
+        data: &[u8],
+        format: DataType,
+        replace: bool,
+    ) -> Result<(), WebVowlStoreError> {
+        // The parsers tell the type of a document by its file extension.
+        let mut file = tempfile::Builder::new()
+            .suffix(&format!(".{}", format.extension()))
+            .tempfile()?;
+        std::io::Write::write_all(&mut file, data)?;
+        std::io::Write::flush(&mut file)?;
+        let document = prepare_document(file.path(), false)?;
+        if !replace {
+            return self.load_prepared(document).await;
+        }
+
+        let mut quads = Vec::new();
+        let mut asserted = self.asserted_quads().await?;
+        while let Some(quad) = asserted.next().await {
+            quads.push(quad?);
+        }
+        let cleared = PreparedDocument {
+            quads,
+            prefixes: self.prefixes(),
+            base_iris: self.base_iris().into_iter().collect(),
+            violations: None,
+        };
+        self.clear().await?;
+        if let Err(e) = self.load_prepared(document).await {
+            warn!("Loading the cleared documents again, as the document failed to load: {e}");
+            // The document may have been loaded in part.
+            self.clear().await?;
+            self.load_prepared(cleared).await?;
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Loads the prepared `document` into the graphs of its quads, usually the default graph.
+    async fn load_prepared(&self, document: PreparedDocument) -> Result<(), WebVowlStoreError> {
         info!("Loading input into database...");
         let start_time = Instant::now();
-        self.session
-            .load_from_reader(parser.parser, parser.input.as_slice())
-            .await?;
+        let previous = self.generation();
+        // The declarations belong to the graphs the quads of the document are loaded into.
+        let mut graphs = document
+            .quads
+            .iter()
+            .map(|quad| quad.graph_name.clone())
+            .collect::<HashSet<_>>();
+        if graphs.is_empty() {
+            graphs.insert(GraphName::DefaultGraph);
+        }
+        self.session.extend(document.quads).await?;
+        for graph in graphs {
+            self.add_prefixes(graph.clone(), document.prefixes.clone());
+            self.add_base_iris(graph, document.base_iris.clone());
+        }
+        self.clear_inferences().await?;
+        self.next_generation();
+        self.add_profile_violations(previous, document.violations)
+            .await;
         info!(
             "Loaded {} quads in {} s",
             self.session.len().await.unwrap(),
@@ -52,9 +300,18 @@ impl VOWLRStore {
         Ok(())
     }
 
+    /// Streams the asserted quads, leaving out the inferred graph.
+    async fn asserted_quads(&self) -> Result<QuadStream, WebVowlStoreError> {
+        Ok(self
+            .session
+            .quads_for_pattern(None, None, None, Some(GraphNameRef::DefaultGraph))
+            .await?)
+    }
+
     pub async fn serialize_to_file(&self, path: &Path) -> Result<(), WebVowlStoreError> {
         let mut file = File::create(path)?;
-        let mut results = parse_stream_to(self.session.stream().await?, DataType::OWL).await?;
+        let mut results =
+            parse_stream_to(self.asserted_quads().await?, DataType::OWL, self.prefixes()).await?;
         while let Some(result) = results.next().await {
             let result = result.unwrap();
             std::io::Write::write_all(&mut file, &result)?;
@@ -71,188 +328,646 @@ impl VOWLRStore {
             "Store size before export: {}",
             self.session.len().await.unwrap_or(0)
         );
-        let results = parse_stream_to(self.session.stream().await?, resource_type).await?;
+        let results =
+            parse_stream_to(self.asserted_quads().await?, resource_type, self.prefixes()).await?;
         Ok(results)
     }
 
-    pub async fn start_upload(&mut self, filename: &str) -> Result<(), WebVowlStoreError> {
-        let extension = Path::new(filename)
-            .extension()
-            .and_then(|e| e.to_str())
-            .unwrap_or("owl");
-        let file = tempfile::Builder::new()
-            .suffix(&format!(".{}", extension))
-            .tempfile()?;
-        self.upload_handle = Some(file);
-        Ok(())
+    /// Returns the distinct language tags of all labels in the store, sorted alphabetically.
+    pub async fn label_languages(&self) -> Result<Vec<String>, WebVowlStoreError> {
+        let query = QueryAssembler::assemble_label_languages_query();
+        let mut languages = Vec::new();
+        if let QueryResults::Solutions(mut solutions) = self.session.query(query.as_str()).await? {
+            while let Some(solution) = solutions.next().await {
+                if let Some(Term::Literal(language)) = solution?.get("lang") {
+                    languages.push(language.value().to_string());
+                }
+            }
+        }
+        Ok(languages)
     }
 
-    pub async fn upload_chunk(&mut self, data: &[u8]) -> Result<(), WebVowlStoreError> {
-        if let Some(file) = &mut self.upload_handle {
-            std::io::Write::write_all(file, data)?;
-            Ok(())
-        } else {
-            warn!("upload_chunk called without start_upload");
-            Ok(())
+    /// Returns the generation of the loaded documents.
+    ///
+    /// The generation increments whenever documents are loaded, updated or cleared.
+    pub fn generation(&self) -> u64 {
+        *self.generation.borrow()
+    }
+
+    /// Streams the generations the documents move on to from now on.
+    ///
+    /// Generations moved on to while the previous one is still unread are skipped,
+    /// so only the latest is yielded.
+    pub fn changes(&self) -> impl Stream<Item = u64> + Send + 'static {
+        futures::stream::unfold(self.generation.subscribe(), |mut receiver| async move {
+            receiver.changed().await.ok()?;
+            let generation = *receiver.borrow_and_update();
+            Some((generation, receiver))
+        })
+    }
+
+    /// Moves on to the next generation, forgetting the graphs serialized before.
+    fn next_generation(&self) {
+        let mut generation = 0;
+        self.generation.send_modify(|current| {
+            *current += 1;
+            generation = *current;
+        });
+        match self.graph_cache.lock() {
+            Ok(mut cache) => cache.retain_generation(generation),
+            Err(e) => warn!("Failed to clear the graph cache: {e}"),
         }
     }
 
-    pub async fn complete_upload(&mut self) -> Result<(), WebVowlStoreError> {
-        if let Some(file) = &mut self.upload_handle {
-            std::io::Write::flush(file)?;
-            let path = file.path();
-            let parser = parser_from_format(path, false)?;
-
-            info!("Loading input into database...");
-            let start_time = Instant::now();
-            self.session
-                .load_from_reader(parser.parser, parser.input.as_slice())
-                .await?;
-            info!(
-                "Loaded {} quads in {} s",
-                self.session.len().await.unwrap(),
-                Instant::now()
-                    .checked_duration_since(start_time)
-                    .unwrap_or(Duration::new(0, 0))
-                    .as_secs_f32()
-            );
+    /// Removes all documents, prefixes and inferences.
+    pub async fn clear(&self) -> Result<(), WebVowlStoreError> {
+        self.session.clear().await?;
+        match self.prefixes.write() {
+            Ok(mut prefixes) => prefixes.clear(),
+            Err(e) => warn!("Failed to clear prefixes: {e}"),
         }
-        self.upload_handle = None;
+        match self.base_iris.write() {
+            Ok(mut base_iris) => base_iris.clear(),
+            Err(e) => warn!("Failed to clear base IRIs: {e}"),
+        }
+        self.next_generation();
+        *self.profile_violations.lock().await = Some((self.generation(), Vec::new()));
         Ok(())
     }
-}
 
-pub const DEFAULT_QUERY_1: &str = r#"
-    PREFIX owl: <http://www.w3.org/2002/07/owl#>
-    PREFIX rdfs: <http://www.w3.org/2000/01/rdf-schema#>
-    PREFIX rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#>
-    PREFIX xsd: <http://www.w3.org/2001/XMLSchema#>
-    PREFIX vowlr: <http://www.example.com/iri#>
+    /// Returns the hits and misses of the graph cache.
+    pub fn cache_metrics(&self) -> CacheMetrics {
+        self.graph_cache
+            .lock()
+            .map(|cache| cache.metrics())
+            .unwrap_or_default()
+    }
+
+    /// Limits the resources used by each query of this store.
+    pub fn with_limits(mut self, limits: QueryLimits) -> Self {
+        self.limits = limits;
+        self
+    }
 
-    SELECT ?id ?nodeType ?label
-    WHERE {
-        {
-            # 1. Identify Named Classes
-            ?id a owl:Class .
-            FILTER(isIRI(?id))
-            BIND(owl:Class AS ?nodeType)
-            OPTIONAL { ?id rdfs:label ?label }
+    /// Returns the limits on the resources used by each query.
+    pub fn limits(&self) -> QueryLimits {
+        self.limits
+    }
+
+    /// Runs `query`, cancelling it if it doesn't complete within the timeout.
+    async fn with_timeout<T>(
+        &self,
+        query: impl Future<Output = Result<T, WebVowlStoreError>>,
+    ) -> Result<T, WebVowlStoreError> {
+        match tokio::time::timeout(self.limits.timeout, query).await {
+            Ok(result) => result,
+            Err(_) => Err(WebVowlStoreErrorKind::Timeout(self.limits.timeout).into()),
         }
-        UNION
-        {
-            ?id rdf:type owl:Ontology .
-            BIND(owl:Ontology AS ?nodeType)
+    }
+
+    /// Runs `query` for `request`, cancelling it once the same client requests a newer graph.
+    ///
+    /// Queries without a request are never cancelled.
+    pub async fn for_request<T>(
+        &self,
+        request: Option<GraphRequest>,
+        query: impl Future<Output = Result<T, WebVowlStoreError>>,
+    ) -> Result<T, WebVowlStoreError> {
+        let Some(request) = request else {
+            return query.await;
+        };
+        let cancelled = Arc::new(Notify::new());
+        let superseded = match self.requests.lock() {
+            Ok(mut requests) => match requests.get(&request.client) {
+                Some((sequence, _)) if *sequence > request.sequence => true,
+                _ => {
+                    if let Some((_, previous)) =
+                        requests.insert(request.client, (request.sequence, cancelled.clone()))
+                    {
+                        previous.notify_one();
+                    }
+                    false
+                }
+            },
+            Err(e) => {
+                warn!("Failed to register the graph request: {e}");
+                false
+            }
+        };
+        if superseded {
+            return Err(WebVowlStoreErrorKind::Cancelled.into());
         }
-        UNION
+
+        let result = match select(std::pin::pin!(query), std::pin::pin!(cancelled.notified())).await
         {
-            ?id rdf:type owl:ObjectProperty .
-            BIND(owl:ObjectProperty AS ?nodeType)
-        }
-        UNION
+            Either::Left((result, _)) => result,
+            Either::Right(_) => {
+                info!("Cancelled a graph request superseded by a newer one");
+                Err(WebVowlStoreErrorKind::Cancelled.into())
+            }
+        };
+        if let Ok(mut requests) = self.requests.lock()
+            && requests
+                .get(&request.client)
+                .is_some_and(|(sequence, _)| *sequence == request.sequence)
         {
-            ?id rdfs:domain ?label .
-            BIND(rdfs:domain AS ?nodeType)
+            requests.remove(&request.client);
         }
-        UNION
-        {
-            ?id rdfs:range ?label .
-            BIND(rdfs:range AS ?nodeType)
+        result
+    }
+
+    /// Queries the graph drawn by `query` and serializes it with `options`.
+    ///
+    /// Only the first [`QueryLimits::max_solutions`] solutions are serialized,
+    /// see [`QueryOutcome::truncated`].
+    ///
+    /// Serialized graphs are cached until documents are loaded or cleared,
+    /// so drawing the same graph again doesn't query the store.
+    /// Graphs of documents changed while querying aren't cached.
+    pub async fn graph(
+        &self,
+        query: &str,
+        options: SerializerOptions,
+    ) -> Result<(GraphDisplayData, GraphFlags, QueryOutcome), WebVowlStoreError> {
+        let mut graphs = vec![GraphName::DefaultGraph.to_string()];
+        if options.inference {
+            graphs.push(INFERRED_GRAPH.to_string());
         }
-        UNION
-        {
-            # 2. Identify Intersections
-            # Any node (usually blank) that is the subject of an intersectionOf list
-            ?id owl:intersectionOf ?label .
-            BIND(owl:intersectionOf AS ?nodeType)
+        let key = CacheKey::new(graphs, query, &options, &self.limits, self.generation());
+        let cached = match self.graph_cache.lock() {
+            Ok(mut cache) => cache.get(&key),
+            Err(e) => {
+                warn!("Failed to read the graph cache: {e}");
+                None
+            }
+        };
+        if let Some(graph) = cached {
+            info!("Using the cached graph: {}", self.cache_metrics());
+            return Ok(graph);
         }
-        UNION
-        {
-            # 3. Identify Unions
-            ?id owl:unionOf ?label .
-            BIND(owl:unionOf AS ?nodeType)
+
+        let prefixed_query = self.with_document_prefixes(query);
+        let (mut data, mut flags) = (GraphDisplayData::new(), GraphFlags::default());
+        // Materializing inferences and checking diagnostics count towards the timeout,
+        // as they take as long as the query itself on large documents.
+        let outcome = self
+            .with_timeout(async {
+                if options.inference {
+                    self.ensure_inferences().await?;
+                }
+                // The graph is still drawn if the checks fail, just without highlights.
+                let diagnostics = if options.diagnostics {
+                    self.diagnostics().await.unwrap_or_else(|e| {
+                        warn!("Failed to check the documents for diagnostics: {e}");
+                        Vec::new()
+                    })
+                } else {
+                    Vec::new()
+                };
+                let serializer = GraphDisplayDataSolutionSerializer::with_options(options)
+                    .with_prefixes(self.prefixes())
+                    .with_base_iris(self.base_iris())
+                    .with_diagnostics(diagnostics)
+                    .with_max_solutions(self.limits.max_solutions);
+                let QueryResults::Solutions(solutions) =
+                    self.session.query(prefixed_query.as_str()).await?
+                else {
+                    return Err("Query stream is not a solutions stream".to_string().into());
+                };
+                serializer
+                    .serialize_nodes_stream(&mut data, &mut flags, solutions)
+                    .await
+            })
+            .await?;
+
+        // The generation is checked while holding the cache, which is cleared of stale graphs
+        // only after moving on to the next generation, see `next_generation`.
+        match self.graph_cache.lock() {
+            Ok(_) if self.generation() != key.generation => {
+                info!("Not caching the graph, as the documents changed while querying")
+            }
+            Ok(mut cache) => {
+                cache.insert(key, (data.clone(), flags.clone(), outcome));
+                info!("Cached the graph: {}", cache.metrics());
+            }
+            Err(e) => warn!("Failed to cache the graph: {e}"),
         }
-        UNION
+        Ok((data, flags, outcome))
+    }
+
+    /// Returns the graph drawn by `query` with the elements drawn by `filter`,
+    /// without the elements whose type is `hidden` and those whose kind is `hidden_kinds`,
+    /// see [`GraphFlags::kinds`].
+    ///
+    /// Elements of a kind are only hidden by their kind, not by the type they're drawn as.
+    ///
+    /// The elements are selected from the cached graph, which is much faster
+    /// than querying the store again. The graph is queried and cached first if needed.
+    pub async fn filtered_graph(
+        &self,
+        query: &str,
+        options: SerializerOptions,
+        filter: &ElementFilter,
+        hidden: &HashSet<ElementType>,
+        hidden_kinds: &HashSet<ElementKind>,
+    ) -> Result<(GraphDisplayData, GraphFlags, QueryOutcome), WebVowlStoreError> {
+        let (data, flags, outcome) = self.graph(query, options).await?;
+        let start_time = Instant::now();
+        let (data, flags) = filter_elements(&data, &flags, filter);
+        let (data, flags) = select_subgraph(&data, &flags, |element, kind| match kind {
+            Some(kind) => !hidden_kinds.contains(&kind),
+            None => !hidden.contains(element),
+        });
+        info!(
+            "Filtered graph in {} s",
+            Instant::now()
+                .checked_duration_since(start_time)
+                .unwrap_or(Duration::new(0, 0))
+                .as_secs_f32()
+        );
+        Ok((data, flags, outcome))
+    }
+
+    /// Returns the IRI and label of the named elements within reach of `focus`,
+    /// each a tuple of its IRI and radius, ordered by label.
+    pub async fn neighborhood_elements(
+        &self,
+        focus: &[(String, usize)],
+        languages: &[String],
+    ) -> Result<Vec<(String, String)>, WebVowlStoreError> {
+        let query = QueryAssembler::neighborhood_elements_query(focus, languages)
+            .map_err(|e| e.to_string())?
+            .build();
+        self.with_timeout(async {
+            let mut elements = Vec::new();
+            if let QueryResults::Solutions(mut solutions) =
+                self.session.query(query.as_str()).await?
+            {
+                while let Some(solution) = solutions.next().await {
+                    let solution = solution?;
+                    if let Some(Term::NamedNode(id)) = solution.get("id") {
+                        let label = match solution.get("label") {
+                            Some(Term::Literal(label)) => label.value().to_string(),
+                            _ => String::new(),
+                        };
+                        elements.push((id.as_str().to_string(), label));
+                    }
+                }
+            }
+            Ok(elements)
+        })
+        .await
+    }
+
+    /// Declares the prefixes of the loaded documents in the user-provided `query`.
+    fn with_document_prefixes(&self, query: &str) -> String {
+        QueryAssembler::with_document_prefixes(
+            query,
+            self.prefixes()
+                .iter()
+                .map(|(name, iri)| (name.as_str(), iri.as_str())),
+        )
+    }
+
+    /// Runs `query` of any form for the SPARQL console.
+    ///
+    /// Only the first [`QueryLimits::max_rows`] solutions or triples are returned,
+    /// see [`SolutionTable::truncated`].
+    pub async fn console_query(&self, query: &str) -> Result<SparqlResults, WebVowlStoreError> {
+        self.query_results(query, self.limits.max_rows).await
+    }
+
+    /// Runs `query` of any form, returning up to `max_rows` solutions or triples.
+    pub async fn query_results(
+        &self,
+        query: &str,
+        max_rows: usize,
+    ) -> Result<SparqlResults, WebVowlStoreError> {
+        let query = self.with_document_prefixes(query);
+        self.with_timeout(async {
+            Ok(match self.session.query(query.as_str()).await? {
+                QueryResults::Solutions(mut solutions) => {
+                    let mut table = SolutionTable {
+                        variables: solutions
+                            .variables()
+                            .iter()
+                            .map(|variable| variable.as_str().to_string())
+                            .collect(),
+                        ..SolutionTable::default()
+                    };
+                    while let Some(solution) = solutions.next().await {
+                        if table.rows.len() >= max_rows {
+                            table.truncated = true;
+                            break;
+                        }
+                        let solution = solution?;
+                        let row = table
+                            .variables
+                            .iter()
+                            .map(|variable| solution.get(variable.as_str()).map(result_term))
+                            .collect();
+                        table.rows.push(row);
+                    }
+                    SparqlResults::Solutions(table)
+                }
+                QueryResults::Boolean(answer) => SparqlResults::Boolean(answer),
+                QueryResults::Graph(mut triples) => {
+                    let mut table = SolutionTable {
+                        variables: ["subject", "predicate", "object"]
+                            .map(String::from)
+                            .to_vec(),
+                        ..SolutionTable::default()
+                    };
+                    while let Some(triple) = triples.next().await {
+                        if table.rows.len() >= max_rows {
+                            table.truncated = true;
+                            break;
+                        }
+                        let triple = triple?;
+                        table.rows.push(vec![
+                            Some(result_term(&triple.subject.into())),
+                            Some(ResultTerm::Iri(triple.predicate.as_str().to_string())),
+                            Some(result_term(&triple.object)),
+                        ]);
+                    }
+                    SparqlResults::Graph(table)
+                }
+            })
+        })
+        .await
+    }
+
+    /// Writes `results` in `format`, which should be one of [`SparqlResults::formats`].
+    ///
+    /// Triples are written using the prefixes of the loaded documents.
+    pub fn write_results(
+        &self,
+        results: &SparqlResults,
+        format: ResultFormat,
+    ) -> Result<Vec<u8>, WebVowlStoreError> {
+        write_results(results, format, &self.prefixes())
+    }
+
+    /// Runs the SPARQL Update `update` against the loaded documents.
+    ///
+    /// As the documents change, the inferences are cleared and the cached graphs forgotten.
+    /// This happens even if the update fails or times out, as it may have changed the documents
+    /// before that.
+    pub async fn update(&self, update: &str) -> Result<(), WebVowlStoreError> {
+        let update = self.with_document_prefixes(update);
+        let updated = self
+            .with_timeout(async { Ok(self.session.update(update.as_str()).await?) })
+            .await;
+        let cleared = self.clear_inferences().await;
+        self.next_generation();
+        updated.and(cleared)
+    }
+
+    /// Draws the triples of the `CONSTRUCT` or `DESCRIBE` `query` as a new graph.
+    ///
+    /// The triples are loaded into a separate store and drawn as plain RDF,
+    /// so the loaded documents are left untouched.
+    pub async fn construct_graph(
+        &self,
+        query: &str,
+        languages: &[String],
+    ) -> Result<(GraphDisplayData, GraphFlags, QueryOutcome), WebVowlStoreError> {
+        let query = self.with_document_prefixes(query);
+        let max_solutions = self.limits.max_solutions;
+        let (quads, truncated) = self
+            .with_timeout(async {
+                let QueryResults::Graph(mut triples) = self.session.query(query.as_str()).await?
+                else {
+                    return Err(
+                        "Only CONSTRUCT and DESCRIBE queries can be drawn as a graph"
+                            .to_string()
+                            .into(),
+                    );
+                };
+                let mut quads = Vec::new();
+                while let Some(triple) = triples.next().await {
+                    if quads.len() >= max_solutions {
+                        return Ok((quads, true));
+                    }
+                    let triple = triple?;
+                    quads.push(Quad::new(
+                        triple.subject,
+                        triple.predicate,
+                        triple.object,
+                        GraphName::DefaultGraph,
+                    ));
+                }
+                Ok((quads, false))
+            })
+            .await?;
+
+        let constructed = VOWLRStore::new(Store::default()).with_limits(self.limits);
+        constructed.add_prefixes(GraphName::DefaultGraph, self.prefixes());
+        constructed.add_base_iris(
+            GraphName::DefaultGraph,
+            self.base_iris().into_iter().collect(),
+        );
+        constructed.session.extend(quads).await?;
+        let options = SerializerOptions {
+            mode: VisualizationMode::Rdf,
+            ..SerializerOptions::default()
+        };
+        let (data, flags, mut outcome) = constructed
+            .graph(&QueryAssembler::assemble_rdf_query(languages), options)
+            .await?;
+        outcome.truncated |= truncated;
+        Ok((data, flags, outcome))
+    }
+
+    /// Returns the asserted triples in the default graph.
+    async fn asserted_facts(&self) -> Result<HashSet<Fact>, WebVowlStoreError> {
+        let mut facts = HashSet::new();
+        if let QueryResults::Solutions(mut solutions) = self
+            .session
+            .query("SELECT ?s ?p ?o WHERE { ?s ?p ?o }")
+            .await?
         {
-            ?id owl:Restriction ?label .
-            BIND(owl:Restriction AS ?nodeType)
+            while let Some(solution) = solutions.next().await {
+                let solution = solution?;
+                if let (Some(s), Some(Term::NamedNode(p)), Some(o)) =
+                    (solution.get("s"), solution.get("p"), solution.get("o"))
+                {
+                    facts.insert((s.clone(), p.clone(), o.clone()));
+                }
+            }
         }
-        UNION
+        Ok(facts)
+    }
+
+    /// Checks the asserted triples for unsatisfiable classes, subclass cycles
+    /// and properties with disjoint domains or ranges.
+    ///
+    /// The triples are checked once per generation, later calls return the same diagnostics.
+    pub async fn diagnostics(&self) -> Result<Vec<Diagnostic>, WebVowlStoreError> {
+        let mut checked = self.diagnostics.lock().await;
+        let generation = self.generation();
+        if let Some((checked_generation, diagnostics)) = checked.as_ref()
+            && *checked_generation == generation
         {
-            ?id owl:equivalentClass ?label .
-            BIND(owl:equivalentClass AS ?nodeType)
+            return Ok(diagnostics.clone());
         }
-        # Edges
-        UNION
-        {
-            # 2. Identify subclasses
-            ?id rdfs:subClassOf ?label .
-            BIND(rdfs:subClassOf AS ?nodeType)
-        }
-        UNION {
-            # 1. Find ANY subject/property that points to the start of a list
-            ?subject ?nodeType ?id .
-
-            # 2. Verify ?listHead is actually the start of a list (it must have an rdf:first)
-            ?id rdf:first ?anyFirst .
-
-            # 3. FLATTEN THE LIST
-            # Traverse from the list head to find every member
-            ?id rdf:rest*/rdf:first ?label .
-
-            # 4. (Optional) Safety Filter
-            # Ensure we only expand relevant OWL logic operators, not random RDF lists.
-            FILTER(?property IN (
-                owl:intersectionOf, 
-                owl:unionOf, 
-                owl:oneOf, 
-                owl:disjointUnionOf
-            ))
-        }
-        UNION
-        {
-            # 3. Identify datatypes
-            ?id rdfs:datatype ?label .
-            BIND(owl:datatype AS ?nodeType)
+        let diagnostics = check(&self.asserted_facts().await?);
+        for diagnostic in diagnostics.iter() {
+            warn!("{diagnostic}");
         }
-        UNION
+        *checked = Some((generation, diagnostics.clone()));
+        Ok(diagnostics)
+    }
+
+    /// Validates the asserted triples against the OWL 2 EL, QL, RL and DL profiles.
+    ///
+    /// OWL documents are validated on the OWL model read while loading them.
+    /// Otherwise, e.g. after an update, the asserted triples are validated
+    /// once per generation, later calls return the same violations.
+    pub async fn profile_violations(&self) -> Result<Vec<ProfileViolation>, WebVowlStoreError> {
+        let mut validated = self.profile_violations.lock().await;
+        let generation = self.generation();
+        if let Some((validated_generation, violations)) = validated.as_ref()
+            && *validated_generation == generation
         {
-            # 4. Identify OWL datatype properties
-            ?id owl:DatatypeProperty ?label .
-            BIND(owl:DatatypeProperty AS ?nodeType)
+            return Ok(violations.clone());
         }
-        UNION
-        {
-            # 5. Identify OWL disjoint with
-            ?id owl:disjointWith ?label
-            BIND(owl:disjointWith AS ?nodeType)
+        // The profiles are checked on the OWL model, which is read from RDF/XML.
+        let mut document = Vec::new();
+        let mut results =
+            parse_stream_to(self.asserted_quads().await?, DataType::RDF, self.prefixes()).await?;
+        while let Some(result) = results.next().await {
+            document.extend(result?);
         }
-        UNION {
-            # BRIDGE: Start at the Named Class, jump to the intermediate node
-            ?id ?connector ?intermediateNode .
-            FILTER(isIRI(?id)) 
+        let violations = validate_rdf_xml(&document)?;
+        info!("Found {} profile violations", violations.len());
+        *validated = Some((generation, violations.clone()));
+        Ok(violations)
+    }
 
-            # Match the logic property (unionOf, etc) on the intermediate node
-            ?intermediateNode ?nodeType ?blanknode .
+    /// Adds the profile violations of a document loaded on top of the `previous` generation.
+    ///
+    /// Unless the violations of both are known, the current generation is validated on request.
+    async fn add_profile_violations(
+        &self,
+        previous: u64,
+        violations: Option<Vec<ProfileViolation>>,
+    ) {
+        let mut validated = self.profile_violations.lock().await;
+        let generation = self.generation();
+        match (validated.take(), violations) {
+            (Some((validated_generation, mut known)), Some(violations))
+                // Documents loaded concurrently may have moved on past the next generation.
+                if validated_generation == previous && generation == previous + 1 =>
+            {
+                known.extend(violations);
+                known.sort_by_key(|violation| violation.profile);
+                *validated = Some((generation, known));
+            }
+            (stale, _) => *validated = stale,
+        }
+    }
 
-            # Flatten the list from the blanknode
-            ?blanknode rdf:rest*/rdf:first ?label .
+    /// Materializes the consequences of RDFS and OWL 2 RL rules into the inferred graph,
+    /// replacing any previous consequences.
+    ///
+    /// Only the asserted triples in the default graph are reasoned about.
+    /// Returns the number of inferred triples.
+    pub async fn materialize_inferences(&self) -> Result<usize, WebVowlStoreError> {
+        self.clear_inferences().await?;
+        info!("Inferring consequences...");
+        let start_time = Instant::now();
+        let inferred = infer(&self.asserted_facts().await?);
 
-            # Filter for Logic Types
-            # FILTER(?nodeType IN (owl:intersectionOf, owl:unionOf, owl:oneOf))
-            FILTER(?label != rdf:nil)
+        // The consequences are inserted in batches, so they aren't all copied into quads at once.
+        let graph = NamedNodeRef::new_unchecked(INFERRED_GRAPH).into_owned();
+        let mut quads = inferred
+            .into_iter()
+            .filter_map(|(subject, predicate, object)| {
+                let subject = match subject {
+                    Term::NamedNode(node) => NamedOrBlankNode::from(node),
+                    Term::BlankNode(node) => NamedOrBlankNode::from(node),
+                    _ => return None,
+                };
+                Some(Quad::new(subject, predicate, object, graph.clone()))
+            });
+        let mut count = 0;
+        loop {
+            let batch = quads
+                .by_ref()
+                .take(INFERENCE_BATCH_SIZE)
+                .collect::<Vec<_>>();
+            if batch.is_empty() {
+                break;
+            }
+            count += batch.len();
+            self.session.extend(batch).await?;
         }
-        UNION
-        {
-            # 6. WIP Identify OWL deprecated properties
-            ?id owl:deprecated "true"^^<http://www.w3.org/2001/XMLSchema#boolean>
-            BIND("DeprecatedProperty" AS ?nodeType)
+        info!(
+            "Inferred {count} triples in {} s",
+            Instant::now()
+                .checked_duration_since(start_time)
+                .unwrap_or(Duration::new(0, 0))
+                .as_secs_f32()
+        );
+        Ok(count)
+    }
+
+    /// Removes all inferred triples.
+    pub async fn clear_inferences(&self) -> Result<(), WebVowlStoreError> {
+        self.session
+            .clear_graph(NamedNodeRef::new_unchecked(INFERRED_GRAPH))
+            .await?;
+        Ok(())
+    }
+
+    /// Materializes the inferred triples of the current generation, unless that's already done.
+    ///
+    /// The inferred graph is left in place until documents are loaded, updated or cleared,
+    /// as queries only include it on request, see `Layers::inference` of the queries.
+    pub async fn ensure_inferences(&self) -> Result<(), WebVowlStoreError> {
+        let mut materialized = self.inferred.lock().await;
+        let generation = self.generation();
+        if *materialized == Some(generation) {
+            return Ok(());
+        }
+        self.materialize_inferences().await?;
+        *materialized = Some(generation);
+        Ok(())
+    }
+
+    pub async fn start_upload(&mut self, filename: &str) -> Result<(), WebVowlStoreError> {
+        let extension = Path::new(filename)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("owl");
+        let file = tempfile::Builder::new()
+            .suffix(&format!(".{}", extension))
+            .tempfile()?;
+        self.upload_handle = Some(file);
+        Ok(())
+    }
+
+    pub async fn upload_chunk(&mut self, data: &[u8]) -> Result<(), WebVowlStoreError> {
+        if let Some(file) = &mut self.upload_handle {
+            std::io::Write::write_all(file, data)?;
+            Ok(())
+        } else {
+            warn!("upload_chunk called without start_upload");
+            Ok(())
+        }
+    }
+
+    pub async fn complete_upload(&mut self) -> Result<(), WebVowlStoreError> {
+        if let Some(file) = &mut self.upload_handle {
+            std::io::Write::flush(file)?;
+            let document = prepare_document(file.path(), false)?;
+            self.load_prepared(document).await?;
         }
-        BIND(
-            IF(?nodeType = owl:Class, 1, 2)
-            AS ?weight)
+        self.upload_handle = None;
+        Ok(())
     }
-    ORDER BY ?weight
-    "#;
+}
 
 #[cfg(test)]
 #[allow(unused_must_use)]
@@ -330,7 +1045,12 @@ mod test {
         let mut out = vec![];
         let store = VOWLRStore::default();
         store.insert_file(Path::new(&resource), false).await?;
-        let mut results = parse_stream_to(store.session.stream().await?, DataType::OWL).await?;
+        let mut results = parse_stream_to(
+            store.session.stream().await?,
+            DataType::OWL,
+            store.prefixes(),
+        )
+        .await?;
         while let Some(result) = results.next().await {
             out.extend(result?);
         }
@@ -344,7 +1064,12 @@ mod test {
         let mut out = vec![];
         let store = VOWLRStore::default();
         store.insert_file(Path::new(&resource), false).await?;
-        let mut results = parse_stream_to(store.session.stream().await?, DataType::OWL).await?;
+        let mut results = parse_stream_to(
+            store.session.stream().await?,
+            DataType::OWL,
+            store.prefixes(),
+        )
+        .await?;
         while let Some(result) = results.next().await {
             out.extend(result?);
         }
@@ -358,7 +1083,12 @@ mod test {
         let mut out = vec![];
         let store = VOWLRStore::default();
         store.insert_file(Path::new(&resource), false).await?;
-        let mut results = parse_stream_to(store.session.stream().await?, DataType::OWL).await?;
+        let mut results = parse_stream_to(
+            store.session.stream().await?,
+            DataType::OWL,
+            store.prefixes(),
+        )
+        .await?;
         while let Some(result) = results.next().await {
             out.extend(result?);
         }
@@ -372,7 +1102,12 @@ mod test {
         let mut out = vec![];
         let store = VOWLRStore::default();
         store.insert_file(Path::new(&resource), false).await?;
-        let mut results = parse_stream_to(store.session.stream().await?, DataType::OWL).await?;
+        let mut results = parse_stream_to(
+            store.session.stream().await?,
+            DataType::OWL,
+            store.prefixes(),
+        )
+        .await?;
         while let Some(result) = results.next().await {
             out.extend(result?);
         }
@@ -381,4 +1116,15 @@ mod test {
         store.session.clear().await?;
         Ok(())
     }
+    #[test_resources("crates/database/data/owl-ttl/*.ttl")]
+    async fn test_ttl_document_prefixes(resource: &str) -> Result<(), WebVowlStoreError> {
+        let store = VOWLRStore::new(Store::default());
+        store.insert_file(Path::new(&resource), false).await?;
+        assert!(
+            store.prefixes().contains_key("owl"),
+            "Expected the owl prefix for: {}",
+            resource
+        );
+        Ok(())
+    }
 }
@@ -1,4 +1,4 @@
-//! Provides ready to use [`NamedNodeRef`](oxrdf::NamedNodeRef)s for OWL vocabularies.
+//! Provides ready to use [`NamedNodeRef`](oxrdf::NamedNodeRef)s for OWL, XML Schema and SKOS vocabularies.
 
 #![allow(unused)]
 pub mod owl {
@@ -11,9 +11,9 @@ pub mod owl {
     /// The class of collections of pairwise disjoint classes.
     pub const ALL_DISJOINT_CLASSES: NamedNodeRef<'_> =
         NamedNodeRef::new_unchecked("http://www.w3.org/2002/07/owl#AllDisjointClasses");
-    /// The class of collections of pairwise disjoint classes.
+    /// The class of collections of pairwise disjoint properties.
     pub const ALL_DISJOINT_PROPERTIES: NamedNodeRef<'_> =
-        NamedNodeRef::new_unchecked("http://www.w3.org/2002/07/owl#AllDisjointClasses");
+        NamedNodeRef::new_unchecked("http://www.w3.org/2002/07/owl#AllDisjointProperties");
     /// The class of annotated annotations for which the RDF serialization consists of an annotated subject, predicate and object.
     pub const ANNOTATION: NamedNodeRef<'_> =
         NamedNodeRef::new_unchecked("http://www.w3.org/2002/07/owl#Annotation");
@@ -232,8 +232,70 @@ pub mod owl {
         NamedNodeRef::new_unchecked("http://www.w3.org/2002/07/owl#versionInfo");
     ///The property that identifies the version IRI of an ontology.
     pub const VERSION_IRI: NamedNodeRef<'_> =
-        NamedNodeRef::new_unchecked("http://www.w3.org/2002/07/owlversionIRI#");
+        NamedNodeRef::new_unchecked("http://www.w3.org/2002/07/owl#versionIRI");
     ///The property that determines the collection of facet-value pairs that define a datatype restriction.
     pub const WITH_RESTRICTIONS: NamedNodeRef<'_> =
         NamedNodeRef::new_unchecked("http://www.w3.org/2002/07/owl#withRestrictions");
 }
+
+pub mod xsd {
+    //! [XML Schema](https://www.w3.org/TR/xmlschema11-2/) constraining facets used by OWL2.
+    //!
+    //! See [OWL2 datatype maps](https://www.w3.org/TR/owl2-syntax/#Datatype_Maps).
+    use oxrdf::NamedNodeRef;
+
+    /// The number of characters of a string, or octets of binary data.
+    pub const LENGTH: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2001/XMLSchema#length");
+    /// The minimum number of characters of a string, or octets of binary data.
+    pub const MIN_LENGTH: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2001/XMLSchema#minLength");
+    /// The maximum number of characters of a string, or octets of binary data.
+    pub const MAX_LENGTH: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2001/XMLSchema#maxLength");
+    /// A regular expression the lexical form of a value must match.
+    pub const PATTERN: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2001/XMLSchema#pattern");
+    /// The inclusive lower bound of a value.
+    pub const MIN_INCLUSIVE: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2001/XMLSchema#minInclusive");
+    /// The exclusive lower bound of a value.
+    pub const MIN_EXCLUSIVE: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2001/XMLSchema#minExclusive");
+    /// The inclusive upper bound of a value.
+    pub const MAX_INCLUSIVE: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2001/XMLSchema#maxInclusive");
+    /// The exclusive upper bound of a value.
+    pub const MAX_EXCLUSIVE: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2001/XMLSchema#maxExclusive");
+    /// The maximum number of digits of a decimal value.
+    pub const TOTAL_DIGITS: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2001/XMLSchema#totalDigits");
+    /// The maximum number of fraction digits of a decimal value.
+    pub const FRACTION_DIGITS: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2001/XMLSchema#fractionDigits");
+}
+
+pub mod skos {
+    //! [SKOS](https://www.w3.org/TR/skos-reference/) vocabulary.
+    use oxrdf::NamedNodeRef;
+
+    /// The class of units of thought.
+    pub const CONCEPT: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2004/02/skos/core#Concept");
+    /// The class of aggregations of one or more concepts.
+    pub const CONCEPT_SCHEME: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2004/02/skos/core#ConceptScheme");
+    /// The property that relates a concept to a more general concept.
+    pub const BROADER: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2004/02/skos/core#broader");
+    /// The property that relates a concept to a more specific concept.
+    pub const NARROWER: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2004/02/skos/core#narrower");
+    /// The property that relates a concept to a concept scheme it belongs to.
+    pub const IN_SCHEME: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2004/02/skos/core#inScheme");
+    /// The preferred lexical label of a resource.
+    pub const PREF_LABEL: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/2004/02/skos/core#prefLabel");
+}
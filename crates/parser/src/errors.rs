@@ -1,6 +1,7 @@
 use std::{
     io::{Error, ErrorKind},
     panic::Location,
+    time::Duration,
 };
 
 use horned_owl::error::HornedError;
@@ -21,6 +22,10 @@ pub enum WebVowlStoreErrorKind {
     QueryEvaluationError(QueryEvaluationError),
     JoinError(JoinError),
     StorageError(StorageError),
+    /// A query was cancelled after running for the given time.
+    Timeout(Duration),
+    /// A query was cancelled, as the same client requested a newer graph.
+    Cancelled,
 }
 
 #[derive(Debug)]
@@ -29,6 +34,13 @@ pub struct WebVowlStoreError {
     location: &'static Location<'static>,
 }
 
+impl WebVowlStoreError {
+    /// The kind of the error.
+    pub fn kind(&self) -> &WebVowlStoreErrorKind {
+        &self.inner
+    }
+}
+
 impl Into<Error> for WebVowlStoreError {
     fn into(self) -> Error {
         Error::new(ErrorKind::Other, self.to_string())
@@ -130,7 +142,9 @@ impl std::fmt::Display for WebVowlStoreError {
 impl std::error::Error for WebVowlStoreError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match &self.inner {
-            WebVowlStoreErrorKind::InvalidInput(_) => None,
+            WebVowlStoreErrorKind::InvalidInput(_)
+            | WebVowlStoreErrorKind::Timeout(_)
+            | WebVowlStoreErrorKind::Cancelled => None,
             WebVowlStoreErrorKind::HornedError(e) => Some(e),
             WebVowlStoreErrorKind::IOError(e) => Some(e),
             WebVowlStoreErrorKind::IriParseError(e) => Some(e),
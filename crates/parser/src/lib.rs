@@ -1,2 +1,3 @@
 pub mod errors;
 pub mod parser_util;
+pub mod profiles;
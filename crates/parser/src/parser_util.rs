@@ -1,4 +1,7 @@
-use crate::errors::{WebVowlStoreError, WebVowlStoreErrorKind};
+use crate::{
+    errors::{WebVowlStoreError, WebVowlStoreErrorKind},
+    profiles::validate_ontology,
+};
 use futures::{StreamExt, stream::BoxStream};
 use horned_owl::{
     io::{rdf::reader::ConcreteRDFOntology, *},
@@ -9,10 +12,11 @@ use log::info;
 use rdf_fusion::{
     execution::results::QuadStream,
     io::{JsonLdProfileSet, RdfFormat, RdfParser, RdfSerializer},
-    model::GraphName,
+    model::{GraphName, Quad},
 };
 use std::io;
 use std::{
+    collections::BTreeMap,
     fs::File,
     io::{BufReader, Cursor, Write},
     path::Path,
@@ -20,31 +24,56 @@ use std::{
 };
 use tokio::sync::mpsc::{self, UnboundedSender};
 use tokio_stream::wrappers::UnboundedReceiverStream;
-use vowlr_util::datatypes::DataType;
-
-pub enum ParserInput {
-    File(Vec<u8>),
-    Buffer(Cursor<Vec<u8>>),
+use vowlr_util::{datatypes::DataType, diagnostics::ProfileViolation};
+
+pub struct PreparedDocument {
+    /// The quads of the document, in the default graph.
+    pub quads: Vec<Quad>,
+    /// The prefix declarations of the document.
+    ///
+    /// - Key = The prefix name, e.g. `owl`.
+    /// - Value = The namespace IRI, e.g. `http://www.w3.org/2002/07/owl#`.
+    pub prefixes: BTreeMap<String, String>,
+    /// The base IRIs declared by the document, in order of declaration.
+    ///
+    /// For instance, `xml:base` in RDF/XML or `@base` in Turtle.
+    pub base_iris: Vec<String>,
+    /// The profile violations of the document, validated on the OWL model read while preparing.
+    ///
+    /// Only OWL documents are read into an OWL model, others leave this `None`.
+    pub violations: Option<Vec<ProfileViolation>>,
 }
 
-impl ParserInput {
-    pub fn from_path(path: &Path) -> Result<Self, WebVowlStoreError> {
-        std::fs::read(path)
-            .map(ParserInput::File)
-            .map_err(WebVowlStoreError::from)
-    }
-
-    pub fn as_slice(&self) -> &[u8] {
-        match self {
-            ParserInput::Buffer(cursor) => cursor.get_ref().as_slice(),
-            ParserInput::File(bytes) => bytes.as_slice(),
+/// Parses `input` using `parser`, collecting its quads along with the prefix
+/// and base IRI declarations read while parsing.
+///
+/// Only Turtle, TriG, N3 and RDF/XML documents declare prefixes and base IRIs.
+pub fn parse_document(
+    parser: RdfParser,
+    input: &[u8],
+) -> Result<PreparedDocument, WebVowlStoreError> {
+    let mut parser = parser.for_slice(input);
+    let mut quads = Vec::new();
+    let mut base_iris: Vec<String> = Vec::new();
+    // The base IRI may change during parsing, so it is recorded after every quad.
+    while let Some(quad) = parser.next() {
+        quads.push(quad.map_err(|e| e.to_string())?);
+        if let Some(base) = parser.base_iri()
+            && !base_iris.iter().any(|known| known == base)
+        {
+            base_iris.push(base.to_string());
         }
     }
-}
-
-pub struct PreparedParser {
-    pub parser: RdfParser,
-    pub input: ParserInput,
+    let prefixes = parser
+        .prefixes()
+        .map(|(name, iri)| (name.to_string(), iri.to_string()))
+        .collect();
+    Ok(PreparedDocument {
+        quads,
+        prefixes,
+        base_iris,
+        violations: None,
+    })
 }
 
 pub fn path_type(path: &Path) -> Option<DataType> {
@@ -77,22 +106,40 @@ pub fn format_from_resource_type(resource_type: &DataType) -> Option<RdfFormat>
         _ => None,
     }
 }
+/// Creates a serializer for `format` which abbreviates IRIs using `prefixes`.
+fn serializer_with_prefixes(
+    format: RdfFormat,
+    prefixes: &BTreeMap<String, String>,
+) -> Result<RdfSerializer, WebVowlStoreError> {
+    let mut serializer = RdfSerializer::from_format(format);
+    for (name, iri) in prefixes {
+        serializer = serializer.with_prefix(name, iri)?;
+    }
+    Ok(serializer)
+}
+
+/// Serializes `stream` into `output_type`.
+///
+/// `prefixes` are declared in the output where the format supports it.
 pub async fn parse_stream_to(
     mut stream: QuadStream,
     output_type: DataType,
+    prefixes: BTreeMap<String, String>,
 ) -> Result<BoxStream<'static, Result<Vec<u8>, WebVowlStoreError>>, WebVowlStoreError> {
     match output_type {
         DataType::OFN | DataType::OWX | DataType::OWL => {
             let (tx, rx) = mpsc::unbounded_channel();
             let mut buf = Vec::new();
-            let mut serializer =
-                RdfSerializer::from_format(format_from_resource_type(&DataType::OWL).ok_or(
+            let mut serializer = serializer_with_prefixes(
+                format_from_resource_type(&DataType::OWL).ok_or(
                     WebVowlStoreErrorKind::InvalidInput(format!(
                         "Unsupported output type: {:?}",
                         output_type
                     )),
-                )?)
-                .for_writer(&mut buf);
+                )?,
+                &prefixes,
+            )?
+            .for_writer(&mut buf);
             while let Some(quad) = stream.next().await {
                 serializer.serialize_quad(&quad?)?;
             }
@@ -103,15 +150,23 @@ pub async fn parse_stream_to(
                 let mut writer = ChannelWriter { sender: tx.clone() };
                 let result = (|| match output_type {
                     DataType::OFN => {
-                        let (ont, prefix): (RcComponentMappedOntology, _) =
+                        let (ont, mut prefix): (RcComponentMappedOntology, _) =
                             ofn::reader::read(&mut reader, ParserConfiguration::default())?;
+                        for (name, iri) in prefixes.iter() {
+                            // Invalid prefix names are left out of the output.
+                            let _ = prefix.add_prefix(name, iri);
+                        }
                         ofn::writer::write(&mut writer, &ont, Some(&prefix))?;
                         writer.flush()?;
                         Ok(writer)
                     }
                     DataType::OWX => {
-                        let (ont, prefix): (RcComponentMappedOntology, _) =
+                        let (ont, mut prefix): (RcComponentMappedOntology, _) =
                             owx::reader::read(&mut reader, ParserConfiguration::default())?;
+                        for (name, iri) in prefixes.iter() {
+                            // Invalid prefix names are left out of the output.
+                            let _ = prefix.add_prefix(name, iri);
+                        }
                         owx::writer::write(&mut writer, &ont, Some(&prefix))?;
                         writer.flush()?;
                         Ok(writer)
@@ -144,14 +199,16 @@ pub async fn parse_stream_to(
             tokio::task::spawn(async move {
                 let mut writer = ChannelWriter { sender: tx.clone() };
                 let result = (|| async {
-                    let mut serializer =
-                        RdfSerializer::from_format(format_from_resource_type(&output_type).ok_or(
+                    let mut serializer = serializer_with_prefixes(
+                        format_from_resource_type(&output_type).ok_or(
                             WebVowlStoreErrorKind::InvalidInput(format!(
                                 "Unsupported output type: {:?}",
                                 output_type
                             )),
-                        )?)
-                        .for_writer(&mut writer);
+                        )?,
+                        &prefixes,
+                    )?
+                    .for_writer(&mut writer);
                     while let Some(quad) = stream.next().await {
                         serializer.serialize_quad(&quad?)?;
                     }
@@ -170,7 +227,10 @@ pub async fn parse_stream_to(
     }
 }
 
-pub fn parser_from_format(path: &Path, lenient: bool) -> Result<PreparedParser, WebVowlStoreError> {
+/// Parses the document at `path`, telling its type by the file extension.
+///
+/// OWL documents which aren't RDF are converted to RDF/XML first.
+pub fn prepare_document(path: &Path, lenient: bool) -> Result<PreparedDocument, WebVowlStoreError> {
     let make_parser = |fmt| {
         let path_str = path.to_str().unwrap();
         // TODO: Handle non default graph
@@ -187,8 +247,12 @@ pub fn parser_from_format(path: &Path, lenient: bool) -> Result<PreparedParser,
             info!("Parsing OFN input...");
             let start_time = Instant::now();
 
-            let (ont, _): (RcComponentMappedOntology, _) =
+            let (ont, mapping): (RcComponentMappedOntology, _) =
                 ofn::reader::read(&mut reader, ParserConfiguration::default())?;
+            let prefixes = mapping
+                .mappings()
+                .map(|(name, iri)| (name.to_string(), iri.to_string()))
+                .collect();
 
             info!(
                 "Parsing completed in {} s",
@@ -201,6 +265,7 @@ pub fn parser_from_format(path: &Path, lenient: bool) -> Result<PreparedParser,
             info!("Writing to RDF...");
             let start_time = Instant::now();
 
+            let violations = validate_ontology(&ont);
             let mut buf = Vec::new();
             rdf::writer::write(&mut buf, &ont)?;
 
@@ -212,9 +277,11 @@ pub fn parser_from_format(path: &Path, lenient: bool) -> Result<PreparedParser,
                     .as_secs_f32()
             );
 
-            Ok(PreparedParser {
-                parser: make_parser(RdfFormat::RdfXml),
-                input: ParserInput::Buffer(Cursor::new(buf)),
+            Ok(PreparedDocument {
+                prefixes,
+                base_iris: Vec::new(),
+                violations: Some(violations),
+                ..parse_document(make_parser(RdfFormat::RdfXml), &buf)?
             })
         }
         Some(DataType::OWX) => {
@@ -241,8 +308,15 @@ pub fn parser_from_format(path: &Path, lenient: bool) -> Result<PreparedParser,
             info!("Writing to RDF...");
             let start_time = Instant::now();
 
+            let prefixes = ontology
+                .1
+                .mappings()
+                .map(|(name, iri)| (name.to_string(), iri.to_string()))
+                .collect();
+            let ontology: RcComponentMappedOntology = ontology.0.into();
+            let violations = validate_ontology(&ontology);
             let mut buf = Vec::new();
-            rdf::writer::write(&mut buf, &ontology.0.into())?;
+            rdf::writer::write(&mut buf, &ontology)?;
 
             info!(
                 "Writing completed in {} s",
@@ -251,9 +325,11 @@ pub fn parser_from_format(path: &Path, lenient: bool) -> Result<PreparedParser,
                     .unwrap_or(Duration::new(0, 0))
                     .as_secs_f32()
             );
-            Ok(PreparedParser {
-                parser: make_parser(RdfFormat::RdfXml),
-                input: ParserInput::Buffer(Cursor::new(buf)),
+            Ok(PreparedDocument {
+                prefixes,
+                base_iris: Vec::new(),
+                violations: Some(violations),
+                ..parse_document(make_parser(RdfFormat::RdfXml), &buf)?
             })
         }
         Some(DataType::OWL) => {
@@ -279,8 +355,10 @@ pub fn parser_from_format(path: &Path, lenient: bool) -> Result<PreparedParser,
             info!("Writing to RDF...");
             let start_time = Instant::now();
 
+            let ontology: RcComponentMappedOntology = ontology.into();
+            let violations = validate_ontology(&ontology);
             let mut buf = Vec::new();
-            rdf::writer::write(&mut buf, &ontology.into())?;
+            rdf::writer::write(&mut buf, &ontology)?;
 
             info!(
                 "Writing completed in {} s",
@@ -290,61 +368,43 @@ pub fn parser_from_format(path: &Path, lenient: bool) -> Result<PreparedParser,
                     .as_secs_f32()
             );
 
-            Ok(PreparedParser {
-                parser: make_parser(RdfFormat::RdfXml),
-                input: ParserInput::Buffer(Cursor::new(buf)),
+            // horned-owl drops the namespace and base declarations of the original
+            // RDF/XML document, so they are read from the original document.
+            let original = parse_document(
+                RdfParser::from_format(RdfFormat::RdfXml).lenient(),
+                &std::fs::read(path)?,
+            )?;
+            Ok(PreparedDocument {
+                prefixes: original.prefixes,
+                base_iris: original.base_iris,
+                violations: Some(violations),
+                ..parse_document(make_parser(RdfFormat::RdfXml), &buf)?
             })
         }
         Some(DataType::TTL) => {
-            let input = ParserInput::from_path(path)?;
-            Ok(PreparedParser {
-                parser: make_parser(RdfFormat::Turtle),
-                input,
-            })
+            parse_document(make_parser(RdfFormat::Turtle), &std::fs::read(path)?)
         }
         Some(DataType::NTriples) => {
-            let input = ParserInput::from_path(path)?;
-            Ok(PreparedParser {
-                parser: make_parser(RdfFormat::NTriples),
-                input,
-            })
+            parse_document(make_parser(RdfFormat::NTriples), &std::fs::read(path)?)
         }
         Some(DataType::NQuads) => {
-            let input = ParserInput::from_path(path)?;
-            Ok(PreparedParser {
-                parser: make_parser(RdfFormat::NQuads),
-                input,
-            })
-        }
-        Some(DataType::TriG) => {
-            let input = ParserInput::from_path(path)?;
-            Ok(PreparedParser {
-                parser: make_parser(RdfFormat::TriG),
-                input,
-            })
-        }
-        Some(DataType::JsonLd) => {
-            let input = ParserInput::from_path(path)?;
-            Ok(PreparedParser {
-                parser: make_parser(RdfFormat::JsonLd {
-                    profile: JsonLdProfileSet::default(),
-                }),
-                input,
-            })
-        }
-        Some(DataType::N3) => {
-            let input = ParserInput::from_path(path)?;
-            Ok(PreparedParser {
-                parser: make_parser(RdfFormat::N3),
-                input,
-            })
+            parse_document(make_parser(RdfFormat::NQuads), &std::fs::read(path)?)
         }
+        Some(DataType::TriG) => parse_document(make_parser(RdfFormat::TriG), &std::fs::read(path)?),
+        Some(DataType::JsonLd) => parse_document(
+            make_parser(RdfFormat::JsonLd {
+                profile: JsonLdProfileSet::default(),
+            }),
+            &std::fs::read(path)?,
+        ),
+        Some(DataType::N3) => parse_document(make_parser(RdfFormat::N3), &std::fs::read(path)?),
         _ => Err(WebVowlStoreErrorKind::InvalidInput(format!(
             "Unsupported parser: {}",
             path.display()
-        ))),
+        ))
+        .into()),
     };
-    Ok(prepared?)
+    prepared
 }
 struct ChannelWriter {
     sender: UnboundedSender<Result<Vec<u8>, io::Error>>,
@@ -376,10 +436,8 @@ mod test {
         use rdf_fusion::store::Store;
         let session = Store::default();
         for resource in resources {
-            let parser = parser_from_format(Path::new(&resource), false).unwrap();
-            let _ = session
-                .load_from_reader(parser.parser, parser.input.as_slice())
-                .await;
+            let document = prepare_document(Path::new(&resource), false).unwrap();
+            let _ = session.extend(document.quads).await;
             assert_ne!(
                 session.len().await.unwrap(),
                 0,
@@ -395,10 +453,8 @@ mod test {
         let session = Store::default();
         let resources = resources_with_suffix("data/owl-rdf", "owl");
         for resource in resources {
-            let parser = parser_from_format(Path::new(&resource), false).unwrap();
-            let _ = session
-                .load_from_reader(parser.parser, parser.input.as_slice())
-                .await;
+            let document = prepare_document(Path::new(&resource), false).unwrap();
+            let _ = session.extend(document.quads).await;
             assert_ne!(
                 session.len().await.unwrap(),
                 0,
@@ -413,10 +469,8 @@ mod test {
         let session = Store::default();
         let resources = resources_with_suffix("data/owl-ttl", "ttl");
         for resource in resources {
-            let parser = parser_from_format(Path::new(&resource), false).unwrap();
-            let _ = session
-                .load_from_reader(parser.parser, parser.input.as_slice())
-                .await;
+            let document = prepare_document(Path::new(&resource), false).unwrap();
+            let _ = session.extend(document.quads).await;
             assert_ne!(
                 session.len().await.unwrap(),
                 0,
@@ -0,0 +1,623 @@
+//! Validates ontologies against the OWL 2 profiles.
+//!
+//! The syntactic restrictions of [OWL 2 EL, QL and RL](https://www.w3.org/TR/owl2-profiles/)
+//! are checked per axiom, as are the global restrictions of OWL 2 DL on non-simple properties
+//! and property chains. Restrictions on datatypes aren't checked.
+
+use crate::errors::WebVowlStoreError;
+use horned_owl::{
+    io::{ParserConfiguration, rdf::reader::ConcreteRDFOntology},
+    model::{
+        ClassExpression, Component, DisjointClasses, DisjointObjectProperties, DisjointUnion,
+        EquivalentClasses, EquivalentObjectProperties, ForIRI, FunctionalObjectProperty, HasKey,
+        Individual, InverseFunctionalObjectProperty, InverseObjectProperties,
+        IrreflexiveObjectProperty, ObjectPropertyDomain, ObjectPropertyExpression,
+        ObjectPropertyRange, RcAnnotatedComponent, RcStr, SubClassOf, SubObjectPropertyExpression,
+        SubObjectPropertyOf, TransitiveObjectProperty,
+    },
+    ontology::component_mapped::RcComponentMappedOntology,
+};
+use std::{collections::HashSet, io::Cursor};
+use vowlr_util::diagnostics::{Profile, ProfileViolation};
+
+use ClassExpression as CE;
+use ObjectPropertyExpression as OPE;
+
+const THING: &str = "http://www.w3.org/2002/07/owl#Thing";
+
+/// Where a class expression occurs in an axiom.
+///
+/// The QL and RL profiles allow different class expressions in each position.
+#[derive(Clone, Copy)]
+enum Position {
+    Subclass,
+    Superclass,
+    Equivalent,
+    Assertion,
+}
+
+impl Position {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Subclass => "subclass",
+            Self::Superclass => "superclass",
+            Self::Equivalent => "equivalent class",
+            Self::Assertion => "class of an individual",
+        }
+    }
+}
+
+fn is_thing<A: ForIRI>(ce: &CE<A>) -> bool {
+    matches!(ce, CE::Class(class) if class.0.to_string() == THING)
+}
+
+fn render_property<A: ForIRI>(ope: &OPE<A>) -> String {
+    match ope {
+        OPE::ObjectProperty(property) => format!("<{}>", property.0),
+        OPE::InverseObjectProperty(property) => format!("ObjectInverseOf(<{}>)", property.0),
+    }
+}
+
+fn render_individual<A: ForIRI>(individual: &Individual<A>) -> String {
+    match individual {
+        Individual::Named(individual) => format!("<{}>", individual.0),
+        Individual::Anonymous(_) => "[]".to_string(),
+    }
+}
+
+/// Renders `ce` in functional syntax, leaving out data ranges and literals.
+fn render<A: ForIRI>(ce: &CE<A>) -> String {
+    let list = |ces: &[CE<A>]| ces.iter().map(render).collect::<Vec<_>>().join(" ");
+    match ce {
+        CE::Class(class) => format!("<{}>", class.0),
+        CE::ObjectIntersectionOf(ces) => format!("ObjectIntersectionOf({})", list(ces)),
+        CE::ObjectUnionOf(ces) => format!("ObjectUnionOf({})", list(ces)),
+        CE::ObjectComplementOf(ce) => format!("ObjectComplementOf({})", render(ce)),
+        CE::ObjectOneOf(individuals) => format!(
+            "ObjectOneOf({})",
+            individuals
+                .iter()
+                .map(render_individual)
+                .collect::<Vec<_>>()
+                .join(" ")
+        ),
+        CE::ObjectSomeValuesFrom { ope, bce } => format!(
+            "ObjectSomeValuesFrom({} {})",
+            render_property(ope),
+            render(bce)
+        ),
+        CE::ObjectAllValuesFrom { ope, bce } => format!(
+            "ObjectAllValuesFrom({} {})",
+            render_property(ope),
+            render(bce)
+        ),
+        CE::ObjectHasValue { ope, i } => format!(
+            "ObjectHasValue({} {})",
+            render_property(ope),
+            render_individual(i)
+        ),
+        CE::ObjectHasSelf(ope) => format!("ObjectHasSelf({})", render_property(ope)),
+        CE::ObjectMinCardinality { n, ope, bce } => format!(
+            "ObjectMinCardinality({n} {} {})",
+            render_property(ope),
+            render(bce)
+        ),
+        CE::ObjectMaxCardinality { n, ope, bce } => format!(
+            "ObjectMaxCardinality({n} {} {})",
+            render_property(ope),
+            render(bce)
+        ),
+        CE::ObjectExactCardinality { n, ope, bce } => format!(
+            "ObjectExactCardinality({n} {} {})",
+            render_property(ope),
+            render(bce)
+        ),
+        CE::DataSomeValuesFrom { dp, .. } => format!("DataSomeValuesFrom(<{}> ...)", dp.0),
+        CE::DataAllValuesFrom { dp, .. } => format!("DataAllValuesFrom(<{}> ...)", dp.0),
+        CE::DataHasValue { dp, .. } => format!("DataHasValue(<{}> ...)", dp.0),
+        CE::DataMinCardinality { n, dp, .. } => format!("DataMinCardinality({n} <{}> ...)", dp.0),
+        CE::DataMaxCardinality { n, dp, .. } => format!("DataMaxCardinality({n} <{}> ...)", dp.0),
+        CE::DataExactCardinality { n, dp, .. } => {
+            format!("DataExactCardinality({n} <{}> ...)", dp.0)
+        }
+    }
+}
+
+/// Returns the first part of `ce` which isn't an OWL 2 EL class expression.
+fn el<A: ForIRI>(ce: &CE<A>) -> Option<&CE<A>> {
+    match ce {
+        CE::Class(_)
+        | CE::ObjectHasValue {
+            ope: OPE::ObjectProperty(_),
+            ..
+        }
+        | CE::ObjectHasSelf(OPE::ObjectProperty(_))
+        | CE::DataSomeValuesFrom { .. }
+        | CE::DataHasValue { .. } => None,
+        CE::ObjectOneOf(individuals) if individuals.len() == 1 => None,
+        CE::ObjectIntersectionOf(ces) => ces.iter().find_map(el),
+        CE::ObjectSomeValuesFrom {
+            ope: OPE::ObjectProperty(_),
+            bce,
+        } => el(bce),
+        _ => Some(ce),
+    }
+}
+
+/// Returns the first part of `ce` which isn't an OWL 2 QL subclass expression.
+fn ql_subclass<A: ForIRI>(ce: &CE<A>) -> Option<&CE<A>> {
+    match ce {
+        CE::Class(_) | CE::DataSomeValuesFrom { .. } => None,
+        CE::ObjectSomeValuesFrom { bce, .. } if is_thing(bce) => None,
+        _ => Some(ce),
+    }
+}
+
+/// Returns the first part of `ce` which isn't an OWL 2 QL superclass expression.
+fn ql_superclass<A: ForIRI>(ce: &CE<A>) -> Option<&CE<A>> {
+    match ce {
+        CE::Class(_) | CE::DataSomeValuesFrom { .. } => None,
+        CE::ObjectSomeValuesFrom { bce, .. } if matches!(**bce, CE::Class(_)) => None,
+        CE::ObjectIntersectionOf(ces) => ces.iter().find_map(ql_superclass),
+        CE::ObjectComplementOf(ce) => ql_subclass(ce),
+        _ => Some(ce),
+    }
+}
+
+/// Returns the first part of `ce` which isn't an OWL 2 RL subclass expression.
+fn rl_subclass<A: ForIRI>(ce: &CE<A>) -> Option<&CE<A>> {
+    match ce {
+        CE::Class(_) if !is_thing(ce) => None,
+        CE::ObjectOneOf(_)
+        | CE::ObjectHasValue { .. }
+        | CE::DataSomeValuesFrom { .. }
+        | CE::DataHasValue { .. } => None,
+        CE::ObjectIntersectionOf(ces) | CE::ObjectUnionOf(ces) => ces.iter().find_map(rl_subclass),
+        CE::ObjectSomeValuesFrom { bce, .. } if is_thing(bce) => None,
+        CE::ObjectSomeValuesFrom { bce, .. } => rl_subclass(bce),
+        _ => Some(ce),
+    }
+}
+
+/// Returns the first part of `ce` which isn't an OWL 2 RL superclass expression.
+fn rl_superclass<A: ForIRI>(ce: &CE<A>) -> Option<&CE<A>> {
+    match ce {
+        CE::Class(_) if !is_thing(ce) => None,
+        CE::ObjectHasValue { .. } | CE::DataAllValuesFrom { .. } | CE::DataHasValue { .. } => None,
+        CE::DataMaxCardinality { n: 0 | 1, .. } => None,
+        CE::ObjectIntersectionOf(ces) => ces.iter().find_map(rl_superclass),
+        CE::ObjectComplementOf(ce) => rl_subclass(ce),
+        CE::ObjectAllValuesFrom { bce, .. } => rl_superclass(bce),
+        CE::ObjectMaxCardinality { n: 0 | 1, bce, .. } if is_thing(bce) => None,
+        CE::ObjectMaxCardinality { n: 0 | 1, bce, .. } => rl_subclass(bce),
+        _ => Some(ce),
+    }
+}
+
+/// Returns the first part of `ce` which isn't an OWL 2 RL equivalent class expression.
+fn rl_equivalent<A: ForIRI>(ce: &CE<A>) -> Option<&CE<A>> {
+    match ce {
+        CE::Class(_) if !is_thing(ce) => None,
+        CE::ObjectHasValue { .. } | CE::DataHasValue { .. } => None,
+        CE::ObjectIntersectionOf(ces) => ces.iter().find_map(rl_equivalent),
+        _ => Some(ce),
+    }
+}
+
+/// The IRI of the object property in `ope`, inverted or not.
+fn property_iri<A: ForIRI>(ope: &OPE<A>) -> String {
+    match ope {
+        OPE::ObjectProperty(property) | OPE::InverseObjectProperty(property) => {
+            property.0.to_string()
+        }
+    }
+}
+
+/// Returns the object properties which aren't simple in OWL 2 DL.
+///
+/// Transitive properties and super properties of property chains aren't simple,
+/// and neither are their super properties, equivalent properties and inverses.
+fn non_simple_properties<A: ForIRI>(components: &[&Component<A>]) -> HashSet<String> {
+    let mut non_simple = HashSet::new();
+    let mut implications = Vec::new();
+    for component in components {
+        match component {
+            Component::TransitiveObjectProperty(TransitiveObjectProperty(ope)) => {
+                non_simple.insert(property_iri(ope));
+            }
+            Component::SubObjectPropertyOf(SubObjectPropertyOf {
+                sub: SubObjectPropertyExpression::ObjectPropertyChain(_),
+                sup,
+            }) => {
+                non_simple.insert(property_iri(sup));
+            }
+            Component::SubObjectPropertyOf(SubObjectPropertyOf {
+                sub: SubObjectPropertyExpression::ObjectPropertyExpression(sub),
+                sup,
+            }) => implications.push((property_iri(sub), property_iri(sup))),
+            Component::EquivalentObjectProperties(EquivalentObjectProperties(opes)) => {
+                for (a, b) in opes.iter().zip(opes.iter().skip(1)) {
+                    implications.push((property_iri(a), property_iri(b)));
+                    implications.push((property_iri(b), property_iri(a)));
+                }
+            }
+            Component::InverseObjectProperties(InverseObjectProperties(a, b)) => {
+                implications.push((a.0.to_string(), b.0.to_string()));
+                implications.push((b.0.to_string(), a.0.to_string()));
+            }
+            _ => {}
+        }
+    }
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (sub, sup) in implications.iter() {
+            if non_simple.contains(sub) {
+                changed |= non_simple.insert(sup.clone());
+            }
+        }
+    }
+    non_simple
+}
+
+struct Validator {
+    non_simple: HashSet<String>,
+    violations: Vec<ProfileViolation>,
+}
+
+impl Validator {
+    fn report(&mut self, profile: Profile, axiom: &str, reason: impl Into<String>) {
+        self.violations.push(ProfileViolation {
+            profile,
+            axiom: axiom.to_string(),
+            reason: reason.into(),
+        });
+    }
+
+    fn is_non_simple(&self, ope: &OPE<impl ForIRI>) -> bool {
+        self.non_simple.contains(&property_iri(ope))
+    }
+
+    /// Returns the first cardinality or self restriction in `ce` on a non-simple property.
+    fn dl<'c, A: ForIRI>(&self, ce: &'c CE<A>) -> Option<&'c CE<A>> {
+        match ce {
+            CE::ObjectMinCardinality { ope, bce, .. }
+            | CE::ObjectMaxCardinality { ope, bce, .. }
+            | CE::ObjectExactCardinality { ope, bce, .. } => {
+                if self.is_non_simple(ope) {
+                    Some(ce)
+                } else {
+                    self.dl(bce)
+                }
+            }
+            CE::ObjectHasSelf(ope) if self.is_non_simple(ope) => Some(ce),
+            CE::ObjectIntersectionOf(ces) | CE::ObjectUnionOf(ces) => {
+                ces.iter().find_map(|ce| self.dl(ce))
+            }
+            CE::ObjectComplementOf(bce)
+            | CE::ObjectSomeValuesFrom { bce, .. }
+            | CE::ObjectAllValuesFrom { bce, .. } => self.dl(bce),
+            _ => None,
+        }
+    }
+
+    /// Checks a class expression occurring at `position` of `axiom` against all profiles.
+    fn check_expression<A: ForIRI>(&mut self, axiom: &str, ce: &CE<A>, position: Position) {
+        if let Some(part) = el(ce) {
+            self.report(
+                Profile::El,
+                axiom,
+                format!("{} isn't allowed", render(part)),
+            );
+        }
+        let (ql, rl) = match position {
+            Position::Subclass => (ql_subclass(ce), rl_subclass(ce)),
+            Position::Superclass => (ql_superclass(ce), rl_superclass(ce)),
+            Position::Equivalent => (ql_subclass(ce), rl_equivalent(ce)),
+            Position::Assertion => (
+                (!matches!(ce, CE::Class(_))).then_some(ce),
+                rl_superclass(ce),
+            ),
+        };
+        for (profile, part) in [(Profile::Ql, ql), (Profile::Rl, rl)] {
+            if let Some(part) = part {
+                self.report(
+                    profile,
+                    axiom,
+                    format!("{} isn't allowed as {}", render(part), position.name()),
+                );
+            }
+        }
+        if let Some(part) = self.dl(ce) {
+            self.report(
+                Profile::Dl,
+                axiom,
+                format!("{} restricts a non-simple property", render(part)),
+            );
+        }
+    }
+
+    /// Checks that `ope` is simple, as required by `axiom` in OWL 2 DL.
+    fn check_simple<A: ForIRI>(&mut self, axiom: &str, ope: &OPE<A>) {
+        if self.is_non_simple(ope) {
+            self.report(
+                Profile::Dl,
+                axiom,
+                format!("{} isn't a simple property", render_property(ope)),
+            );
+        }
+    }
+
+    fn check<A: ForIRI>(&mut self, component: &Component<A>) {
+        let list = |ces: &[CE<A>]| ces.iter().map(render).collect::<Vec<_>>().join(" ");
+        match component {
+            Component::SubClassOf(SubClassOf { sub, sup }) => {
+                let axiom = format!("SubClassOf({} {})", render(sub), render(sup));
+                self.check_expression(&axiom, sub, Position::Subclass);
+                self.check_expression(&axiom, sup, Position::Superclass);
+            }
+            Component::EquivalentClasses(EquivalentClasses(ces)) => {
+                let axiom = format!("EquivalentClasses({})", list(ces));
+                for ce in ces {
+                    self.check_expression(&axiom, ce, Position::Equivalent);
+                }
+            }
+            Component::DisjointClasses(DisjointClasses(ces)) => {
+                let axiom = format!("DisjointClasses({})", list(ces));
+                for ce in ces {
+                    self.check_expression(&axiom, ce, Position::Subclass);
+                }
+            }
+            Component::DisjointUnion(DisjointUnion(class, ces)) => {
+                let axiom = format!("DisjointUnionOf(<{}> {})", class.0, list(ces));
+                for profile in [Profile::El, Profile::Ql, Profile::Rl] {
+                    self.report(profile, &axiom, "disjoint unions aren't allowed");
+                }
+                if let Some(part) = ces.iter().find_map(|ce| self.dl(ce)) {
+                    let reason = format!("{} restricts a non-simple property", render(part));
+                    self.report(Profile::Dl, &axiom, reason);
+                }
+            }
+            Component::ObjectPropertyDomain(ObjectPropertyDomain { ope, ce }) => {
+                let axiom = format!(
+                    "ObjectPropertyDomain({} {})",
+                    render_property(ope),
+                    render(ce)
+                );
+                self.check_expression(&axiom, ce, Position::Superclass);
+            }
+            Component::ObjectPropertyRange(ObjectPropertyRange { ope, ce }) => {
+                let axiom = format!(
+                    "ObjectPropertyRange({} {})",
+                    render_property(ope),
+                    render(ce)
+                );
+                self.check_expression(&axiom, ce, Position::Superclass);
+            }
+            Component::ClassAssertion(assertion) => {
+                let axiom = format!(
+                    "ClassAssertion({} {})",
+                    render(&assertion.ce),
+                    render_individual(&assertion.i)
+                );
+                self.check_expression(&axiom, &assertion.ce, Position::Assertion);
+            }
+            Component::HasKey(HasKey { ce, .. }) => {
+                let axiom = format!("HasKey({} ...)", render(ce));
+                self.report(Profile::Ql, &axiom, "keys aren't allowed");
+                self.check_expression(&axiom, ce, Position::Subclass);
+            }
+            Component::SubObjectPropertyOf(SubObjectPropertyOf {
+                sub: SubObjectPropertyExpression::ObjectPropertyChain(chain),
+                sup,
+            }) => {
+                let axiom = format!(
+                    "SubObjectPropertyOf(ObjectPropertyChain({}) {})",
+                    chain
+                        .iter()
+                        .map(render_property)
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                    render_property(sup)
+                );
+                self.report(Profile::Ql, &axiom, "property chains aren't allowed");
+                // A chain may only use its super property at either end.
+                let inner = chain
+                    .get(1..chain.len().saturating_sub(1))
+                    .unwrap_or_default();
+                if inner.contains(sup) {
+                    let reason = format!("{} occurs inside its own chain", render_property(sup));
+                    self.report(Profile::Dl, &axiom, reason);
+                }
+            }
+            Component::TransitiveObjectProperty(TransitiveObjectProperty(ope)) => {
+                let axiom = format!("TransitiveObjectProperty({})", render_property(ope));
+                self.report(Profile::Ql, &axiom, "transitive properties aren't allowed");
+            }
+            Component::FunctionalObjectProperty(FunctionalObjectProperty(ope)) => {
+                let axiom = format!("FunctionalObjectProperty({})", render_property(ope));
+                for profile in [Profile::El, Profile::Ql] {
+                    self.report(profile, &axiom, "functional properties aren't allowed");
+                }
+                self.check_simple(&axiom, ope);
+            }
+            Component::InverseFunctionalObjectProperty(InverseFunctionalObjectProperty(ope)) => {
+                let axiom = format!("InverseFunctionalObjectProperty({})", render_property(ope));
+                for profile in [Profile::El, Profile::Ql] {
+                    self.report(
+                        profile,
+                        &axiom,
+                        "inverse functional properties aren't allowed",
+                    );
+                }
+                self.check_simple(&axiom, ope);
+            }
+            Component::IrreflexiveObjectProperty(IrreflexiveObjectProperty(ope)) => {
+                let axiom = format!("IrreflexiveObjectProperty({})", render_property(ope));
+                self.report(Profile::El, &axiom, "irreflexive properties aren't allowed");
+                self.check_simple(&axiom, ope);
+            }
+            Component::DisjointObjectProperties(DisjointObjectProperties(opes)) => {
+                let axiom = format!(
+                    "DisjointObjectProperties({})",
+                    opes.iter()
+                        .map(render_property)
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                );
+                self.report(Profile::El, &axiom, "disjoint properties aren't allowed");
+                for ope in opes {
+                    self.check_simple(&axiom, ope);
+                }
+            }
+            Component::InverseObjectProperties(InverseObjectProperties(a, b)) => {
+                let axiom = format!("InverseObjectProperties(<{}> <{}>)", a.0, b.0);
+                self.report(Profile::El, &axiom, "inverse properties aren't allowed");
+            }
+            Component::AsymmetricObjectProperty(property) => {
+                let axiom = format!("AsymmetricObjectProperty({})", render_property(&property.0));
+                self.report(Profile::El, &axiom, "asymmetric properties aren't allowed");
+                self.check_simple(&axiom, &property.0);
+            }
+            Component::SymmetricObjectProperty(property) => {
+                let axiom = format!("SymmetricObjectProperty({})", render_property(&property.0));
+                self.report(Profile::El, &axiom, "symmetric properties aren't allowed");
+            }
+            Component::ReflexiveObjectProperty(property) => {
+                let axiom = format!("ReflexiveObjectProperty({})", render_property(&property.0));
+                self.report(Profile::Rl, &axiom, "reflexive properties aren't allowed");
+            }
+            Component::FunctionalDataProperty(property) => {
+                let axiom = format!("FunctionalDataProperty(<{}>)", property.0.0);
+                self.report(Profile::Ql, &axiom, "functional properties aren't allowed");
+            }
+            Component::DisjointDataProperties(properties) => {
+                let axiom = format!(
+                    "DisjointDataProperties({})",
+                    properties
+                        .0
+                        .iter()
+                        .map(|property| format!("<{}>", property.0))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                );
+                self.report(Profile::El, &axiom, "disjoint properties aren't allowed");
+            }
+            Component::SameIndividual(individuals) => {
+                let axiom = format!(
+                    "SameIndividual({})",
+                    individuals
+                        .0
+                        .iter()
+                        .map(render_individual)
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                );
+                self.report(Profile::Ql, &axiom, "equal individuals aren't allowed");
+            }
+            Component::NegativeObjectPropertyAssertion(assertion) => {
+                let axiom = format!(
+                    "NegativeObjectPropertyAssertion({} {} {})",
+                    render_property(&assertion.ope),
+                    render_individual(&assertion.from),
+                    render_individual(&assertion.to)
+                );
+                self.report(Profile::Ql, &axiom, "negative assertions aren't allowed");
+            }
+            Component::NegativeDataPropertyAssertion(assertion) => {
+                let axiom = format!(
+                    "NegativeDataPropertyAssertion(<{}> {} ...)",
+                    assertion.dp.0,
+                    render_individual(&assertion.from)
+                );
+                self.report(Profile::Ql, &axiom, "negative assertions aren't allowed");
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns the axioms of `components` which don't fit a profile, ordered by profile.
+pub fn validate<'a, A: ForIRI + 'a>(
+    components: impl IntoIterator<Item = &'a Component<A>>,
+) -> Vec<ProfileViolation> {
+    let components = components.into_iter().collect::<Vec<_>>();
+    let mut validator = Validator {
+        non_simple: non_simple_properties(&components),
+        violations: Vec::new(),
+    };
+    for component in components {
+        validator.check(component);
+    }
+    let mut violations = validator.violations;
+    violations.sort_by_key(|violation| violation.profile);
+    violations
+}
+
+/// Validates `ontology` against all profiles.
+pub fn validate_ontology(ontology: &RcComponentMappedOntology) -> Vec<ProfileViolation> {
+    validate(ontology.iter().map(|component| &component.component))
+}
+
+/// Validates the ontology in an RDF/XML document against all profiles.
+pub fn validate_rdf_xml(input: &[u8]) -> Result<Vec<ProfileViolation>, WebVowlStoreError> {
+    let (ontology, _): (ConcreteRDFOntology<RcStr, RcAnnotatedComponent>, _) =
+        horned_owl::io::rdf::reader::read(&mut Cursor::new(input), ParserConfiguration::default())?;
+    Ok(validate_ontology(&ontology.into()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use horned_owl::model::{Build, ObjectProperty};
+
+    #[test]
+    fn test_validate() {
+        let b = Build::<RcStr>::new();
+        let class = |name: &str| CE::Class(b.class(format!("http://example.com#{name}")));
+        let property = |name: &str| -> ObjectProperty<RcStr> {
+            b.object_property(format!("http://example.com#{name}"))
+        };
+        let components = [
+            Component::SubClassOf(SubClassOf {
+                sub: class("Pet"),
+                sup: CE::ObjectUnionOf(vec![class("Cat"), class("Dog")]),
+            }),
+            Component::TransitiveObjectProperty(TransitiveObjectProperty(OPE::ObjectProperty(
+                property("ancestorOf"),
+            ))),
+            Component::SubClassOf(SubClassOf {
+                sub: class("Person"),
+                sup: CE::ObjectMaxCardinality {
+                    n: 2,
+                    ope: OPE::ObjectProperty(property("ancestorOf")),
+                    bce: Box::new(class("Person")),
+                },
+            }),
+        ];
+        let violations = validate(components.iter());
+        let reasons = |profile: Profile| {
+            violations
+                .iter()
+                .filter(|violation| violation.profile == profile)
+                .map(|violation| violation.reason.clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            reasons(Profile::El),
+            vec![
+                "ObjectUnionOf(<http://example.com#Cat> <http://example.com#Dog>) isn't allowed",
+                "ObjectMaxCardinality(2 <http://example.com#ancestorOf> <http://example.com#Person>) isn't allowed",
+            ]
+        );
+        assert_eq!(reasons(Profile::Ql).len(), 3);
+        assert_eq!(reasons(Profile::Rl).len(), 2);
+        assert_eq!(
+            reasons(Profile::Dl),
+            vec![
+                "ObjectMaxCardinality(2 <http://example.com#ancestorOf> <http://example.com#Person>) restricts a non-simple property"
+            ]
+        );
+    }
+}
@@ -1,9 +1,17 @@
 pub mod util {
     use grapher::prelude::GraphDisplayData;
+    use log::info;
+    use std::collections::{HashMap, HashSet};
+    use std::time::{Duration, Instant};
     use vowlr_database::{
         prelude::{GraphDisplayDataSolutionSerializer, QueryResults},
         store::VOWLRStore,
     };
+    use vowlr_sparql_queries::prelude::{DEFAULT_QUERY, QueryAssembler};
+    use vowlr_util::{
+        flags::GraphFlags,
+        options::{ElementFilter, SerializerOptions},
+    };
 
     pub async fn query(query: String) -> Result<GraphDisplayData, String> {
         let store = VOWLRStore::default();
@@ -13,7 +21,7 @@ pub mod util {
         let query_stream = store.session.query(query.as_str()).await.unwrap();
         if let QueryResults::Solutions(solutions) = query_stream {
             solution_serializer
-                .serialize_nodes_stream(&mut data_buffer, solutions)
+                .serialize_nodes_stream(&mut data_buffer, &mut GraphFlags::default(), solutions)
                 .await
                 .unwrap();
         } else {
@@ -21,4 +29,55 @@ pub mod util {
         }
         Ok(data_buffer)
     }
+
+    /// Compares filtering the cached graph with querying a filtered graph.
+    ///
+    /// Each element type of the graph is hidden in turn, as if unchecked in the filter menu,
+    /// and the total time of both approaches is reported.
+    pub async fn measure_filtering() -> Result<(), String> {
+        let store = VOWLRStore::default();
+        let options = SerializerOptions::default();
+        let (data, _, _) = store
+            .graph(&DEFAULT_QUERY, options.clone())
+            .await
+            .map_err(|e| e.to_string())?;
+        let element_types = data.elements.iter().copied().collect::<HashSet<_>>();
+
+        let (mut queried, mut selected) = (Duration::ZERO, Duration::ZERO);
+        for hidden in element_types.iter() {
+            let checks = element_types
+                .iter()
+                .map(|element| (*element, element != hidden))
+                .collect::<HashMap<_, _>>();
+            let start_time = Instant::now();
+            query(QueryAssembler::assemble_filtered_query(&checks, &[])).await?;
+            queried += start_time.elapsed();
+
+            let start_time = Instant::now();
+            store
+                .filtered_graph(
+                    &DEFAULT_QUERY,
+                    options.clone(),
+                    &ElementFilter::default(),
+                    &HashSet::from([*hidden]),
+                    &HashSet::new(),
+                )
+                .await
+                .map_err(|e| e.to_string())?;
+            selected += start_time.elapsed();
+        }
+        info!(
+            "Filtered {} element types\n \
+            \tFiltered queries: {} s\n \
+            \tCached graph    : {} s\n \
+            \tSpeedup         : {:.1}x\n \
+            \tGraph cache     : {}",
+            element_types.len(),
+            queried.as_secs_f32(),
+            selected.as_secs_f32(),
+            queried.as_secs_f32() / selected.as_secs_f32().max(f32::EPSILON),
+            store.cache_metrics()
+        );
+        Ok(())
+    }
 }
@@ -1,7 +1,7 @@
 use env_logger::Env;
 use grapher::prelude::{EVENT_DISPATCHER, RenderEvent};
 use grapher::run;
-use perfdebugger::util::query;
+use perfdebugger::util::{measure_filtering, query};
 use std::env;
 use std::path::Path;
 use vowlr_database::store::VOWLRStore;
@@ -22,6 +22,7 @@ async fn main() {
             .expect("Error inserting file");
 
         let data = query(DEFAULT_QUERY.to_string()).await.unwrap();
+        measure_filtering().await.unwrap();
         EVENT_DISPATCHER
             .rend_write_chan
             .send(RenderEvent::LoadGraph(data))
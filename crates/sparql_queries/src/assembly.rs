@@ -1,19 +1,59 @@
 use crate::{
-    element_type_injection::SparqlSnippet, prelude::GENERAL_SNIPPETS, snippets::void::VOID,
+    builder::{GraphPattern, InvalidIri, Order, SelectQuery},
+    element_type_injection::SparqlSnippet,
+    prelude::{GENERAL_SNIPPETS, RDF_SNIPPETS, SKOS_SNIPPETS},
+    snippets::{
+        general::ANNOTATIONS,
+        hierarchy::{Hierarchy, hierarchy},
+        inference::INFERRED,
+        label::{LABEL_LANGUAGES, label_selection},
+        neighborhood::neighborhood,
+        snippets_from_enum,
+        void::VOID,
+    },
+};
+use grapher::prelude::{
+    ElementType, GenericEdge, GenericNode, OwlEdge, OwlNode, RdfEdge, RdfsEdge, RdfsNode,
 };
-use grapher::prelude::ElementType;
 use std::collections::HashMap;
 
-// TODO: Remove when automatic prefix fetching is implemented.
-pub const DEFAULT_PREFIXES: [&str; 6] = [
-    "owl: <http://www.w3.org/2002/07/owl#>",
-    "rdfs: <http://www.w3.org/2000/01/rdf-schema#>",
-    "rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#>",
-    "xsd: <http://www.w3.org/2001/XMLSchema#>",
-    "vowlr: <http://www.example.com/iri#>",
-    "xml: <http://www.w3.org/XML/1998/namespace>",
+/// Prefixes of the vocabularies referenced by the SPARQL snippets.
+///
+/// Each prefix is a tuple of its name and namespace IRI.
+pub const VOCABULARY_PREFIXES: [(&str, &str); 6] = [
+    ("owl", "http://www.w3.org/2002/07/owl#"),
+    ("rdfs", "http://www.w3.org/2000/01/rdf-schema#"),
+    ("rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#"),
+    ("xsd", "http://www.w3.org/2001/XMLSchema#"),
+    ("xml", "http://www.w3.org/XML/1998/namespace"),
+    ("skos", "http://www.w3.org/2004/02/skos/core#"),
 ];
 
+/// Sorts the ontology first, then classes and datatypes, then everything else.
+const WEIGHT: &str =
+    "IF(?nodeType = owl:Ontology, 0, IF(?nodeType IN (owl:Class, rdfs:Datatype), 1, 2))";
+
+/// The optional layers of the default query, each adding the snippets of its elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layers {
+    /// Whether to query individuals and the assertions about them.
+    pub individuals: bool,
+    /// Whether to query annotations and annotated axioms.
+    pub annotations: bool,
+    /// Whether to query the inferred relations, which must be materialized first.
+    pub inference: bool,
+}
+
+impl Default for Layers {
+    fn default() -> Self {
+        Self {
+            individuals: true,
+            annotations: false,
+            inference: false,
+        }
+    }
+}
+
 /// Compiles snippets of SPARQL code into full-fledged SPARQL queries.
 pub struct QueryAssembler;
 
@@ -21,52 +61,314 @@ impl QueryAssembler {
     /// Construct a SPARQL query from URI prefixes and SPARQL snippets.
     ///
     /// `prefixes` is the collection of prefixes to use.
-    /// An example of a prefix is: `owl: <http://www.w3.org/2002/07/owl#>`.
+    /// An example of a prefix is: `("owl", "http://www.w3.org/2002/07/owl#")`.
     ///
     /// `snippets` is the collection of SPARQL snippets to use.
-    pub fn assemble_query(prefixes: Vec<&str>, snippets: Vec<&'static str>) -> String {
+    ///
+    /// `languages` is the list of preferred label languages, most preferred first.
+    /// Labels fall back to untagged labels, then `skos:prefLabel`, then the local name of the IRI.
+    ///
+    /// The query can be extended before it is built, e.g. scoped to a graph or paginated.
+    pub fn select_query(
+        prefixes: &[(&str, &str)],
+        snippets: Vec<&'static str>,
+        languages: &[String],
+    ) -> SelectQuery {
+        SelectQuery::new()
+            .prefixes(prefixes)
+            .select(["id", "nodeType", "target", "label", "inferred"])
+            .pattern(GraphPattern::Union(
+                snippets
+                    .into_iter()
+                    .filter(|snippet| !snippet.is_empty())
+                    .map(|snippet| GraphPattern::Raw(snippet.to_string()))
+                    .collect(),
+            ))
+            .pattern(GraphPattern::Raw(label_selection(languages)))
+            .pattern(GraphPattern::Bind {
+                expression: WEIGHT.to_string(),
+                variable: "weight".into(),
+            })
+            .order_by(Order::Ascending("weight".into()))
+    }
+
+    /// Construct a SPARQL query from URI prefixes and SPARQL snippets.
+    ///
+    /// See [`QueryAssembler::select_query`].
+    pub fn assemble_query(
+        prefixes: &[(&str, &str)],
+        snippets: Vec<&'static str>,
+        languages: &[String],
+    ) -> String {
+        Self::select_query(prefixes, snippets, languages).build()
+    }
+
+    /// Construct the query containing all classes and properties supported by VOWL-R,
+    /// along with the elements of the enabled `layers`.
+    ///
+    /// Labels are chosen according to `languages`, most preferred first.
+    pub fn default_query(languages: &[String], layers: Layers) -> SelectQuery {
+        let mut snippets = [
+            snippets_from_enum::<OwlNode>(),
+            snippets_from_enum::<OwlEdge>(),
+            snippets_from_enum::<RdfEdge>(),
+            snippets_from_enum::<RdfsNode>(),
+            snippets_from_enum::<RdfsEdge>(),
+        ]
+        .concat();
+        if layers.individuals {
+            snippets.extend(snippets_from_enum::<GenericNode>());
+            snippets.extend(snippets_from_enum::<GenericEdge>());
+        }
+        if layers.annotations {
+            snippets.push(ANNOTATIONS);
+        }
+        if layers.inference {
+            snippets.push(INFERRED);
+        }
+        snippets.extend(GENERAL_SNIPPETS);
+
+        Self::select_query(&VOCABULARY_PREFIXES, snippets, languages)
+    }
+
+    /// Construct the query containing all classes and properties supported by VOWL-R,
+    /// along with the elements of the enabled `layers`.
+    ///
+    /// Labels are chosen according to `languages`, most preferred first.
+    pub fn assemble_default_query(languages: &[String], layers: Layers) -> String {
+        Self::default_query(languages, layers).build()
+    }
+
+    /// Restricts a query to the elements within reach of `foci`,
+    /// each a tuple of its IRI and the number of hops to reach.
+    ///
+    /// Hops follow subclasses, set operators and the domain and range of properties.
+    /// Returns an error if the IRI of a focus isn't valid.
+    pub fn neighborhood(foci: &[(String, usize)]) -> Result<GraphPattern, InvalidIri> {
+        Ok(GraphPattern::Raw(neighborhood(foci)?))
+    }
+
+    /// Construct the query drawing the elements within reach of `foci`.
+    ///
+    /// Labels are chosen according to `languages`, most preferred first.
+    pub fn neighborhood_query(
+        foci: &[(String, usize)],
+        languages: &[String],
+        layers: Layers,
+    ) -> Result<SelectQuery, InvalidIri> {
+        Ok(Self::default_query(languages, layers).pattern(Self::neighborhood(foci)?))
+    }
+
+    /// Construct a query returning the IRI and label of the named elements within reach of `foci`,
+    /// ordered by label.
+    pub fn neighborhood_elements_query(
+        foci: &[(String, usize)],
+        languages: &[String],
+    ) -> Result<SelectQuery, InvalidIri> {
+        Ok(SelectQuery::new()
+            .prefixes(&VOCABULARY_PREFIXES)
+            .distinct()
+            .select(["id", "label"])
+            .pattern(Self::neighborhood(foci)?)
+            .filter("isIRI(?id) && NOT EXISTS { ?id a owl:Ontology }")
+            .pattern(GraphPattern::Raw(label_selection(languages)))
+            .order_by(Order::Ascending("label".into())))
+    }
+
+    /// Restricts a query to the classes of `hierarchy`, i.e. the subtree below its root
+    /// or the ancestors above it.
+    ///
+    /// The properties and set operators of the classes are kept.
+    /// Returns an error if the IRI of the root isn't valid.
+    pub fn hierarchy(hierarchy: &Hierarchy) -> Result<GraphPattern, InvalidIri> {
+        Ok(GraphPattern::Raw(self::hierarchy(hierarchy)?))
+    }
+
+    /// Construct the query drawing the classes of `hierarchy`.
+    ///
+    /// Labels are chosen according to `languages`, most preferred first.
+    pub fn hierarchy_query(
+        hierarchy: &Hierarchy,
+        languages: &[String],
+        layers: Layers,
+    ) -> Result<SelectQuery, InvalidIri> {
+        Ok(Self::default_query(languages, layers).pattern(Self::hierarchy(hierarchy)?))
+    }
+
+    /// Construct the query drawing every resource as a node and every triple as an edge.
+    ///
+    /// Labels are chosen according to `languages`, most preferred first.
+    pub fn assemble_rdf_query(languages: &[String]) -> String {
+        Self::assemble_query(&VOCABULARY_PREFIXES, RDF_SNIPPETS.into(), languages)
+    }
+
+    /// Construct the query drawing the concepts of SKOS vocabularies and their relations.
+    ///
+    /// Labels are chosen according to `languages`, most preferred first.
+    pub fn assemble_skos_query(languages: &[String]) -> String {
+        Self::assemble_query(&VOCABULARY_PREFIXES, SKOS_SNIPPETS.into(), languages)
+    }
+
+    /// Construct a query returning the distinct language tags of all labels.
+    pub fn assemble_label_languages_query() -> String {
         format!(
-            r#"
-            {}
-            SELECT ?id ?nodeType ?target ?label
-            WHERE {{
-                {}
-                BIND(
-                    IF(?nodeType = owl:Ontology, 0,
-                        IF(?nodeType = owl:Class, 1, 2)
-                    )
-                    AS ?weight
-                )
-            }}
-            ORDER BY ?weight
-        "#,
-            prefixes
-                .iter()
-                .map(|item| format!("PREFIX {item}"))
-                .collect::<Vec<_>>()
-                .join("\n"),
-            snippets
-                .iter()
-                .map(|item| item.to_string())
-                .filter(|item| item.len() > 0)
-                .collect::<Vec<_>>()
-                .join(" UNION "),
+            "{}\n{}",
+            Self::prologue(&VOCABULARY_PREFIXES),
+            LABEL_LANGUAGES
         )
     }
 
+    /// Construct the `PREFIX` declarations of a query.
+    pub fn prologue(prefixes: &[(&str, &str)]) -> String {
+        prefixes
+            .iter()
+            .map(|(name, iri)| format!("PREFIX {name}: <{iri}>"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Declare the prefixes of the loaded documents in `query`.
+    ///
+    /// Prefixes already declared by `query` are left untouched,
+    /// so the query can always rely on its own declarations.
+    pub fn with_document_prefixes<'a>(
+        query: &str,
+        prefixes: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> String {
+        let declared = query
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim_start();
+                line.get(..7)
+                    .filter(|keyword| keyword.eq_ignore_ascii_case("PREFIX "))
+                    .and_then(|_| line[7..].trim_start().split_once(':'))
+                    .map(|(name, _)| name.trim().to_string())
+            })
+            .collect::<Vec<_>>();
+        let missing = prefixes
+            .into_iter()
+            .filter(|(name, _)| !declared.iter().any(|declared| declared == name))
+            .collect::<Vec<_>>();
+        if missing.is_empty() {
+            return query.to_string();
+        }
+        format!("{}\n{}", Self::prologue(&missing), query)
+    }
+
     /// Construct a filtered SPARQL query based on the truth value of entries in `element_filter`.
-    pub fn assemble_filtered_query(element_filter: &HashMap<ElementType, bool>) -> String {
+    ///
+    /// Labels are chosen according to `languages`, most preferred first.
+    pub fn filtered_query(
+        element_filter: &HashMap<ElementType, bool>,
+        languages: &[String],
+    ) -> SelectQuery {
         let mut snippets = element_filter
             .iter()
             .filter(|&(_, &checked)| checked)
             .map(|(elem, _)| elem.snippet())
             .collect::<Vec<&str>>();
+        // The same filter always gives the same query, regardless of the map's order.
+        snippets.sort_unstable();
+        snippets.dedup();
 
-        if snippets.len() > 0 {
-            snippets.extend(GENERAL_SNIPPETS);
-        } else {
+        if snippets.is_empty() {
             snippets.push(VOID);
+        } else {
+            snippets.extend(GENERAL_SNIPPETS);
         }
-        Self::assemble_query(DEFAULT_PREFIXES.into(), snippets)
+        Self::select_query(&VOCABULARY_PREFIXES, snippets, languages)
+    }
+
+    /// Construct a filtered SPARQL query based on the truth value of entries in `element_filter`.
+    ///
+    /// Labels are chosen according to `languages`, most preferred first.
+    pub fn assemble_filtered_query(
+        element_filter: &HashMap<ElementType, bool>,
+        languages: &[String],
+    ) -> String {
+        Self::filtered_query(element_filter, languages).build()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use grapher::prelude::{OwlType, RdfsType};
+
+    #[test]
+    fn test_with_document_prefixes() {
+        let query = "PREFIX owl: <http://www.w3.org/2002/07/owl#>\nSELECT * WHERE { ?s ?p ?o }";
+        let prefixed = QueryAssembler::with_document_prefixes(
+            query,
+            [
+                ("owl", "http://example.com/not-owl#"),
+                ("ex", "http://example.com/"),
+            ],
+        );
+        assert_eq!(
+            prefixed,
+            format!("PREFIX ex: <http://example.com/>\n{query}")
+        );
+    }
+
+    #[test]
+    fn test_default_query() {
+        insta::assert_snapshot!(QueryAssembler::assemble_default_query(
+            &["en".to_string()],
+            Layers::default()
+        ));
+    }
+
+    #[test]
+    fn test_default_query_layers() {
+        let query = QueryAssembler::assemble_default_query(
+            &[],
+            Layers {
+                individuals: false,
+                annotations: false,
+                inference: false,
+            },
+        );
+        assert!(!query.contains("BIND(owl:NamedIndividual AS ?nodeType)"));
+        assert!(!query.contains("?id a owl:Axiom"));
+        assert!(!query.contains("BIND(true AS ?inferred)"));
+        assert!(query.contains("?id a owl:Class"));
+
+        let query = QueryAssembler::assemble_default_query(
+            &[],
+            Layers {
+                individuals: true,
+                annotations: true,
+                inference: true,
+            },
+        );
+        assert!(query.contains("BIND(owl:NamedIndividual AS ?nodeType)"));
+        assert!(query.contains("?id a owl:Axiom"));
+        assert!(query.contains("BIND(true AS ?inferred)"));
+    }
+
+    #[test]
+    fn test_filtered_query() {
+        let filter = HashMap::from([
+            (ElementType::Owl(OwlType::Node(OwlNode::Class)), true),
+            (
+                ElementType::Owl(OwlType::Edge(OwlEdge::ObjectProperty)),
+                true,
+            ),
+            (ElementType::Rdfs(RdfsType::Node(RdfsNode::Literal)), false),
+        ]);
+        insta::assert_snapshot!(QueryAssembler::assemble_filtered_query(&filter, &[]));
+        insta::assert_snapshot!(
+            "filtered_query_nothing_checked",
+            QueryAssembler::assemble_filtered_query(&HashMap::new(), &[])
+        );
+    }
+
+    #[test]
+    fn test_assemble_skos_query() {
+        let query = QueryAssembler::assemble_skos_query(&[]);
+        assert!(query.contains("PREFIX skos: <http://www.w3.org/2004/02/skos/core#>"));
+        assert!(query.contains("?target skos:narrower ?id"));
+        assert!(!query.contains("?id a owl:Class"));
     }
 }
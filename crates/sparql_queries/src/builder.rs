@@ -0,0 +1,355 @@
+//! A typed builder for SPARQL `SELECT` queries.
+//!
+//! The builder only arranges the parts of a query, so expressions and snippets are plain SPARQL.
+//! Variables are sanitized and IRIs checked by [`iri`], as they may come from user input.
+
+use std::fmt::{Display, Formatter, Result};
+
+/// A query variable, e.g. `?id`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Variable(String);
+
+impl Variable {
+    /// Creates the variable `?name`.
+    ///
+    /// Characters not allowed in variable names are left out.
+    pub fn new(name: &str) -> Self {
+        Self(
+            name.trim_start_matches(['?', '$'])
+                .chars()
+                .filter(|c| c.is_alphanumeric() || *c == '_')
+                .collect(),
+        )
+    }
+}
+
+impl From<&str> for Variable {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl Display for Variable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "?{}", self.0)
+    }
+}
+
+/// Characters not allowed in IRI references, besides whitespace.
+const INVALID_IRI_CHARACTERS: &str = "<>\"{}|^`\\";
+
+/// An IRI containing characters not allowed in IRI references.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidIri(pub String);
+
+impl Display for InvalidIri {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "Invalid IRI {:?}, as whitespace and {INVALID_IRI_CHARACTERS} aren't allowed",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for InvalidIri {}
+
+/// Write `iri` as an IRI reference, e.g. `<http://example.com/>`.
+///
+/// IRIs with characters not allowed in IRI references are rejected,
+/// which prevents user input from escaping the reference.
+pub fn iri(iri: &str) -> std::result::Result<String, InvalidIri> {
+    if iri
+        .chars()
+        .any(|c| c.is_whitespace() || INVALID_IRI_CHARACTERS.contains(c))
+    {
+        return Err(InvalidIri(iri.to_string()));
+    }
+    Ok(format!("<{iri}>"))
+}
+
+/// A part of the `WHERE` clause.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphPattern {
+    /// SPARQL written by hand, e.g. one of the snippets, inserted verbatim.
+    Raw(String),
+    /// Alternatives, any of which may match.
+    Union(Vec<GraphPattern>),
+    /// A pattern matched against the named graph with the given IRI,
+    /// which is written as given, see [`SelectQuery::from_graph`].
+    Graph {
+        graph: String,
+        pattern: Box<GraphPattern>,
+    },
+    /// A pattern extending the solutions where it matches.
+    Optional(Box<GraphPattern>),
+    /// Binds the value of `expression` to `variable`.
+    Bind {
+        expression: String,
+        variable: Variable,
+    },
+    /// Keeps the solutions for which `expression` is true.
+    Filter(String),
+    /// Binds `variables` to each row of terms. `UNDEF` leaves a variable unbound.
+    Values {
+        variables: Vec<Variable>,
+        rows: Vec<Vec<String>>,
+    },
+}
+
+impl GraphPattern {
+    /// Writes the pattern as a group, i.e. enclosed in braces.
+    fn group(&self) -> String {
+        match self {
+            Self::Raw(sparql) if sparql.trim_start().starts_with('{') => sparql.clone(),
+            _ => format!("{{ {self} }}"),
+        }
+    }
+}
+
+impl Display for GraphPattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Raw(sparql) => write!(f, "{sparql}"),
+            Self::Union(patterns) => write!(
+                f,
+                "{{\n{}\n}}",
+                patterns
+                    .iter()
+                    .map(Self::group)
+                    .collect::<Vec<_>>()
+                    .join(" UNION ")
+            ),
+            Self::Graph { graph, pattern } => write!(f, "GRAPH <{graph}> {}", pattern.group()),
+            Self::Optional(pattern) => write!(f, "OPTIONAL {}", pattern.group()),
+            Self::Bind {
+                expression,
+                variable,
+            } => write!(f, "BIND({expression} AS {variable})"),
+            Self::Filter(expression) => write!(f, "FILTER({expression})"),
+            Self::Values { variables, rows } => write!(
+                f,
+                "VALUES ({}) {{ {} }}",
+                variables
+                    .iter()
+                    .map(Variable::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                rows.iter()
+                    .map(|row| format!("({})", row.join(" ")))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
+        }
+    }
+}
+
+/// The order of the solutions by a variable.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Order {
+    Ascending(Variable),
+    Descending(Variable),
+}
+
+impl Display for Order {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Ascending(variable) => write!(f, "{variable}"),
+            Self::Descending(variable) => write!(f, "DESC({variable})"),
+        }
+    }
+}
+
+/// A SPARQL `SELECT` query.
+///
+/// Without a projection, all variables are selected.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SelectQuery {
+    prefixes: Vec<(String, String)>,
+    distinct: bool,
+    projection: Vec<Variable>,
+    graphs: Vec<String>,
+    patterns: Vec<GraphPattern>,
+    order: Vec<Order>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+}
+
+impl SelectQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares `prefixes`, each a tuple of its name and namespace IRI.
+    ///
+    /// The IRIs are written as given, so user input should be checked by [`iri`] first.
+    pub fn prefixes(mut self, prefixes: &[(&str, &str)]) -> Self {
+        self.prefixes.extend(
+            prefixes
+                .iter()
+                .map(|(name, iri)| (name.to_string(), iri.to_string())),
+        );
+        self
+    }
+
+    /// Removes duplicate solutions.
+    pub fn distinct(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
+
+    /// Adds `variables` to the projection.
+    pub fn select<V: Into<Variable>>(mut self, variables: impl IntoIterator<Item = V>) -> Self {
+        self.projection
+            .extend(variables.into_iter().map(Into::into));
+        self
+    }
+
+    /// Matches the query against the named graph `graph` instead of the default graph.
+    ///
+    /// The default graph of the query becomes the merge of all added graphs.
+    /// The IRI is written as given, so user input should be checked by [`iri`] first.
+    pub fn from_graph(mut self, graph: &str) -> Self {
+        self.graphs.push(graph.to_string());
+        self
+    }
+
+    /// Adds `pattern` to the `WHERE` clause.
+    pub fn pattern(mut self, pattern: GraphPattern) -> Self {
+        self.patterns.push(pattern);
+        self
+    }
+
+    /// Keeps the solutions for which `expression` is true.
+    pub fn filter(self, expression: &str) -> Self {
+        self.pattern(GraphPattern::Filter(expression.to_string()))
+    }
+
+    /// Binds `variables` to each row of terms.
+    pub fn values<V: Into<Variable>>(
+        self,
+        variables: impl IntoIterator<Item = V>,
+        rows: Vec<Vec<String>>,
+    ) -> Self {
+        self.pattern(GraphPattern::Values {
+            variables: variables.into_iter().map(Into::into).collect(),
+            rows,
+        })
+    }
+
+    /// Orders the solutions by `order`, after any previously added orders.
+    pub fn order_by(mut self, order: Order) -> Self {
+        self.order.push(order);
+        self
+    }
+
+    /// Returns at most `limit` solutions.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Skips the first `offset` solutions.
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Writes the query as SPARQL.
+    pub fn build(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Display for SelectQuery {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        for (name, namespace) in self.prefixes.iter() {
+            writeln!(f, "PREFIX {name}: <{namespace}>")?;
+        }
+        write!(f, "SELECT ")?;
+        if self.distinct {
+            write!(f, "DISTINCT ")?;
+        }
+        if self.projection.is_empty() {
+            writeln!(f, "*")?;
+        } else {
+            writeln!(
+                f,
+                "{}",
+                self.projection
+                    .iter()
+                    .map(Variable::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            )?;
+        }
+        for graph in self.graphs.iter() {
+            writeln!(f, "FROM <{graph}>")?;
+        }
+        writeln!(f, "WHERE {{")?;
+        for pattern in self.patterns.iter() {
+            writeln!(f, "{pattern}")?;
+        }
+        write!(f, "}}")?;
+        if !self.order.is_empty() {
+            write!(
+                f,
+                "\nORDER BY {}",
+                self.order
+                    .iter()
+                    .map(Order::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            )?;
+        }
+        if let Some(limit) = self.limit {
+            write!(f, "\nLIMIT {limit}")?;
+        }
+        if let Some(offset) = self.offset {
+            write!(f, "\nOFFSET {offset}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_select_query() {
+        let query = SelectQuery::new()
+            .prefixes(&[("owl", "http://www.w3.org/2002/07/owl#")])
+            .distinct()
+            .select(["id", "?label"])
+            .from_graph("http://example.com/graph")
+            .pattern(GraphPattern::Union(vec![
+                GraphPattern::Raw("{ ?id a owl:Class }".to_string()),
+                GraphPattern::Graph {
+                    graph: "urn:vowlr:inferred".to_string(),
+                    pattern: Box::new(GraphPattern::Raw("?id a owl:Class".to_string())),
+                },
+            ]))
+            .pattern(GraphPattern::Optional(Box::new(GraphPattern::Raw(
+                "?id rdfs:label ?label".to_string(),
+            ))))
+            .values(["id"], vec![vec!["<http://example.com#A>".to_string()]])
+            .filter("isIRI(?id)")
+            .order_by(Order::Descending("label".into()))
+            .order_by(Order::Ascending("id".into()))
+            .limit(10)
+            .offset(20);
+        insta::assert_snapshot!(query.build());
+    }
+
+    #[test]
+    fn test_sanitizes_user_input() {
+        assert_eq!(Variable::new("?id } DROP ALL").to_string(), "?idDROPALL");
+        assert_eq!(
+            iri("http://example.com/#A"),
+            Ok("<http://example.com/#A>".to_string())
+        );
+        assert!(iri("http://example.com/> } DROP ALL { <").is_err());
+        assert!(iri("http://example.com/A B").is_err());
+    }
+}
@@ -3,6 +3,30 @@ use grapher::prelude::{
     RdfType, RdfsEdge, RdfsNode, RdfsType,
 };
 
+/// Keeps the elements whose IRI is outside the namespaces of the ontology,
+/// i.e. of its ontology IRIs and version IRIs.
+///
+/// See `match_length` of the serializer, which this mirrors. The serializer still
+/// decides which of the remaining elements are external, as only it knows the
+/// base IRIs of the documents and the namespaces marked by the user.
+macro_rules! outside_ontology {
+    () => {
+        r##"
+                FILTER NOT EXISTS {
+                    { ?ontology a owl:Ontology . }
+                    UNION
+                    { ?versioned owl:versionIRI ?ontology . }
+                    FILTER(isIRI(?ontology))
+                    BIND(REPLACE(STR(?ontology), "#$", "") AS ?base)
+                    BIND(STRAFTER(STR(?id), ?base) AS ?rest)
+                    FILTER(STRSTARTS(STR(?id), ?base) && (
+                        STRENDS(?base, "/") || STR(?id) = ?base
+                        || STRSTARTS(?rest, "#") || STRSTARTS(?rest, "/")
+                    ))
+                }"##
+    };
+}
+
 pub trait SparqlSnippet {
     /// Get the SPARQL snippet representing `self`.
     fn snippet(self) -> &'static str;
@@ -26,7 +50,13 @@ impl SparqlSnippet for ElementType {
 impl SparqlSnippet for GenericNode {
     fn snippet(self) -> &'static str {
         match self {
-            GenericNode::Generic => todo!(),
+            GenericNode::Generic => {
+                // Individuals
+                r#"{
+                ?id a owl:NamedIndividual .
+                BIND(owl:NamedIndividual AS ?nodeType)
+                }"#
+            }
         }
     }
 }
@@ -34,7 +64,35 @@ impl SparqlSnippet for GenericNode {
 impl SparqlSnippet for GenericEdge {
     fn snippet(self) -> &'static str {
         match self {
-            GenericEdge::Generic => todo!(),
+            GenericEdge::Generic => {
+                // Assertions about individuals
+                r#"{
+                ?id a owl:NamedIndividual .
+                {
+                    # Class assertions
+                    ?id a ?target .
+                    FILTER(isIRI(?target) && ?target != owl:NamedIndividual)
+                    BIND(rdf:type AS ?nodeType)
+                }
+                UNION
+                {
+                    # Object and data property assertions
+                    ?id ?nodeType ?target .
+                    ?nodeType a ?propertyType .
+                    FILTER(?propertyType IN (owl:ObjectProperty, owl:DatatypeProperty))
+                }
+                UNION
+                {
+                    ?id owl:sameAs ?target .
+                    BIND(owl:sameAs AS ?nodeType)
+                }
+                UNION
+                {
+                    ?id owl:differentFrom ?target .
+                    BIND(owl:differentFrom AS ?nodeType)
+                }
+                }"#
+            }
         }
     }
 }
@@ -64,7 +122,7 @@ impl SparqlSnippet for RdfsNode {
             }
             RdfsNode::Datatype => {
                 r#"{
-                ?id rdfs:Datatype ?target
+                ?id a rdfs:Datatype
                 BIND(rdfs:Datatype AS ?nodeType)
                 }"#
             }
@@ -127,11 +185,15 @@ impl SparqlSnippet for OwlNode {
                 BIND(owl:DeprecatedClass AS ?nodeType)
                 }"#
             }
-            OwlNode::ExternalClass => {
-                // Not handled here as externals uses identical
-                // logic across classes and properties.
-                ""
-            }
+            OwlNode::ExternalClass => concat!(
+                r#"{
+                ?id a owl:Class .
+                FILTER(isIRI(?id))"#,
+                outside_ontology!(),
+                r#"
+                BIND(owl:Class AS ?nodeType)
+                }"#
+            ),
             OwlNode::EquivalentClass => {
                 r#"{
                 ?id owl:equivalentClass ?target
@@ -171,7 +233,7 @@ impl SparqlSnippet for OwlEdge {
         match self {
             OwlEdge::DatatypeProperty => {
                 r#"{
-                ?id owl:DatatypeProperty ?target
+                ?id a owl:DatatypeProperty
                 BIND(owl:DatatypeProperty AS ?nodeType)
                 }"#
             }
@@ -187,11 +249,15 @@ impl SparqlSnippet for OwlEdge {
                 BIND(owl:DeprecatedProperty AS ?nodeType)
                 }"#
             }
-            OwlEdge::ExternalProperty => {
-                // Not handled here as externals uses identical
-                // logic across classes and properties.
-                ""
-            }
+            OwlEdge::ExternalProperty => concat!(
+                r#"{
+                ?id a ?nodeType .
+                FILTER(?nodeType IN (owl:ObjectProperty, owl:DatatypeProperty))
+                FILTER(isIRI(?id))"#,
+                outside_ontology!(),
+                r#"
+                }"#
+            ),
             OwlEdge::InverseOf => {
                 r#"{
                 ?id owl:inverseOf ?target .
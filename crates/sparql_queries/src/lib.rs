@@ -5,43 +5,53 @@
 //! - the client/wasm side (via `vowlr`)
 
 mod assembly;
+mod builder;
 mod element_type_injection;
 mod snippets;
 
 /// Exports all the core types of the library.
 pub mod prelude {
-    use grapher::prelude::{OwlEdge, OwlNode, RdfEdge, RdfsEdge, RdfsNode};
     use std::sync::LazyLock;
 
-    use crate::assembly::DEFAULT_PREFIXES;
-    pub use crate::assembly::QueryAssembler;
+    pub use crate::assembly::{Layers, QueryAssembler, VOCABULARY_PREFIXES};
+    pub use crate::builder::{GraphPattern, InvalidIri, Order, SelectQuery, Variable, iri};
     use crate::snippets::general::{
-        COLLECTIONS, DOMAIN_AND_RANGE, LABEL, ONTOLOGY, OWL_DEPRECATED, XML_BASE,
+        COLLECTIONS, DATATYPE_DEFINITIONS, DISJOINTNESS, DOMAIN_AND_RANGE, HAS_VALUE, IMPORTS,
+        ONTOLOGY, OWL_DEPRECATED, PROPERTY_CHAINS, PROPERTY_HIERARCHY, VERSION_IRI,
     };
-    use crate::snippets::snippets_from_enum;
+    pub use crate::snippets::hierarchy::{Direction, Hierarchy, MAX_DEPTH};
+    pub use crate::snippets::inference::INFERRED_GRAPH;
+    pub use crate::snippets::neighborhood::MAX_RADIUS;
+    use crate::snippets::rdf::TRIPLES;
+    use crate::snippets::skos::{BROADER, CONCEPTS, MAPPINGS, RELATED, SCHEMES};
 
     /// SPARQL snippets that should generally be included in all queries.
-    pub static GENERAL_SNIPPETS: [&str; 6] = [
+    ///
+    /// Labels are not a snippet, as they are joined onto every result row.
+    /// Snippets of optional layers are added by [`QueryAssembler::default_query`].
+    pub static GENERAL_SNIPPETS: [&str; 11] = [
         ONTOLOGY,
-        XML_BASE,
+        VERSION_IRI,
+        IMPORTS,
         COLLECTIONS,
+        HAS_VALUE,
+        DATATYPE_DEFINITIONS,
+        PROPERTY_HIERARCHY,
+        PROPERTY_CHAINS,
+        DISJOINTNESS,
         DOMAIN_AND_RANGE,
         OWL_DEPRECATED,
-        LABEL,
     ];
 
-    /// The default query contains all classes and properties supported by VOWL-R.
-    pub static DEFAULT_QUERY: LazyLock<String> = LazyLock::new(|| {
-        let snippets = vec![
-            snippets_from_enum::<OwlNode>(),
-            snippets_from_enum::<OwlEdge>(),
-            snippets_from_enum::<RdfEdge>(),
-            snippets_from_enum::<RdfsNode>(),
-            snippets_from_enum::<RdfsEdge>(),
-            GENERAL_SNIPPETS.into(),
-        ]
-        .concat();
+    /// SPARQL snippets drawing every resource as a node and every triple as an edge.
+    pub static RDF_SNIPPETS: [&str; 1] = [TRIPLES];
+
+    /// SPARQL snippets drawing the concepts of SKOS vocabularies and their relations.
+    pub static SKOS_SNIPPETS: [&str; 5] = [CONCEPTS, BROADER, RELATED, SCHEMES, MAPPINGS];
 
-        QueryAssembler::assemble_query(DEFAULT_PREFIXES.into(), snippets)
-    });
+    /// The default query contains all classes and properties supported by VOWL-R.
+    ///
+    /// Labels are chosen without any language preference, and the default layers are drawn.
+    pub static DEFAULT_QUERY: LazyLock<String> =
+        LazyLock::new(|| QueryAssembler::assemble_default_query(&[], Layers::default()));
 }
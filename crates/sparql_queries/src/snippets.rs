@@ -1,4 +1,10 @@
 pub mod general;
+pub mod hierarchy;
+pub mod inference;
+pub mod label;
+pub mod neighborhood;
+pub mod rdf;
+pub mod skos;
 pub mod void;
 
 use crate::element_type_injection::SparqlSnippet;
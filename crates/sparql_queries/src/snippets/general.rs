@@ -9,66 +9,56 @@ pub const COLLECTIONS: &str = r#"{
                 owl:intersectionOf, 
                 owl:unionOf, 
                 owl:oneOf,
-                owl:disjointUnionOf
+                owl:disjointUnionOf,
+                owl:members,
+                owl:distinctMembers
             ))
 
             # 6. Safety: Remove nil to avoid phantom edges
             # FILTER(?label != rdf:nil)
             }"#;
 
-/// External classes.
+/// External elements.
 ///
-/// 1. Elements whose base URI differs from that of the visualized ontology.
-///    p. 6 of https://www.semantic-web-journal.net/system/files/swj1114.pdf
-/// 2. A base URI is EITHER `xml:base` OR that of the document.
-///    https://www.w3.org/TR/rdf-syntax-grammar/#section-Syntax-ID-xml-base
-pub const XML_BASE: &str = r#"{
-            ?id xml:base ?base .
-            BIND(xml:base AS ?nodeType)
-            }"#;
-
-/// External classes.
+/// Get the IRI of the ontology, used as the base namespace of its elements.
+/// Elements outside this namespace are external.
+/// p. 6 of https://www.semantic-web-journal.net/system/files/swj1114.pdf
 ///
-/// Get the base URI of the document.
+/// Note that `xml:base` is part of the RDF/XML syntax and not of the data.
+/// It's therefore collected by the parser instead.
+/// https://www.w3.org/TR/rdf-syntax-grammar/#section-Syntax-ID-xml-base
 pub const ONTOLOGY: &str = r#"{
             ?id a owl:Ontology .
             BIND(owl:Ontology AS ?nodeType)
             }"#;
 
-/// Generic, deprecated OWL elements.
+/// External elements.
 ///
-/// This query is still work-in-progress.
-/// We need to figure out what type the deprecated element is.
-/// It could be a class or a property!
-pub const OWL_DEPRECATED: &str = r#"{
-            # WIP: Identify OWL deprecated properties
-            # ?id owl:deprecated "true"^^<http://www.w3.org/2001/XMLSchema#boolean>
-            # BIND("DeprecatedProperty" AS ?nodeType)
-        
-            # DEPRECATED
-            ?id owl:deprecated ?target .
-            BIND(owl:deprecated AS ?nodeType)
+/// Get the version IRI of the ontology, which may differ from the ontology IRI.
+/// https://www.w3.org/TR/owl2-syntax/#Ontology_IRI_and_Version_IRI
+pub const VERSION_IRI: &str = r#"{
+            ?id owl:versionIRI ?target .
+            BIND(owl:versionIRI AS ?nodeType)
             }"#;
 
-/// Find labels for elements in the following order:
-/// 1. Use rdfs:label, if exists.
-///    https://www.w3.org/TR/rdf-schema/#ch_label
-/// 2. Use rdf:resource, if exists.
-///    https://www.w3.org/TR/rdf-syntax-grammar/#section-Syntax-empty-property-elements
-/// 3. Use rdf:ID, if exists.
-///    https://www.w3.org/TR/rdf-syntax-grammar/#section-Syntax-ID-xml-base
-pub const LABEL: &str = r#"{
-            OPTIONAL { ?id rdfs:label ?theLabel }
-            OPTIONAL { ?id rdf:resource ?resLabel }
-            OPTIONAL { ?id rdf:ID ?idLabel }
-            BIND (
-                COALESCE(
-                    IF( BOUND(?theLabel), ?theLabel, 1/0 ),
-                    IF( BOUND(?resLabel), ?resLabel, 1/0 ),
-                    IF( BOUND(?idLabel), ?idLabel, 1/0 ),
-                    ""
-                ) AS ?label
-            )
+/// External elements.
+///
+/// Get the ontologies imported by the ontology. Their elements are external.
+/// https://www.w3.org/TR/owl2-syntax/#Imports
+pub const IMPORTS: &str = r#"{
+            ?id owl:imports ?target .
+            BIND(owl:imports AS ?nodeType)
+            }"#;
+
+/// Deprecated elements of any kind.
+///
+/// Both typed (`"true"^^xsd:boolean`) and plain (`"true"`) literals are accepted.
+/// Whether the element is a class or a property is decided by the serializer.
+/// https://www.w3.org/TR/owl2-syntax/#Annotation_Properties
+pub const OWL_DEPRECATED: &str = r#"{
+            ?id owl:deprecated ?deprecated .
+            FILTER(LCASE(STR(?deprecated)) IN ("true", "1"))
+            BIND(owl:deprecated AS ?nodeType)
             }"#;
 
 /// Find the domain and range of any property.
@@ -85,3 +75,131 @@ pub const DOMAIN_AND_RANGE: &str = r#"{
                 BIND(rdfs:range AS ?nodeType)
             }
             }"#;
+
+/// Value restrictions, e.g. `ObjectHasValue(:op :I)` or `DataHasValue(:dp "A Literal")`.
+///
+/// The restricted property is returned in a separate row, with the restriction as `?id`.
+/// https://www.w3.org/TR/owl2-syntax/#Individual_Value_Restriction
+pub const HAS_VALUE: &str = r#"{
+            {
+                ?id owl:hasValue ?target
+                BIND(owl:hasValue AS ?nodeType)
+            }
+            UNION
+            {
+                ?id owl:hasValue ?value ;
+                    owl:onProperty ?target
+                BIND(owl:onProperty AS ?nodeType)
+            }
+            }"#;
+
+/// Datatype complements and restrictions, e.g. `DataComplementOf(owl:rational)`
+/// or `DatatypeRestriction(xsd:integer xsd:minExclusive "10"^^xsd:integer)`.
+///
+/// Each facet of a restriction is returned as a separate row, with the facet as `?nodeType`.
+/// Unions and intersections of datatypes are part of [`COLLECTIONS`].
+/// https://www.w3.org/TR/owl2-syntax/#Data_Ranges
+pub const DATATYPE_DEFINITIONS: &str = r#"{
+            {
+                ?id owl:datatypeComplementOf ?target
+                BIND(owl:datatypeComplementOf AS ?nodeType)
+            }
+            UNION
+            {
+                ?id owl:onDatatype ?target
+                BIND(owl:onDatatype AS ?nodeType)
+            }
+            UNION
+            {
+                ?id owl:withRestrictions ?restrictions .
+                ?restrictions rdf:rest*/rdf:first ?facet .
+                ?facet ?nodeType ?target .
+                FILTER(STRSTARTS(STR(?nodeType), STR(xsd:)))
+            }
+            }"#;
+
+/// Relations between properties.
+///
+/// Whether the hierarchy is drawn is decided by the serializer.
+/// https://www.w3.org/TR/owl2-syntax/#Object_Subproperties
+pub const PROPERTY_HIERARCHY: &str = r#"{
+            {
+                ?id rdfs:subPropertyOf ?target
+                BIND(rdfs:subPropertyOf AS ?nodeType)
+            }
+            UNION
+            {
+                ?id owl:equivalentProperty ?target
+                BIND(owl:equivalentProperty AS ?nodeType)
+            }
+            }"#;
+
+/// Property chains, e.g. `SubObjectPropertyOf(ObjectPropertyChain(:r :s) :t)`.
+///
+/// Unlike [`COLLECTIONS`], the cells of a chain are returned as `rdf:first`
+/// and `rdf:rest` rows, as the order of its properties matters.
+/// https://www.w3.org/TR/owl2-syntax/#Object_Subproperties
+pub const PROPERTY_CHAINS: &str = r#"{
+            {
+                ?id owl:propertyChainAxiom ?target
+                BIND(owl:propertyChainAxiom AS ?nodeType)
+            }
+            UNION
+            {
+                ?property owl:propertyChainAxiom ?chain .
+                ?chain rdf:rest* ?id .
+                ?id ?nodeType ?target .
+                FILTER(?nodeType IN (rdf:first, rdf:rest))
+            }
+            }"#;
+
+/// Disjoint properties, and n-ary disjointness and difference axioms.
+///
+/// The members of n-ary axioms are part of [`COLLECTIONS`].
+/// https://www.w3.org/TR/owl2-mapping-to-rdf/#Translation_of_Axioms_without_Annotations
+pub const DISJOINTNESS: &str = r#"{
+            {
+                ?id owl:propertyDisjointWith ?target
+                BIND(owl:propertyDisjointWith AS ?nodeType)
+            }
+            UNION
+            {
+                ?id a ?nodeType .
+                FILTER(?nodeType IN (
+                    owl:AllDisjointClasses,
+                    owl:AllDisjointProperties,
+                    owl:AllDifferent
+                ))
+            }
+            }"#;
+
+/// Annotations drawn by the optional annotation layer.
+///
+/// This includes declared annotation properties, annotations with IRI values,
+/// e.g. `rdfs:seeAlso` or `oboInOwl:hasDbXref`, and annotated axioms.
+/// Only queried with the annotation layer, see `Layers::annotations`.
+/// https://www.w3.org/TR/owl2-mapping-to-rdf/#Axioms_that_are_Translated_to_Multiple_Triples
+pub const ANNOTATIONS: &str = r#"{
+            {
+                ?id a owl:AnnotationProperty
+                BIND(owl:AnnotationProperty AS ?nodeType)
+            }
+            UNION
+            {
+                ?id ?nodeType ?target .
+                {
+                    ?nodeType a owl:AnnotationProperty
+                }
+                UNION
+                {
+                    VALUES ?nodeType { rdfs:seeAlso rdfs:isDefinedBy }
+                }
+                FILTER(isIRI(?target))
+            }
+            UNION
+            {
+                ?id a owl:Axiom ;
+                    ?nodeType ?target .
+                FILTER(?nodeType != rdf:type)
+            }
+            }"#;
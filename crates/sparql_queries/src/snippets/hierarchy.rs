@@ -0,0 +1,108 @@
+//! Provides SPARQL query snippets for the class hierarchy below or above a root class.
+
+use crate::builder::{InvalidIri, iri};
+
+/// The direction in which the class hierarchy is followed from the root.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Direction {
+    /// The subclasses of the root, their subclasses and so on.
+    #[default]
+    Descendants,
+    /// The superclasses of the root, their superclasses and so on.
+    Ancestors,
+}
+
+/// The deepest supported bounded depth, as every level lengthens the property path.
+///
+/// Deeper hierarchies can still be followed without a bound.
+pub const MAX_DEPTH: usize = 10;
+
+/// A root class and the part of the class hierarchy to draw from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hierarchy {
+    /// The IRI of the root class.
+    pub root: String,
+    pub direction: Direction,
+    /// The number of levels to follow, or `None` to follow the hierarchy to its end.
+    pub depth: Option<usize>,
+}
+
+impl Hierarchy {
+    /// The property path from the root to the classes in the hierarchy.
+    fn levels(&self) -> String {
+        let level = match self.direction {
+            Direction::Descendants => "^rdfs:subClassOf",
+            Direction::Ancestors => "rdfs:subClassOf",
+        };
+        match self.depth {
+            Some(depth) => vec![format!("{level}?"); depth.clamp(1, MAX_DEPTH)].join("/"),
+            None => format!("{level}*"),
+        }
+    }
+}
+
+/// Binds `?id` to the classes in `hierarchy`.
+///
+/// The properties whose domain or range is one of the classes are included,
+/// as are the set operators containing them and the ontology.
+///
+/// Returns an error if the IRI of the root isn't valid.
+pub fn hierarchy(hierarchy: &Hierarchy) -> Result<String, InvalidIri> {
+    Ok(format!(
+        r#"{{
+            SELECT DISTINCT ?id
+            WHERE {{
+                {{
+                    VALUES ?root {{ {} }}
+                    ?root {} ?class
+                    ?class (^rdfs:domain|^rdfs:range|^((owl:intersectionOf|owl:unionOf|owl:disjointUnionOf)/rdf:rest*/rdf:first))? ?id
+                }}
+                UNION
+                {{ ?id a owl:Ontology }}
+            }}
+            }}"#,
+        iri(&hierarchy.root)?,
+        hierarchy.levels()
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_hierarchy() {
+        let mut subtree = Hierarchy {
+            root: "http://example.com#A".to_string(),
+            direction: Direction::Descendants,
+            depth: Some(2),
+        };
+        assert!(hierarchy(&subtree).unwrap().contains(
+            "VALUES ?root { <http://example.com#A> }\n                    \
+             ?root ^rdfs:subClassOf?/^rdfs:subClassOf? ?class"
+        ));
+
+        subtree.depth = None;
+        assert!(
+            hierarchy(&subtree)
+                .unwrap()
+                .contains("?root ^rdfs:subClassOf* ?class")
+        );
+
+        let ancestors = Hierarchy {
+            direction: Direction::Ancestors,
+            depth: Some(MAX_DEPTH + 1),
+            ..subtree
+        };
+        assert!(hierarchy(&ancestors).unwrap().contains(&format!(
+            "?root {} ?class",
+            ["rdfs:subClassOf?"; MAX_DEPTH].join("/")
+        )));
+
+        let invalid = Hierarchy {
+            root: "http://example.com#A> } DROP ALL { <".to_string(),
+            ..ancestors
+        };
+        assert!(hierarchy(&invalid).is_err());
+    }
+}
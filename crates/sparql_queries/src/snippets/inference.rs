@@ -0,0 +1,21 @@
+//! Provides SPARQL query snippets for the consequences inferred by the reasoner.
+
+use const_format::concatcp;
+
+/// The named graph holding the inferred triples, separate from the asserted ones.
+pub const INFERRED_GRAPH: &str = "urn:vowlr:inferred";
+
+/// Inferred relations between classes, properties and individuals.
+///
+/// The rows match those of the asserted relations, and are flagged by binding `?inferred`.
+/// Inferred types are only returned for individuals, as classes and properties
+/// are declared by their asserted types.
+pub const INFERRED: &str = concatcp!(
+    r#"{
+            GRAPH <"#,
+    INFERRED_GRAPH,
+    r#"> { ?id ?nodeType ?target }
+            FILTER(?nodeType != rdf:type || EXISTS { ?id a owl:NamedIndividual })
+            BIND(true AS ?inferred)
+            }"#
+);
@@ -0,0 +1,165 @@
+//! Provides SPARQL query snippets for language-aware label selection.
+
+/// Find all language tags used by labels in the store.
+pub const LABEL_LANGUAGES: &str = r#"
+            SELECT DISTINCT ?lang
+            WHERE {
+                { ?id rdfs:label ?value }
+                UNION
+                { ?id skos:prefLabel ?value }
+                BIND(LCASE(LANG(?value)) AS ?lang)
+                FILTER(?lang != "")
+            }
+            ORDER BY ?lang
+        "#;
+
+/// Strip everything from a language tag which is not allowed by BCP 47.
+///
+/// This prevents user input from escaping the string literal it is placed in.
+fn sanitize_language(language: &str) -> String {
+    language
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+        .collect()
+}
+
+/// Return an `OPTIONAL` block binding `candidate` to one value of `property` per element,
+/// among the values matching `filter`.
+///
+/// The values are sampled in a subquery, so elements with several matching values
+/// still yield a single solution.
+fn one_label(property: &str, candidate: &str, filter: Option<String>) -> String {
+    let value = format!("{candidate}Value");
+    let filter = filter
+        .map(|filter| format!(" . FILTER({})", filter.replace("{value}", &value)))
+        .unwrap_or_default();
+    format!(
+        "OPTIONAL {{ SELECT ?id (SAMPLE({value}) AS {candidate}) \
+         WHERE {{ ?id {property} {value}{filter} }} GROUP BY ?id }}"
+    )
+}
+
+/// Append the `OPTIONAL` blocks for `property`, binding one variable per language.
+///
+/// Returns the bound variables in order of preference.
+fn label_candidates(
+    property: &str,
+    variable: &str,
+    languages: &[String],
+    blocks: &mut Vec<String>,
+) -> Vec<String> {
+    let mut candidates = Vec::new();
+    for (i, language) in languages.iter().enumerate() {
+        let candidate = format!("?{variable}{i}");
+        blocks.push(one_label(
+            property,
+            &candidate,
+            Some(format!(r#"langMatches(LANG({{value}}), "{language}")"#)),
+        ));
+        candidates.push(candidate);
+    }
+
+    let untagged = format!("?{variable}Untagged");
+    blocks.push(one_label(
+        property,
+        &untagged,
+        Some(r#"LANG({value}) = """#.to_string()),
+    ));
+    candidates.push(untagged);
+
+    // Without a preference, any label is better than none.
+    if languages.is_empty() {
+        let any = format!("?{variable}Any");
+        blocks.push(one_label(property, &any, None));
+        candidates.push(any);
+    }
+    candidates
+}
+
+/// Find labels for elements in the following order:
+/// 1. Use rdfs:label in each of the preferred `languages`, if exists.
+///    https://www.w3.org/TR/rdf-schema/#ch_label
+/// 2. Use an untagged rdfs:label, if exists.
+/// 3. Use skos:prefLabel, following the same order as rdfs:label.
+///    https://www.w3.org/TR/skos-reference/#prefLabel
+/// 4. Use rdf:resource, if exists.
+///    https://www.w3.org/TR/rdf-syntax-grammar/#section-Syntax-empty-property-elements
+/// 5. Use rdf:ID, if exists.
+///    https://www.w3.org/TR/rdf-syntax-grammar/#section-Syntax-ID-xml-base
+///
+/// An empty label is bound if none of the above exist.
+/// The serializer then falls back to the local name of the IRI.
+///
+/// Languages are matched using `langMatches`, i.e. `en` also matches `en-GB`.
+/// Each element gets a single label, even if it has several in the chosen language.
+pub fn label_selection(languages: &[String]) -> String {
+    let languages = languages
+        .iter()
+        .map(|language| sanitize_language(language))
+        .filter(|language| !language.is_empty())
+        .collect::<Vec<_>>();
+
+    let mut blocks = Vec::new();
+    let mut candidates = label_candidates("rdfs:label", "rdfsLabel", &languages, &mut blocks);
+    candidates.extend(label_candidates(
+        "skos:prefLabel",
+        "prefLabel",
+        &languages,
+        &mut blocks,
+    ));
+
+    blocks.push(one_label("rdf:resource", "?resLabel", None));
+    blocks.push(one_label("rdf:ID", "?idLabel", None));
+    candidates.push("?resLabel".to_string());
+    candidates.push("?idLabel".to_string());
+    candidates.push(r#""""#.to_string());
+
+    format!(
+        "{}\n                BIND(COALESCE({}) AS ?label)",
+        blocks.join("\n                "),
+        candidates.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_label_selection_order() {
+        let selection = label_selection(&["de".to_string(), "en".to_string()]);
+        let coalesce = selection
+            .split("COALESCE(")
+            .nth(1)
+            .expect("label selection should bind ?label using COALESCE");
+        assert!(coalesce.starts_with(
+            "?rdfsLabel0, ?rdfsLabel1, ?rdfsLabelUntagged, ?prefLabel0, ?prefLabel1, ?prefLabelUntagged"
+        ));
+        assert!(selection.contains(r#"langMatches(LANG(?rdfsLabel0Value), "de")"#));
+        assert!(!selection.contains("?rdfsLabelAny"));
+    }
+
+    #[test]
+    fn test_label_selection_samples_one_label() {
+        let selection = label_selection(&["en".to_string()]);
+        assert!(selection.contains(
+            "OPTIONAL { SELECT ?id (SAMPLE(?rdfsLabel0Value) AS ?rdfsLabel0) \
+             WHERE { ?id rdfs:label ?rdfsLabel0Value . \
+             FILTER(langMatches(LANG(?rdfsLabel0Value), \"en\")) } GROUP BY ?id }"
+        ));
+        assert!(selection.contains("SAMPLE(?idLabelValue) AS ?idLabel"));
+    }
+
+    #[test]
+    fn test_label_selection_without_languages() {
+        let selection = label_selection(&[]);
+        assert!(selection.contains("?rdfsLabelAny"));
+        assert!(selection.contains("?prefLabelAny"));
+    }
+
+    #[test]
+    fn test_label_selection_sanitizes_languages() {
+        let selection = label_selection(&[r#"en") } DROP ALL { ("#.to_string()]);
+        assert!(selection.contains(r#"langMatches(LANG(?rdfsLabel0Value), "enDROPALL")"#));
+    }
+}
@@ -0,0 +1,84 @@
+//! Provides SPARQL query snippets for the neighborhood of focus elements.
+
+use crate::builder::{InvalidIri, iri};
+
+/// A single hop between elements drawn next to each other.
+///
+/// Elements are next to each other through subclasses, equivalent and complement classes,
+/// the classes in a set operator, and the domain and range of a property.
+const HOP: &str = "(rdfs:subClassOf|^rdfs:subClassOf\
+    |owl:equivalentClass|^owl:equivalentClass\
+    |owl:complementOf|^owl:complementOf\
+    |(owl:intersectionOf|owl:unionOf|owl:disjointUnionOf)/rdf:rest*/rdf:first\
+    |^((owl:intersectionOf|owl:unionOf|owl:disjointUnionOf)/rdf:rest*/rdf:first)\
+    |^rdfs:domain/rdfs:range|^rdfs:range/rdfs:domain)";
+
+/// The largest supported radius, as every hop lengthens the property path.
+pub const MAX_RADIUS: usize = 5;
+
+/// The property path of at most `radius` hops.
+fn hops(radius: usize) -> String {
+    vec![format!("{HOP}?"); radius.clamp(1, MAX_RADIUS)].join("/")
+}
+
+/// Binds `?id` to the elements within reach of `foci`, each a tuple of its IRI and radius.
+///
+/// The properties whose domain or range is within reach are included,
+/// so the edges between the elements are drawn, as is the ontology.
+///
+/// Returns an error if the IRI of a focus isn't valid.
+pub fn neighborhood(foci: &[(String, usize)]) -> Result<String, InvalidIri> {
+    let mut radii = foci.iter().map(|(_, radius)| *radius).collect::<Vec<_>>();
+    radii.sort_unstable();
+    radii.dedup();
+    let groups = radii
+        .into_iter()
+        .map(|radius| {
+            let values = foci
+                .iter()
+                .filter(|(_, r)| *r == radius)
+                .map(|(focus, _)| iri(focus))
+                .collect::<Result<Vec<_>, _>>()?
+                .join(" ");
+            Ok(format!(
+                "{{ VALUES ?focus {{ {values} }} ?focus {}/(^rdfs:domain|^rdfs:range)? ?id }}",
+                hops(radius)
+            ))
+        })
+        .chain([Ok("{ ?id a owl:Ontology }".to_string())])
+        .collect::<Result<Vec<_>, _>>()?
+        .join("\n                UNION\n                ");
+    Ok(format!(
+        r#"{{
+            SELECT DISTINCT ?id
+            WHERE {{
+                {groups}
+            }}
+            }}"#
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_neighborhood() {
+        let neighborhood = neighborhood(&[
+            ("http://example.com#A".to_string(), 2),
+            ("http://example.com#B".to_string(), 1),
+            ("http://example.com#C".to_string(), 2),
+        ])
+        .unwrap();
+        assert!(neighborhood.contains(&format!(
+            "VALUES ?focus {{ <http://example.com#B> }} ?focus {HOP}?/(^rdfs:domain"
+        )));
+        assert!(neighborhood.contains(&format!(
+            "VALUES ?focus {{ <http://example.com#A> <http://example.com#C> }} ?focus {HOP}?/{HOP}?/("
+        )));
+        assert!(
+            super::neighborhood(&[("http://example.com#A> } DROP ALL { <".to_string(), 1)])
+                .is_err()
+        );
+    }
+}
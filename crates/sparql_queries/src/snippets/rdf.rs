@@ -0,0 +1,9 @@
+//! Provides SPARQL query snippets for visualizing plain RDF.
+
+/// Every triple of the store.
+///
+/// Labels are left out, as they are joined onto every result row instead.
+pub const TRIPLES: &str = r#"{
+            ?id ?nodeType ?target .
+            FILTER(?nodeType NOT IN (rdfs:label, skos:prefLabel))
+            }"#;
@@ -0,0 +1,49 @@
+//! Provides SPARQL query snippets for visualizing SKOS vocabularies.
+//!
+//! Inverse relations are normalized, so each relation is drawn once.
+//! https://www.w3.org/TR/skos-reference/
+
+/// Concepts and concept schemes.
+pub const CONCEPTS: &str = r#"{
+            ?id a ?target .
+            FILTER(?target IN (skos:Concept, skos:ConceptScheme))
+            BIND(rdf:type AS ?nodeType)
+            }"#;
+
+/// Hierarchical relations, with `skos:narrower` flipped into `skos:broader`.
+/// https://www.w3.org/TR/skos-reference/#semantic-relations
+pub const BROADER: &str = r#"{
+            { ?id skos:broader ?target }
+            UNION
+            { ?target skos:narrower ?id }
+            BIND(skos:broader AS ?nodeType)
+            }"#;
+
+/// Associative relations.
+pub const RELATED: &str = r#"{
+            ?id skos:related ?target .
+            BIND(skos:related AS ?nodeType)
+            }"#;
+
+/// Concepts and their schemes, with `skos:hasTopConcept` flipped into `skos:topConceptOf`.
+/// https://www.w3.org/TR/skos-reference/#schemes
+pub const SCHEMES: &str = r#"{
+            { ?id skos:inScheme ?target . BIND(skos:inScheme AS ?nodeType) }
+            UNION
+            { ?id skos:topConceptOf ?target . BIND(skos:topConceptOf AS ?nodeType) }
+            UNION
+            { ?target skos:hasTopConcept ?id . BIND(skos:topConceptOf AS ?nodeType) }
+            }"#;
+
+/// Mapping relations between concepts of different schemes.
+/// https://www.w3.org/TR/skos-reference/#mapping
+pub const MAPPINGS: &str = r#"{
+            ?id ?nodeType ?target .
+            FILTER(?nodeType IN (
+                skos:exactMatch,
+                skos:closeMatch,
+                skos:broadMatch,
+                skos:narrowMatch,
+                skos:relatedMatch
+            ))
+            }"#;
@@ -47,6 +47,27 @@ impl DataType {
             Self::UNKNOWN => "application/octet-stream",
         }
     }
+
+    /// Get the file extension of the data, as recognized by `From<&str>`.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::OWL => "owl",
+            Self::OFN => "ofn",
+            Self::OWX => "owx",
+            Self::TTL => "ttl",
+            Self::RDF => "rdf",
+            Self::NTriples => "nt",
+            Self::NQuads => "nq",
+            Self::TriG => "trig",
+            Self::JsonLd => "jsonld",
+            Self::N3 => "n3",
+            Self::SPARQLJSON => "srj",
+            Self::SPARQLXML => "srx",
+            Self::SPARQLCSV => "csv",
+            Self::SPARQLTSV => "tsv",
+            Self::UNKNOWN => "bin",
+        }
+    }
 }
 
 impl From<&Path> for DataType {
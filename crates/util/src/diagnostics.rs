@@ -0,0 +1,121 @@
+use rkyv::{Archive, Deserialize as RDeserialize, Serialize as RSerialize};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// Kinds of problems found by checking the consistency of the loaded ontologies.
+#[repr(C)]
+#[derive(
+    Archive,
+    RDeserialize,
+    RSerialize,
+    Deserialize,
+    Serialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+pub enum DiagnosticKind {
+    /// A class which can't have any instances,
+    /// e.g. because it's a subclass of disjoint classes.
+    UnsatisfiableClass,
+    /// Classes which are subclasses of each other through `rdfs:subClassOf`.
+    SubclassCycle,
+    /// A property which can't relate any individuals,
+    /// as its domains or ranges are disjoint.
+    DisjointDomainOrRange,
+}
+
+impl DiagnosticKind {
+    /// The characteristic highlighting the affected elements in the graph.
+    pub fn characteristic(&self) -> &'static str {
+        match self {
+            Self::UnsatisfiableClass => "unsatisfiable",
+            Self::SubclassCycle => "subclass cycle",
+            Self::DisjointDomainOrRange => "disjoint domain or range",
+        }
+    }
+}
+
+/// A problem found in the loaded ontologies.
+#[derive(Archive, RDeserialize, RSerialize, Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub kind: DiagnosticKind,
+    /// The affected elements, identified like in the serializer, e.g. `<http://example.com#A>`.
+    pub elements: Vec<String>,
+    /// A human readable description of the problem.
+    pub message: String,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// The OWL 2 profiles an ontology can be validated against.
+#[repr(C)]
+#[derive(
+    Archive,
+    RDeserialize,
+    RSerialize,
+    Deserialize,
+    Serialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+)]
+pub enum Profile {
+    /// OWL 2 EL, for large ontologies of existential restrictions.
+    El,
+    /// OWL 2 QL, for answering queries through relational databases.
+    Ql,
+    /// OWL 2 RL, for reasoning using rules.
+    Rl,
+    /// OWL 2 DL, the decidable fragment all other profiles are part of.
+    Dl,
+}
+
+impl Profile {
+    /// All profiles, in the order they should be presented to the user.
+    pub const ALL: [Profile; 4] = [Self::El, Self::Ql, Self::Rl, Self::Dl];
+
+    /// A short, human readable name of the profile.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::El => "OWL 2 EL",
+            Self::Ql => "OWL 2 QL",
+            Self::Rl => "OWL 2 RL",
+            Self::Dl => "OWL 2 DL",
+        }
+    }
+}
+
+/// An axiom which doesn't fit a profile.
+#[derive(Archive, RDeserialize, RSerialize, Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ProfileViolation {
+    pub profile: Profile,
+    /// The axiom in functional syntax, e.g. `SubClassOf(<http://example.com#A> ...)`.
+    pub axiom: String,
+    /// Why the axiom doesn't fit the profile.
+    pub reason: String,
+}
+
+impl Display for ProfileViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} is outside {}: {}",
+            self.axiom,
+            self.profile.name(),
+            self.reason
+        )
+    }
+}
@@ -0,0 +1,107 @@
+use rkyv::{Archive, Deserialize as RDeserialize, Serialize as RSerialize};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{Display, Formatter},
+};
+
+/// Kinds of elements the renderer has no element type for.
+///
+/// They're drawn as the closest element type, e.g. sub-property edges as subclass edges,
+/// and listed in [`GraphFlags::kinds`], so they can still be told apart, e.g. by the filter.
+#[repr(C)]
+#[derive(
+    Archive,
+    RDeserialize,
+    RSerialize,
+    Deserialize,
+    Serialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+pub enum ElementKind {
+    /// An `rdfs:subPropertyOf` edge of the property hierarchy, drawn as a subclass edge.
+    SubPropertyOf,
+    /// The node joining the members of an n-ary disjointness axiom, drawn as a resource.
+    DisjointnessHub,
+}
+
+impl ElementKind {
+    /// All kinds, in the order they should be presented to the user.
+    pub const ALL: [ElementKind; 2] = [Self::SubPropertyOf, Self::DisjointnessHub];
+
+    /// A short, human readable name of the kind.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::SubPropertyOf => "Subproperty of",
+            Self::DisjointnessHub => "Disjointness",
+        }
+    }
+}
+
+impl Display for ElementKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// What the renderer can't tell from the element types of a graph.
+///
+/// Elements are identified by their index in the graph, like its labels and characteristics.
+#[derive(
+    Archive, RDeserialize, RSerialize, Deserialize, Serialize, Debug, Clone, PartialEq, Default,
+)]
+pub struct GraphFlags {
+    /// The kind of each element drawn as the closest element type.
+    pub kinds: HashMap<usize, ElementKind>,
+    /// The edges drawn from inferred rather than asserted triples,
+    /// which the renderer may draw dashed.
+    pub inferred: HashSet<usize>,
+    /// The IRI of each node, so graphs queried separately can be merged.
+    pub iris: HashMap<usize, String>,
+    /// The IRI of the property each edge is drawn for, if any,
+    /// so edges can be filtered by their namespace.
+    pub properties: HashMap<usize, String>,
+}
+
+impl GraphFlags {
+    /// Returns the flags of the elements in `indices`,
+    /// each mapped from its index in the graph to its index in a new graph.
+    pub fn remap(&self, indices: &HashMap<usize, usize>) -> Self {
+        Self {
+            kinds: self
+                .kinds
+                .iter()
+                .filter_map(|(index, kind)| Some((*indices.get(index)?, *kind)))
+                .collect(),
+            inferred: self
+                .inferred
+                .iter()
+                .filter_map(|index| indices.get(index).copied())
+                .collect(),
+            iris: self
+                .iris
+                .iter()
+                .filter_map(|(index, iri)| Some((*indices.get(index)?, iri.clone())))
+                .collect(),
+            properties: self
+                .properties
+                .iter()
+                .filter_map(|(index, iri)| Some((*indices.get(index)?, iri.clone())))
+                .collect(),
+        }
+    }
+
+    /// Returns the number of elements of each kind.
+    pub fn counts(&self) -> HashMap<ElementKind, usize> {
+        let mut counts = HashMap::new();
+        for kind in self.kinds.values() {
+            *counts.entry(*kind).or_insert(0) += 1;
+        }
+        counts
+    }
+}
@@ -1 +1,6 @@
 pub mod datatypes;
+pub mod diagnostics;
+pub mod flags;
+pub mod limits;
+pub mod options;
+pub mod results;
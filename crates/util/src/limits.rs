@@ -0,0 +1,91 @@
+use rkyv::{Archive, Deserialize as RDeserialize, Serialize as RSerialize};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Bounds the resources a single query may use on the server.
+///
+/// Pathological queries, e.g. long `rdf:rest*/rdf:first` paths on huge files,
+/// would otherwise tie up the server indefinitely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct QueryLimits {
+    /// How long a query may run before it's cancelled.
+    pub timeout: Duration,
+    /// The maximum number of solutions serialized into the graph.
+    ///
+    /// The remaining solutions are not consumed, and the graph is marked as truncated.
+    pub max_solutions: usize,
+    /// The maximum number of rows returned to the SPARQL console.
+    ///
+    /// Rows are sent to the browser as is, so far fewer are returned than serialized.
+    pub max_rows: usize,
+}
+
+impl QueryLimits {
+    /// The time a query may run unless configured otherwise.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+    /// The number of solutions serialized unless configured otherwise.
+    pub const DEFAULT_MAX_SOLUTIONS: usize = 1_000_000;
+    /// The number of rows returned to the SPARQL console unless configured otherwise.
+    pub const DEFAULT_MAX_ROWS: usize = 10_000;
+}
+
+impl Default for QueryLimits {
+    fn default() -> Self {
+        Self {
+            timeout: Self::DEFAULT_TIMEOUT,
+            max_solutions: Self::DEFAULT_MAX_SOLUTIONS,
+            max_rows: Self::DEFAULT_MAX_ROWS,
+        }
+    }
+}
+
+/// How the query drawing a graph completed.
+#[derive(
+    Archive,
+    RDeserialize,
+    RSerialize,
+    Deserialize,
+    Serialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+)]
+pub struct QueryOutcome {
+    /// The number of solutions serialized into the graph.
+    pub solutions: usize,
+    /// Whether the query had more solutions than [`QueryLimits::max_solutions`],
+    /// so only part of the graph is drawn.
+    pub truncated: bool,
+    /// The number of individuals not drawn, as there were more than
+    /// [`SerializerOptions::individual_limit`].
+    ///
+    /// [`SerializerOptions::individual_limit`]: crate::options::SerializerOptions::individual_limit
+    pub hidden_individuals: usize,
+}
+
+/// Identifies a graph requested by a client.
+///
+/// The server cancels the requests of a client superseded by a newer one,
+/// e.g. when filters are toggled faster than the graph is drawn.
+#[derive(
+    Archive,
+    RDeserialize,
+    RSerialize,
+    Deserialize,
+    Serialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+pub struct GraphRequest {
+    /// A random identifier of the client, chosen when the page is loaded.
+    pub client: u64,
+    /// Increases with each graph requested by the client.
+    pub sequence: u64,
+}
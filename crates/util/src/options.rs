@@ -0,0 +1,219 @@
+use rkyv::{Archive, Deserialize as RDeserialize, Serialize as RSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Strategies for deriving a label from the IRI of an element without one.
+#[repr(C)]
+#[derive(
+    Archive,
+    RDeserialize,
+    RSerialize,
+    Deserialize,
+    Serialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+)]
+pub enum LabelFallback {
+    /// The fragment or last path segment of the IRI, e.g. `hasParent`.
+    #[default]
+    LocalName,
+    /// The local name split into words, e.g. `has Parent`.
+    SplitCamelCase,
+    /// The IRI as a prefixed name, e.g. `ex:hasParent`.
+    Curie,
+}
+
+impl LabelFallback {
+    /// All strategies, in the order they should be presented to the user.
+    pub const ALL: [LabelFallback; 3] = [Self::LocalName, Self::SplitCamelCase, Self::Curie];
+
+    /// A short, human readable name of the strategy.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::LocalName => "Local name",
+            Self::SplitCamelCase => "Split camelCase",
+            Self::Curie => "Prefixed name",
+        }
+    }
+}
+
+impl From<&str> for LabelFallback {
+    fn from(value: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|fallback| fallback.name() == value)
+            .unwrap_or_default()
+    }
+}
+
+/// How the loaded data is visualized.
+#[repr(C)]
+#[derive(
+    Archive,
+    RDeserialize,
+    RSerialize,
+    Deserialize,
+    Serialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+)]
+pub enum VisualizationMode {
+    /// Classes, properties and axioms drawn using the VOWL notation.
+    #[default]
+    Owl,
+    /// Every resource is a node and every triple an edge.
+    Rdf,
+    /// Concepts and concept schemes, linked by their semantic relations.
+    Skos,
+}
+
+impl VisualizationMode {
+    /// All modes, in the order they should be presented to the user.
+    pub const ALL: [VisualizationMode; 3] = [Self::Owl, Self::Rdf, Self::Skos];
+
+    /// A short, human readable name of the mode.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Owl => "OWL (VOWL)",
+            Self::Rdf => "Plain RDF",
+            Self::Skos => "SKOS",
+        }
+    }
+}
+
+impl From<&str> for VisualizationMode {
+    fn from(value: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.name() == value)
+            .unwrap_or_default()
+    }
+}
+
+/// Restricts which of the serialized elements are drawn.
+///
+/// The filter is applied to the serialized graph, so it sees the elements as they are drawn,
+/// e.g. whether they are external or deprecated and how many edges they have.
+/// It isn't part of the [`SerializerOptions`], so changing it doesn't serialize the graph again.
+#[derive(
+    Archive, RDeserialize, RSerialize, Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash,
+)]
+pub struct ElementFilter {
+    /// Namespaces whose named elements are drawn.
+    ///
+    /// Without any, named elements of all namespaces are drawn.
+    pub include_namespaces: Vec<String>,
+    /// Namespaces whose named elements are not drawn, even if included.
+    pub exclude_namespaces: Vec<String>,
+    /// Nodes with fewer edges are not drawn, e.g. `2` hides leaves.
+    pub min_degree: usize,
+    /// Nodes with more edges are not drawn, e.g. to hide hubs like `owl:Thing`.
+    pub max_degree: Option<usize>,
+    /// Whether to draw deprecated classes and properties.
+    pub deprecated: bool,
+    /// Whether to draw external classes and properties.
+    pub external: bool,
+    /// A regular expression the labels of named elements must match to be drawn.
+    pub label_pattern: Option<String>,
+}
+
+impl ElementFilter {
+    /// Whether the filter draws every element.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl Default for ElementFilter {
+    fn default() -> Self {
+        Self {
+            include_namespaces: Vec::new(),
+            exclude_namespaces: Vec::new(),
+            min_degree: 0,
+            max_degree: None,
+            deprecated: true,
+            external: true,
+            label_pattern: None,
+        }
+    }
+}
+
+/// Options controlling how query solutions are serialized into graph data.
+#[derive(
+    Archive, RDeserialize, RSerialize, Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash,
+)]
+pub struct SerializerOptions {
+    /// How the query solutions are interpreted.
+    ///
+    /// Must match the snippets the query was assembled from.
+    pub mode: VisualizationMode,
+    /// How to label elements which have no label in the ontology.
+    pub label_fallback: LabelFallback,
+    /// Namespaces whose elements are always drawn as external.
+    pub external_namespaces: Vec<String>,
+    /// Namespaces whose elements are never drawn as external.
+    ///
+    /// If a namespace is in both lists, the longest matching namespace wins.
+    pub internal_namespaces: Vec<String>,
+    /// Whether to draw individuals and the assertions about them.
+    pub individual_layer: bool,
+    /// The maximum number of individuals to draw.
+    ///
+    /// If an ontology has more individuals, the layer is turned off and none of them are drawn,
+    /// as large ABoxes would hide the structure of the ontology.
+    /// Their number is reported to the user, see [`QueryOutcome::hidden_individuals`].
+    ///
+    /// [`QueryOutcome::hidden_individuals`]: crate::limits::QueryOutcome::hidden_individuals
+    pub individual_limit: usize,
+    /// Whether to draw the property hierarchy.
+    ///
+    /// Properties are then also drawn as nodes,
+    /// linked by their `rdfs:subPropertyOf` relations.
+    pub property_hierarchy: bool,
+    /// Whether to draw annotations.
+    ///
+    /// Annotations with IRI values, e.g. `rdfs:seeAlso`, are then drawn as edges,
+    /// and annotations of axioms are attached to the edges they annotate.
+    pub annotation_layer: bool,
+    /// Whether to draw the consequences of RDFS and OWL 2 RL rules.
+    ///
+    /// The consequences are materialized into a separate graph before querying,
+    /// and the edges drawn from them are marked as inferred.
+    pub inference: bool,
+    /// Whether to highlight the elements affected by diagnostics,
+    /// e.g. unsatisfiable classes and subclass cycles.
+    ///
+    /// The asserted triples are only checked if this is enabled.
+    pub diagnostics: bool,
+}
+
+impl SerializerOptions {
+    /// The number of individuals drawn unless configured otherwise.
+    pub const DEFAULT_INDIVIDUAL_LIMIT: usize = 200;
+}
+
+impl Default for SerializerOptions {
+    fn default() -> Self {
+        Self {
+            mode: VisualizationMode::default(),
+            label_fallback: LabelFallback::default(),
+            external_namespaces: Vec::new(),
+            internal_namespaces: Vec::new(),
+            individual_layer: true,
+            individual_limit: Self::DEFAULT_INDIVIDUAL_LIMIT,
+            property_hierarchy: false,
+            annotation_layer: false,
+            inference: false,
+            diagnostics: false,
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use rkyv::{Archive, Deserialize as RDeserialize, Serialize as RSerialize};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// The datatype of simple literals, which is omitted when showing them.
+const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+/// The datatype of language-tagged literals, which is implied by their language.
+const RDF_LANG_STRING: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString";
+
+/// An RDF term bound in a query result.
+#[derive(
+    Archive, RDeserialize, RSerialize, Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash,
+)]
+pub enum ResultTerm {
+    Iri(String),
+    /// A blank node, by its identifier without the `_:` prefix.
+    BlankNode(String),
+    Literal {
+        value: String,
+        /// The IRI of the datatype.
+        datatype: String,
+        language: Option<String>,
+    },
+}
+
+impl ResultTerm {
+    /// The IRI, identifier or lexical value of the term, without any syntax.
+    pub fn value(&self) -> &str {
+        match self {
+            Self::Iri(value) | Self::BlankNode(value) | Self::Literal { value, .. } => value,
+        }
+    }
+}
+
+/// Shows the term in N-Triples syntax, e.g. `<http://example.com#A>` or `"A"@en`.
+impl Display for ResultTerm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Iri(iri) => write!(f, "<{iri}>"),
+            Self::BlankNode(id) => write!(f, "_:{id}"),
+            Self::Literal {
+                value,
+                datatype,
+                language,
+            } => {
+                write!(f, "\"")?;
+                for c in value.chars() {
+                    match c {
+                        '"' => write!(f, "\\\"")?,
+                        '\\' => write!(f, "\\\\")?,
+                        '\n' => write!(f, "\\n")?,
+                        '\r' => write!(f, "\\r")?,
+                        '\t' => write!(f, "\\t")?,
+                        c => write!(f, "{c}")?,
+                    }
+                }
+                write!(f, "\"")?;
+                match language {
+                    Some(language) => write!(f, "@{language}"),
+                    None if datatype == XSD_STRING || datatype == RDF_LANG_STRING => Ok(()),
+                    None => write!(f, "^^<{datatype}>"),
+                }
+            }
+        }
+    }
+}
+
+/// The formats query results can be written in.
+///
+/// The results are written on the server, see `VOWLRStore::write_results`.
+#[derive(
+    Archive, RDeserialize, RSerialize, Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq,
+)]
+pub enum ResultFormat {
+    /// SPARQL 1.1 Query Results JSON Format.
+    Json,
+    /// SPARQL Query Results XML Format.
+    Xml,
+    /// SPARQL 1.1 Query Results CSV Format, which drops the datatypes and languages.
+    Csv,
+    /// SPARQL 1.1 Query Results TSV Format.
+    Tsv,
+    /// Turtle, for the triples of `CONSTRUCT` and `DESCRIBE` queries.
+    Turtle,
+    /// N-Triples, for the triples of `CONSTRUCT` and `DESCRIBE` queries.
+    NTriples,
+}
+
+impl ResultFormat {
+    /// The formats of solutions, in the order they should be presented to the user.
+    pub const SOLUTIONS: [ResultFormat; 4] = [Self::Json, Self::Xml, Self::Csv, Self::Tsv];
+    /// The formats of triples, in the order they should be presented to the user.
+    pub const GRAPHS: [ResultFormat; 2] = [Self::Turtle, Self::NTriples];
+    /// The formats of booleans, in the order they should be presented to the user.
+    pub const BOOLEANS: [ResultFormat; 2] = [Self::Json, Self::Xml];
+
+    /// A short, human readable name of the format.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Json => "JSON",
+            Self::Xml => "XML",
+            Self::Csv => "CSV",
+            Self::Tsv => "TSV",
+            Self::Turtle => "Turtle",
+            Self::NTriples => "N-Triples",
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Self::Json => "application/sparql-results+json",
+            Self::Xml => "application/sparql-results+xml",
+            Self::Csv => "text/csv",
+            Self::Tsv => "text/tab-separated-values",
+            Self::Turtle => "text/turtle",
+            Self::NTriples => "application/n-triples",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Json => "srj",
+            Self::Xml => "srx",
+            Self::Csv => "csv",
+            Self::Tsv => "tsv",
+            Self::Turtle => "ttl",
+            Self::NTriples => "nt",
+        }
+    }
+}
+
+/// The solutions of a query, as a table of terms.
+#[derive(
+    Archive, RDeserialize, RSerialize, Deserialize, Serialize, Debug, Clone, PartialEq, Default,
+)]
+pub struct SolutionTable {
+    /// The names of the variables, without the `?`.
+    pub variables: Vec<String>,
+    /// The bindings of each solution, in the order of [`SolutionTable::variables`].
+    pub rows: Vec<Vec<Option<ResultTerm>>>,
+    /// Whether the query had more solutions than were returned.
+    pub truncated: bool,
+}
+
+/// The results of a query of any form.
+#[derive(Archive, RDeserialize, RSerialize, Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub enum SparqlResults {
+    /// The solutions of a `SELECT` query.
+    Solutions(SolutionTable),
+    /// The answer to an `ASK` query.
+    Boolean(bool),
+    /// The triples of a `CONSTRUCT` or `DESCRIBE` query,
+    /// bound to the variables `subject`, `predicate` and `object`.
+    Graph(SolutionTable),
+}
+
+impl SparqlResults {
+    /// The formats the results can be written in, the default first.
+    pub fn formats(&self) -> &'static [ResultFormat] {
+        match self {
+            Self::Solutions(_) => &ResultFormat::SOLUTIONS,
+            Self::Boolean(_) => &ResultFormat::BOOLEANS,
+            Self::Graph(_) => &ResultFormat::GRAPHS,
+        }
+    }
+
+    /// Whether the query had more solutions or triples than were returned.
+    pub fn truncated(&self) -> bool {
+        match self {
+            Self::Solutions(table) | Self::Graph(table) => table.truncated,
+            Self::Boolean(_) => false,
+        }
+    }
+
+    /// The solutions or triples of the results, if they aren't a boolean.
+    pub fn table(&self) -> Option<&SolutionTable> {
+        match self {
+            Self::Solutions(table) | Self::Graph(table) => Some(table),
+            Self::Boolean(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            ResultTerm::Iri("http://example.com#A".to_string()).to_string(),
+            "<http://example.com#A>"
+        );
+        assert_eq!(ResultTerm::BlankNode("b0".to_string()).to_string(), "_:b0");
+        assert_eq!(
+            ResultTerm::Literal {
+                value: "Say \"hi\",\tthen".to_string(),
+                datatype: RDF_LANG_STRING.to_string(),
+                language: Some("en".to_string()),
+            }
+            .to_string(),
+            "\"Say \\\"hi\\\",\\tthen\"@en"
+        );
+        assert_eq!(
+            ResultTerm::Literal {
+                value: "1".to_string(),
+                datatype: "http://www.w3.org/2001/XMLSchema#integer".to_string(),
+                language: None,
+            }
+            .to_string(),
+            "\"1\"^^<http://www.w3.org/2001/XMLSchema#integer>"
+        );
+        assert_eq!(
+            ResultTerm::Literal {
+                value: "A".to_string(),
+                datatype: XSD_STRING.to_string(),
+                language: None,
+            }
+            .to_string(),
+            "\"A\""
+        );
+    }
+}
@@ -1,5 +1,9 @@
+use crate::blocks::workbench::GraphDataContext;
 use crate::components::buttons::graph_interaction_buttons::GraphInteractionButtons;
 use leptos::prelude::*;
+#[cfg(feature = "server")]
+use vowlr_database::store::VOWLRStore;
+use vowlr_util::diagnostics::{Diagnostic, Profile, ProfileViolation};
 
 #[component]
 pub fn Accordion(#[prop(into)] title: String, children: Children) -> impl IntoView {
@@ -71,26 +75,198 @@ pub fn Author() -> impl IntoView {
     }
 }
 
+#[server]
+pub async fn label_languages() -> Result<Vec<String>, ServerFnError> {
+    let store = VOWLRStore::default();
+    Ok(store.label_languages().await?)
+}
+
 #[component]
 pub fn Language() -> impl IntoView {
-    let ontologylanguages = RwSignal::new(vec![
-        "english".to_string(),
-        "german".to_string(),
-        "french".to_string(),
-    ]);
+    let graph_context = expect_context::<GraphDataContext>();
+    let GraphDataContext {
+        total_graph_data,
+        label_languages: preferred_languages,
+        ..
+    } = graph_context;
+
+    // Refetch the available languages whenever a new graph is loaded.
+    let ontologylanguages = LocalResource::new(move || {
+        total_graph_data.track();
+        label_languages()
+    });
+
+    // Only query the labels again if the preference actually changed.
+    let set_preference = move |languages: Vec<String>| {
+        if preferred_languages.with_untracked(|preferred| *preferred != languages) {
+            preferred_languages.set(languages);
+            graph_context.relabel();
+        }
+    };
+    let prefer = move |language: String| {
+        let mut languages = preferred_languages.get_untracked();
+        if !language.is_empty() && !languages.contains(&language) {
+            languages.push(language);
+        }
+        set_preference(languages);
+    };
+    let unprefer = move |language: &str| {
+        let mut languages = preferred_languages.get_untracked();
+        languages.retain(|preferred| preferred != language);
+        set_preference(languages);
+    };
+
     view! {
-        <p class="flex items-center justify-center gap-2 py-2 text-sm my-2 text-gray-500">
-            "Language(s):"
-            <select class="py-1 px-2 text-sm rounded-md border border-gray-300 focus:ring-2 focus:ring-blue-500 focus:outline-none text-gray-500 w-[100px] h-[30px]">
+        <div class="flex flex-col items-center gap-2 py-2 text-sm my-2 text-gray-500">
+            <p class="flex items-center justify-center gap-2">
+                "Language(s):"
+                <select
+                    class="py-1 px-2 text-sm rounded-md border border-gray-300 focus:ring-2 focus:ring-blue-500 focus:outline-none text-gray-500 w-[100px] h-[30px]"
+                    prop:value=""
+                    on:change=move |ev| prefer(event_target_value(&ev))
+                >
+                    <option value="">"add..."</option>
+                    {move || {
+                        ontologylanguages
+                            .get()
+                            .and_then(|languages| languages.ok())
+                            .unwrap_or_default()
+                            .into_iter()
+                            .filter(|lang| {
+                                !preferred_languages.with(|preferred| preferred.contains(lang))
+                            })
+                            .map(|lang| view! { <option value=lang.clone()>{lang}</option> })
+                            .collect_view()
+                    }}
+                </select>
+            </p>
+            // The preferred languages, most preferred first. Without any, any label is used.
+            <ol class="flex flex-wrap justify-center gap-1">
                 {move || {
-                    ontologylanguages
-                        .get()
+                    let languages = preferred_languages.get();
+                    if languages.is_empty() {
+                        return view! { <li>"any"</li> }.into_any();
+                    }
+                    languages
                         .into_iter()
-                        .map(|lang| view! { <option>{lang}</option> })
+                        .enumerate()
+                        .map(|(i, lang)| {
+                            let remove = lang.clone();
+                            view! {
+                                <li class="flex items-center gap-1 py-0.5 px-2 rounded-md border border-gray-300">
+                                    {format!("{}. {lang}", i + 1)}
+                                    <button
+                                        class="hover:text-red-600"
+                                        title="Remove"
+                                        on:click=move |_| unprefer(&remove)
+                                    >
+                                        "×"
+                                    </button>
+                                </li>
+                            }
+                        })
                         .collect_view()
+                        .into_any()
                 }}
-            </select>
-        </p>
+            </ol>
+        </div>
+    }
+}
+
+#[server]
+pub async fn ontology_diagnostics() -> Result<Vec<Diagnostic>, ServerFnError> {
+    let store = VOWLRStore::default();
+    Ok(store.diagnostics().await?)
+}
+
+#[server]
+pub async fn profile_violations() -> Result<Vec<ProfileViolation>, ServerFnError> {
+    let store = VOWLRStore::default();
+    Ok(store.profile_violations().await?)
+}
+
+#[component]
+pub fn Diagnostics() -> impl IntoView {
+    let GraphDataContext {
+        total_graph_data, ..
+    } = expect_context::<GraphDataContext>();
+
+    // Recheck the loaded ontologies whenever a new graph is loaded.
+    let diagnostics = LocalResource::new(move || {
+        total_graph_data.track();
+        ontology_diagnostics()
+    });
+    let violations = LocalResource::new(move || {
+        total_graph_data.track();
+        profile_violations()
+    });
+
+    view! {
+        <Accordion title="Diagnostics">
+            {move || match diagnostics.get() {
+                None => view! { <p>"Checking..."</p> }.into_any(),
+                Some(Err(e)) => {
+                    view! {
+                        <p class="text-sm text-red-600 break-all">
+                            {format!("Failed to check the ontologies: {e}")}
+                        </p>
+                    }
+                        .into_any()
+                }
+                Some(Ok(diagnostics)) if diagnostics.is_empty() => {
+                    view! { <p>"No problems found."</p> }.into_any()
+                }
+                Some(Ok(diagnostics)) => {
+                    view! {
+                        <ul class="list-disc pl-4 text-sm">
+                            {diagnostics
+                                .into_iter()
+                                .map(|diagnostic| view! { <li>{diagnostic.message}</li> })
+                                .collect_view()}
+                        </ul>
+                    }
+                        .into_any()
+                }
+            }}
+            {move || {
+                let violations = match violations.get() {
+                    None => return view! { <p class="mt-2 text-sm">"Validating..."</p> }.into_any(),
+                    Some(Err(e)) => {
+                        return view! {
+                            <p class="mt-2 text-sm text-red-600 break-all">
+                                {format!("Failed to validate the profiles: {e}")}
+                            </p>
+                        }
+                            .into_any();
+                    }
+                    Some(Ok(violations)) => violations,
+                };
+                Profile::ALL
+                    .into_iter()
+                    .map(|profile| {
+                        let violations = violations
+                            .iter()
+                            .filter(|violation| violation.profile == profile)
+                            .map(|violation| {
+                                view! {
+                                    <li>{format!("{}: {}", violation.axiom, violation.reason)}</li>
+                                }
+                            })
+                            .collect::<Vec<_>>();
+                        let summary = if violations.is_empty() {
+                            format!("{}: no violations", profile.name())
+                        } else {
+                            format!("{}: {} violations", profile.name(), violations.len())
+                        };
+                        view! {
+                            <p class="mt-2 text-sm font-medium">{summary}</p>
+                            <ul class="list-disc pl-4 text-sm break-all">{violations}</ul>
+                        }
+                    })
+                    .collect_view()
+                    .into_any()
+            }}
+        </Accordion>
     }
 }
 
@@ -158,6 +334,7 @@ pub fn RightSidebar() -> impl IntoView {
                     <Language />
                     <Description />
                     <MetaData />
+                    <Diagnostics />
                     <SelectionDetails />
                 </div>
             </div>
@@ -1,24 +1,531 @@
 mod about_menu;
+mod explore_menu;
 mod export_menu;
 mod filter_menu;
 mod ontology_menu;
 mod options_menu;
+mod sparql_console;
 // mod search_menu;1
 use crate::components::lists::{ListDetails, ListElement};
 use crate::components::menu::vertical_menu::VerticalMenu;
+use crate::components::user_input::file_upload::{handle_filtered_graph, handle_internal_sparql};
 use about_menu::AboutMenu;
+use explore_menu::ExploreMenu;
 use export_menu::ExportMenu;
 use filter_menu::FilterMenu;
-use grapher::prelude::GraphDisplayData;
+use futures::StreamExt;
+use futures::future::{AbortHandle, Abortable};
+use grapher::prelude::{EVENT_DISPATCHER, ElementType, GraphDisplayData, RenderEvent};
 use leptos::prelude::*;
+use leptos::server_fn::codec::{StreamingText, TextStream};
+use leptos::task::spawn_local;
+use log::{error, info};
 use ontology_menu::OntologyMenu;
-use options_menu::OptionsMenu;
+use options_menu::{OptionsMenu, SerializerOptionsMenu};
+use sparql_console::SparqlConsoleMenu;
+use std::collections::HashMap;
+use std::time::Duration;
+#[cfg(feature = "server")]
+use vowlr_database::store::VOWLRStore;
+use vowlr_sparql_queries::prelude::{Hierarchy, InvalidIri, Layers, QueryAssembler, SelectQuery};
+use vowlr_util::{
+    flags::{ElementKind, GraphFlags},
+    limits::{GraphRequest, QueryOutcome},
+    options::{ElementFilter, SerializerOptions, VisualizationMode},
+};
 // use search_menu::SearchMenu;
 
-#[derive(Clone)]
+/// The time waited before reconnecting to the changes of the loaded documents at first.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+/// The longest time waited before reconnecting to the changes of the loaded documents.
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+/// Streams the generations of the loaded documents as they change, one per line.
+///
+/// See `VOWLRStore::changes`.
+#[server(output = StreamingText)]
+pub async fn store_changes() -> Result<TextStream, ServerFnError> {
+    let changes = VOWLRStore::default().changes();
+    Ok(TextStream::new(
+        changes.map(|generation| Ok(format!("{generation}\n"))),
+    ))
+}
+
+/// Returns the generation of the loaded documents.
+///
+/// See `VOWLRStore::generation`.
+#[server]
+pub async fn store_generation() -> Result<u64, ServerFnError> {
+    Ok(VOWLRStore::default().generation())
+}
+
+/// Waits for `duration`.
+async fn sleep(duration: Duration) {
+    let (done, waited) = futures::channel::oneshot::channel();
+    set_timeout(
+        move || {
+            let _ = done.send(());
+        },
+        duration,
+    );
+    let _ = waited.await;
+}
+
+/// The graph request preceding the first one of this client, with a random identifier.
+///
+/// Without an identifier, the requests of this client are never cancelled on the server.
+pub fn first_graph_request() -> Option<GraphRequest> {
+    match getrandom::u64() {
+        Ok(client) => Some(GraphRequest {
+            client,
+            sequence: 0,
+        }),
+        Err(e) => {
+            error!("Failed to identify this client: {}", e);
+            None
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
 pub struct GraphDataContext {
     pub graph_data: RwSignal<GraphDisplayData>,
     pub total_graph_data: RwSignal<GraphDisplayData>,
+    /// What the renderer can't tell from the element types of the displayed graph.
+    pub graph_flags: RwSignal<GraphFlags>,
+    /// What the renderer can't tell from the element types of the total graph.
+    pub total_graph_flags: RwSignal<GraphFlags>,
+    /// Preferred label languages, most preferred first.
+    pub label_languages: RwSignal<Vec<String>>,
+    /// The element types hidden by the filter.
+    pub hidden_elements: RwSignal<Vec<ElementType>>,
+    /// The element kinds hidden by the filter, see [`GraphFlags::kinds`].
+    pub hidden_kinds: RwSignal<Vec<ElementKind>>,
+    /// Options passed to the serializer on every query.
+    pub serializer_options: RwSignal<SerializerOptions>,
+    /// Which of the serialized elements are drawn, applied to the graph cached on the server.
+    pub element_filter: RwSignal<ElementFilter>,
+    /// The elements the graph is explored from, each a tuple of its IRI and radius.
+    ///
+    /// Without any, the complete graph is drawn.
+    pub focus: RwSignal<Vec<(String, usize)>>,
+    /// The part of the class hierarchy the graph is restricted to, if any.
+    pub hierarchy: RwSignal<Option<Hierarchy>>,
+    /// How the query drawing the graph completed, e.g. whether the graph is truncated.
+    pub query_outcome: RwSignal<QueryOutcome>,
+    /// Aborts the graph request still running, if any.
+    pub pending_request: StoredValue<Option<AbortHandle>>,
+    /// The latest graph request of this client, see [`first_graph_request`].
+    pub latest_request: StoredValue<Option<GraphRequest>>,
+    /// The number of requests of this client changing the loaded documents still running.
+    pub pending_writes: StoredValue<usize>,
+    /// The generation of the loaded documents after the latest change by this client.
+    ///
+    /// Changes up to it are drawn by the graph reloaded after the change,
+    /// so [`GraphDataContext::follow_store_changes`] skips them.
+    pub written_generation: StoredValue<u64>,
+}
+
+impl GraphDataContext {
+    /// Restricts `query` to the neighborhood of the focus and the selected class hierarchy.
+    ///
+    /// Returns an error if the IRI of a focus or the hierarchy root isn't valid.
+    pub fn scope(&self, mut query: SelectQuery) -> Result<SelectQuery, InvalidIri> {
+        let focus = self.focus.get_untracked();
+        if !focus.is_empty() {
+            query = query.pattern(QueryAssembler::neighborhood(&focus)?);
+        }
+        if let Some(hierarchy) = self.hierarchy.get_untracked() {
+            query = query.pattern(QueryAssembler::hierarchy(&hierarchy)?);
+        }
+        Ok(query)
+    }
+
+    /// The query drawing the graph in the current visualization mode.
+    ///
+    /// In the OWL mode, the graph is restricted by [`GraphDataContext::scope`].
+    pub fn graph_query(&self) -> Result<String, InvalidIri> {
+        let languages = self.label_languages.get_untracked();
+        let options = self.serializer_options.get_untracked();
+        match options.mode {
+            VisualizationMode::Owl => self
+                .scope(QueryAssembler::default_query(&languages, layers(&options)))
+                .map(|query| query.build()),
+            VisualizationMode::Rdf => Ok(QueryAssembler::assemble_rdf_query(&languages)),
+            VisualizationMode::Skos => Ok(QueryAssembler::assemble_skos_query(&languages)),
+        }
+    }
+
+    /// The query drawing the elements next to `iri`, restricted to the selected class hierarchy.
+    ///
+    /// See [`GraphDataContext::expand`].
+    fn ring_query(&self, iri: &str) -> Result<String, InvalidIri> {
+        let languages = self.label_languages.get_untracked();
+        let options = self.serializer_options.get_untracked();
+        let mut query = QueryAssembler::neighborhood_query(
+            &[(iri.to_string(), 1)],
+            &languages,
+            layers(&options),
+        )?;
+        if let Some(hierarchy) = self.hierarchy.get_untracked() {
+            query = query.pattern(QueryAssembler::hierarchy(&hierarchy)?);
+        }
+        Ok(query.build())
+    }
+
+    /// Spawns the graph request made by `request`, cancelling the one still running, if any.
+    ///
+    /// `request` is passed the next graph request of this client, which cancels the previous
+    /// one on the server too, see `VOWLRStore::for_request`. Only the latest graph is drawn.
+    pub fn spawn_graph_request<F>(&self, request: impl FnOnce(Option<GraphRequest>) -> F)
+    where
+        F: Future<Output = ()> + 'static,
+    {
+        self.latest_request.update_value(|latest| {
+            if let Some(latest) = latest {
+                latest.sequence += 1;
+            }
+        });
+        let request = request(self.latest_request.get_value());
+        let (handle, registration) = AbortHandle::new_pair();
+        self.pending_request.update_value(|pending| {
+            if let Some(previous) = pending.replace(handle) {
+                previous.abort();
+            }
+        });
+        spawn_local(async move {
+            let _ = Abortable::new(request, registration).await;
+        });
+    }
+
+    /// Query the graph using the current label languages and serializer options.
+    ///
+    /// The result replaces both the displayed and the total graph.
+    pub fn reload_graph(&self) {
+        let GraphDataContext {
+            graph_data,
+            total_graph_data,
+            graph_flags,
+            total_graph_flags,
+            serializer_options,
+            element_filter,
+            query_outcome,
+            ..
+        } = *self;
+        let query = match self.graph_query() {
+            Ok(query) => query,
+            Err(e) => {
+                error!("{}", e);
+                return;
+            }
+        };
+        let options = serializer_options.get_untracked();
+        let filter = element_filter.get_untracked();
+        self.spawn_graph_request(|request| async move {
+            match handle_internal_sparql(query, options, filter, request).await {
+                Ok((new_graph_data, flags, outcome)) => {
+                    query_outcome.set(outcome);
+                    graph_flags.set(flags.clone());
+                    total_graph_flags.set(flags);
+                    graph_data.set(new_graph_data.clone());
+                    total_graph_data.set(new_graph_data.clone());
+                    let _ = EVENT_DISPATCHER
+                        .rend_write_chan
+                        .send(RenderEvent::LoadGraph(new_graph_data));
+                }
+                Err(e) => error!("{}", e),
+            }
+        });
+    }
+
+    /// Query the graph again using the current label languages, keeping the filter.
+    ///
+    /// Only the displayed graph is replaced, as the elements of the total graph don't change.
+    pub fn relabel(&self) {
+        let GraphDataContext {
+            graph_data,
+            graph_flags,
+            serializer_options,
+            element_filter,
+            hidden_elements,
+            hidden_kinds,
+            query_outcome,
+            ..
+        } = *self;
+        let query = match self.graph_query() {
+            Ok(query) => query,
+            Err(e) => {
+                error!("{}", e);
+                return;
+            }
+        };
+        let options = serializer_options.get_untracked();
+        let filter = element_filter.get_untracked();
+        let hidden = hidden_elements.get_untracked();
+        let kinds = hidden_kinds.get_untracked();
+        self.spawn_graph_request(|request| async move {
+            match handle_filtered_graph(query, options, filter, hidden, kinds, request).await {
+                Ok((new_graph_data, flags, outcome)) => {
+                    query_outcome.set(outcome);
+                    graph_flags.set(flags);
+                    graph_data.set(new_graph_data.clone());
+                    let _ = EVENT_DISPATCHER
+                        .rend_write_chan
+                        .send(RenderEvent::LoadGraph(new_graph_data));
+                }
+                Err(e) => error!("{}", e),
+            }
+        });
+    }
+
+    /// Adds the elements next to `iri` to the explored neighborhood.
+    ///
+    /// Only the elements next to `iri` are queried, and merged into the total graph,
+    /// which replaces the displayed graph like [`GraphDataContext::reload_graph`].
+    /// Outside the OWL mode, the graph isn't explored, so it's reloaded instead.
+    pub fn expand(&self, iri: String) {
+        let GraphDataContext {
+            graph_data,
+            total_graph_data,
+            graph_flags,
+            total_graph_flags,
+            serializer_options,
+            element_filter,
+            focus,
+            query_outcome,
+            ..
+        } = *self;
+        if focus.with_untracked(|focus| focus.iter().any(|(focus, _)| *focus == iri)) {
+            return;
+        }
+        focus.update(|focus| focus.push((iri.clone(), 1)));
+        let options = serializer_options.get_untracked();
+        if options.mode != VisualizationMode::Owl {
+            self.reload_graph();
+            return;
+        }
+        let query = match self.ring_query(&iri) {
+            Ok(query) => query,
+            Err(e) => {
+                error!("{}", e);
+                return;
+            }
+        };
+        let filter = element_filter.get_untracked();
+        // Not a graph request, as cancelling it would leave the elements out of the graph.
+        // A graph drawn meanwhile already contains them, which the merge preserves.
+        spawn_local(async move {
+            match handle_internal_sparql(query, options, filter, None).await {
+                Ok((ring, ring_flags, outcome)) => {
+                    let (new_graph_data, flags) = merge_graph(
+                        &total_graph_data.get_untracked(),
+                        &total_graph_flags.get_untracked(),
+                        ring,
+                        &ring_flags,
+                    );
+                    query_outcome.update(|current| {
+                        current.solutions += outcome.solutions;
+                        current.truncated |= outcome.truncated;
+                        current.hidden_individuals =
+                            current.hidden_individuals.max(outcome.hidden_individuals);
+                    });
+                    graph_flags.set(flags.clone());
+                    total_graph_flags.set(flags);
+                    graph_data.set(new_graph_data.clone());
+                    total_graph_data.set(new_graph_data.clone());
+                    let _ = EVENT_DISPATCHER
+                        .rend_write_chan
+                        .send(RenderEvent::LoadGraph(new_graph_data));
+                }
+                Err(e) => error!("{}", e),
+            }
+        });
+    }
+
+    /// Runs `write`, a request of this client changing the loaded documents.
+    ///
+    /// The caller reloads the graph afterwards, so the changes made until then,
+    /// including those by other clients, are skipped by [`GraphDataContext::follow_store_changes`].
+    pub async fn write<T>(self, write: impl Future<Output = T>) -> T {
+        self.pending_writes.update_value(|writes| *writes += 1);
+        let result = write.await;
+        match store_generation().await {
+            Ok(generation) => self
+                .written_generation
+                .update_value(|written| *written = (*written).max(generation)),
+            Err(e) => error!("{}", e),
+        }
+        self.pending_writes.update_value(|writes| *writes -= 1);
+        result
+    }
+
+    /// Reloads the graph whenever the loaded documents change,
+    /// e.g. when they're edited through the SPARQL endpoint by another client.
+    ///
+    /// Changes made by this client are skipped, see [`GraphDataContext::write`].
+    /// The connection is reestablished with increasing delays, reloading the graph
+    /// if the documents changed while disconnected.
+    pub fn follow_store_changes(&self) {
+        let context = *self;
+        spawn_local(async move {
+            let mut delay = RECONNECT_DELAY;
+            let mut seen = None;
+            loop {
+                match store_generation().await {
+                    Ok(generation) => {
+                        if seen.is_some_and(|seen| seen < generation) {
+                            info!("The loaded documents changed while disconnected");
+                            context.follow_change(generation);
+                        }
+                        seen = Some(generation);
+                    }
+                    Err(e) => error!("{}", e),
+                }
+                match store_changes().await {
+                    Ok(changes) => {
+                        let mut changes = changes.into_inner();
+                        // Changes may arrive batched, so only the latest of each batch counts.
+                        while let Some(change) = changes.next().await {
+                            let generation = change.map(|change| {
+                                change.lines().filter_map(|line| line.parse().ok()).max()
+                            });
+                            match generation {
+                                Ok(Some(generation)) => {
+                                    seen = Some(generation);
+                                    delay = RECONNECT_DELAY;
+                                    context.follow_change(generation);
+                                }
+                                Ok(None) => {}
+                                Err(e) => {
+                                    error!("{}", e);
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => error!("{}", e),
+                }
+                info!("Reconnecting to the changes of the loaded documents in {delay:?}");
+                sleep(delay).await;
+                delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+            }
+        });
+    }
+
+    /// Reloads the graph as the loaded documents moved on to `generation`,
+    /// unless this client changed them, see [`GraphDataContext::write`].
+    fn follow_change(&self, generation: u64) {
+        if self.pending_writes.get_value() > 0 || generation <= self.written_generation.get_value()
+        {
+            return;
+        }
+        info!("The loaded documents changed, reloading the graph");
+        self.reload_graph();
+    }
+}
+
+/// The layers of the default query enabled by `options`.
+fn layers(options: &SerializerOptions) -> Layers {
+    Layers {
+        individuals: options.individual_layer,
+        annotations: options.annotation_layer,
+        inference: options.inference,
+    }
+}
+
+/// Merges `ring` into `base`, adding the nodes and edges of `ring` which aren't in `base` yet.
+///
+/// Nodes are told apart by their IRI, see [`GraphFlags::iris`],
+/// and edges by their type, their label and the nodes they connect.
+fn merge_graph(
+    base: &GraphDisplayData,
+    base_flags: &GraphFlags,
+    ring: GraphDisplayData,
+    ring_flags: &GraphFlags,
+) -> (GraphDisplayData, GraphFlags) {
+    let mut data = base.clone();
+    let mut flags = base_flags.clone();
+    let mut nodes = flags
+        .iris
+        .iter()
+        .map(|(index, iri)| (iri.clone(), *index))
+        .collect::<HashMap<_, _>>();
+    let mut edges = data
+        .edges
+        .iter()
+        .map(|[subject, edge, object]| {
+            let key = (
+                *subject,
+                data.elements[*edge],
+                data.labels[*edge].clone(),
+                *object,
+            );
+            (key, *edge)
+        })
+        .collect::<HashMap<_, _>>();
+
+    // Maps the index of each element in `ring` to its index in the merged graph.
+    let mut indices = HashMap::new();
+    let mut added = Vec::new();
+    let mut add = |data: &mut GraphDisplayData, flags: &mut GraphFlags, index: usize| {
+        let merged = data.elements.len();
+        data.elements.push(ring.elements[index]);
+        data.labels.push(ring.labels[index].clone());
+        if let Some(characteristic) = ring.characteristics.get(&index) {
+            data.characteristics.insert(merged, characteristic.clone());
+        }
+        if let Some(kind) = ring_flags.kinds.get(&index) {
+            flags.kinds.insert(merged, *kind);
+        }
+        if ring_flags.inferred.contains(&index) {
+            flags.inferred.insert(merged);
+        }
+        if let Some(iri) = ring_flags.iris.get(&index) {
+            flags.iris.insert(merged, iri.clone());
+        }
+        if let Some(property) = ring_flags.properties.get(&index) {
+            flags.properties.insert(merged, property.clone());
+        }
+        added.push(index);
+        merged
+    };
+    // Nodes first, so the edges can be connected to them.
+    for (index, iri) in ring_flags.iris.iter() {
+        let merged = match nodes.get(iri) {
+            Some(merged) => *merged,
+            None => {
+                let merged = add(&mut data, &mut flags, *index);
+                nodes.insert(iri.clone(), merged);
+                merged
+            }
+        };
+        indices.insert(*index, merged);
+    }
+    for [subject, edge, object] in ring.edges.iter() {
+        let (Some(subject), Some(object)) = (indices.get(subject), indices.get(object)) else {
+            continue;
+        };
+        let key = (
+            *subject,
+            ring.elements[*edge],
+            ring.labels[*edge].clone(),
+            *object,
+        );
+        if edges.contains_key(&key) {
+            continue;
+        }
+        let merged = add(&mut data, &mut flags, *edge);
+        data.edges.push([*subject, merged, *object]);
+        edges.insert(key, merged);
+        indices.insert(*edge, merged);
+    }
+    data.cardinalities.extend(
+        ring.cardinalities
+            .iter()
+            .filter(|(index, _)| added.contains(index))
+            .filter_map(|(index, cardinality)| Some((*indices.get(index)?, cardinality.clone()))),
+    );
+    (data, flags)
 }
 
 #[component]
@@ -35,14 +542,6 @@ fn WorkbenchMenuItems(#[prop(into)] title: String, children: Children) -> impl I
 
 #[component]
 pub fn NewWorkbench() -> impl IntoView {
-    let graph_data = RwSignal::new(GraphDisplayData::new());
-    let total_graph_data = RwSignal::new(GraphDisplayData::new());
-
-    provide_context(GraphDataContext {
-        graph_data: graph_data.clone(),
-        total_graph_data: total_graph_data.clone(),
-    });
-
     view! {
         <VerticalMenu>
             <ListElement title="Load Ontology" icon=icondata::BiMenuRegular>
@@ -53,10 +552,18 @@ pub fn NewWorkbench() -> impl IntoView {
             //     <SearchMenu />
             // </ListElement>
 
+            <ListElement title="Explore" icon=icondata::BiMenuRegular>
+                <ExploreMenu />
+            </ListElement>
+
             <ListElement title="Filter" icon=icondata::BiMenuRegular>
                 <FilterMenu />
             </ListElement>
 
+            <ListElement title="SPARQL" icon=icondata::BiMenuRegular>
+                <SparqlConsoleMenu />
+            </ListElement>
+
             <ListElement title="Export" icon=icondata::BiMenuRegular>
                 <ExportMenu />
             </ListElement>
@@ -65,6 +572,9 @@ pub fn NewWorkbench() -> impl IntoView {
                 <ListElement title="Simulator">
                     <OptionsMenu />
                 </ListElement>
+                <ListElement title="Serialization">
+                    <SerializerOptionsMenu />
+                </ListElement>
             </ListDetails>
 
             <ListElement title="About" icon=icondata::BiMenuRegular>
@@ -0,0 +1,147 @@
+use super::{GraphDataContext, WorkbenchMenuItems};
+use leptos::prelude::*;
+use leptos::server_fn::codec::Rkyv;
+#[cfg(feature = "server")]
+use vowlr_database::store::VOWLRStore;
+use vowlr_sparql_queries::prelude::MAX_RADIUS;
+
+/// Returns the IRI and label of the named elements within reach of `focus`, ordered by label.
+#[server(input = Rkyv, output = Rkyv)]
+pub async fn neighborhood_elements(
+    focus: Vec<(String, usize)>,
+    languages: Vec<String>,
+) -> Result<Vec<(String, String)>, ServerFnError> {
+    let store = VOWLRStore::default();
+    Ok(store.neighborhood_elements(&focus, &languages).await?)
+}
+
+#[component]
+fn ExploreFrom() -> impl IntoView {
+    let graph_context = expect_context::<GraphDataContext>();
+    let focus = graph_context.focus;
+    let iri = RwSignal::new(String::new());
+    let radius = RwSignal::new(2_usize);
+
+    let explore = move |_| {
+        let iri = iri.get_untracked().trim().to_string();
+        if !iri.is_empty() {
+            focus.set(vec![(iri, radius.get_untracked())]);
+            graph_context.reload_graph();
+        }
+    };
+    let show_all = move |_| {
+        focus.set(Vec::new());
+        graph_context.reload_graph();
+    };
+
+    view! {
+        <fieldset>
+            <legend>"Neighborhood"</legend>
+            <div class="flex flex-col content-around m-4 size-fit">
+                <label class="block mb-1">"Focus IRI:"</label>
+                <input
+                    type="text"
+                    class="p-1 w-full bg-gray-200 rounded"
+                    placeholder="http://xmlns.com/foaf/0.1/Person"
+                    prop:value=move || iri.get()
+                    on:change=move |ev| iri.set(event_target_value(&ev))
+                />
+                <label class="block mt-2 mb-1">"Hops:"</label>
+                <input
+                    type="number"
+                    min="1"
+                    max=MAX_RADIUS.to_string()
+                    class="p-1 w-full bg-gray-200 rounded"
+                    prop:value=move || radius.get().to_string()
+                    on:change=move |ev| {
+                        if let Ok(hops) = event_target_value(&ev).parse::<usize>() {
+                            radius.set(hops.clamp(1, MAX_RADIUS));
+                        }
+                    }
+                />
+                <button class="p-1 mt-2 text-xs bg-gray-200 rounded" on:click=explore>
+                    "Explore from here"
+                </button>
+                <button
+                    class="p-1 mt-1 text-xs bg-gray-200 rounded"
+                    disabled=move || focus.with(|focus| focus.is_empty())
+                    on:click=show_all
+                >
+                    "Show complete graph"
+                </button>
+            </div>
+        </fieldset>
+    }
+}
+
+/// Lists the elements of the explored neighborhood.
+///
+/// Clicking an element expands the neighborhood by the elements next to it,
+/// see [`GraphDataContext::expand`].
+#[component]
+fn ExpandNeighborhood() -> impl IntoView {
+    let graph_context = expect_context::<GraphDataContext>();
+    let GraphDataContext {
+        total_graph_data,
+        label_languages,
+        focus,
+        ..
+    } = graph_context;
+
+    // Refetch the elements whenever the explored neighborhood is drawn.
+    let elements = LocalResource::new(move || {
+        total_graph_data.track();
+        let focus = focus.get_untracked();
+        let languages = label_languages.get_untracked();
+        async move {
+            if focus.is_empty() {
+                Ok(Vec::new())
+            } else {
+                neighborhood_elements(focus, languages).await
+            }
+        }
+    });
+
+    let expand = move |iri: String| graph_context.expand(iri);
+
+    view! {
+        <fieldset>
+            <legend>"Expand"</legend>
+            <ul class="overflow-y-auto m-4 text-sm max-h-[300px]">
+                {move || {
+                    elements
+                        .get()
+                        .and_then(|elements| elements.ok())
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|(iri, label)| {
+                            let title = iri.clone();
+                            let label = if label.is_empty() { iri.clone() } else { label };
+                            view! {
+                                <li>
+                                    <button
+                                        class="text-left hover:underline"
+                                        title=title
+                                        on:click=move |_| expand(iri.clone())
+                                    >
+                                        {label}
+                                    </button>
+                                </li>
+                            }
+                        })
+                        .collect_view()
+                }}
+            </ul>
+        </fieldset>
+    }
+}
+
+#[component]
+pub fn ExploreMenu() -> impl IntoView {
+    view! {
+        <WorkbenchMenuItems title="Explore">
+            <ExploreFrom />
+            <ExpandNeighborhood />
+        </WorkbenchMenuItems>
+    }
+}
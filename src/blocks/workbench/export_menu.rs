@@ -40,6 +40,48 @@ pub fn ExportButton(
     }
 }
 
+/// Lets the browser save `data` as a file named `download_name`.
+#[cfg(target_arch = "wasm32")]
+pub fn save_file(data: &[u8], mime_type: &str, download_name: &str) {
+    use web_sys::wasm_bindgen::JsCast;
+    use web_sys::{Blob, HtmlAnchorElement, Url};
+
+    let window = web_sys::window().unwrap();
+    let document = window.document().unwrap();
+    let body = document.body().unwrap();
+
+    let blob_parts = web_sys::js_sys::Array::new();
+    let uint8_array = web_sys::js_sys::Uint8Array::from(data);
+    blob_parts.push(&uint8_array.into());
+
+    let mut blob_options = web_sys::BlobPropertyBag::new();
+    blob_options.set_type(mime_type);
+
+    let blob = Blob::new_with_str_sequence_and_options(&blob_parts, &blob_options).unwrap();
+    let url = Url::create_object_url_with_blob(&blob).unwrap();
+    leptos::logging::log!("URL: {}", url);
+    let a = document
+        .create_element("a")
+        .unwrap()
+        .unchecked_into::<HtmlAnchorElement>();
+    a.set_href(&url);
+    a.set_download(download_name);
+    a.set_attribute("style", "display: none").unwrap();
+
+    body.append_child(&a).unwrap();
+    a.click();
+    body.remove_child(&a).unwrap();
+    Url::revoke_object_url(&url).unwrap();
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_file(data: &[u8], mime_type: &str, download_name: &str) {
+    let _ = (data, mime_type);
+    leptos::logging::warn!(
+        "save_file invoked on non-wasm target; skipping download of {download_name}"
+    );
+}
+
 #[cfg(target_arch = "wasm32")]
 pub fn download_ontology(resource_type: &str, progress_message: RwSignal<String>) {
     let resource = resource_type.to_string();
@@ -69,36 +111,7 @@ pub fn download_ontology(resource_type: &str, progress_message: RwSignal<String>
                     resource,
                     data.len()
                 );
-                use web_sys::wasm_bindgen::JsCast;
-                use web_sys::{Blob, HtmlAnchorElement, Url};
-
-                let window = web_sys::window().unwrap();
-                let document = window.document().unwrap();
-                let body = document.body().unwrap();
-
-                let blob_parts = web_sys::js_sys::Array::new();
-                let uint8_array = web_sys::js_sys::Uint8Array::from(data.as_slice());
-                blob_parts.push(&uint8_array.into());
-
-                let mut blob_options = web_sys::BlobPropertyBag::new();
-                blob_options.set_type(mime_type);
-
-                let blob =
-                    Blob::new_with_str_sequence_and_options(&blob_parts, &blob_options).unwrap();
-                let url = Url::create_object_url_with_blob(&blob).unwrap();
-                leptos::logging::log!("URL: {}", url);
-                let a = document
-                    .create_element("a")
-                    .unwrap()
-                    .unchecked_into::<HtmlAnchorElement>();
-                a.set_href(&url);
-                a.set_download(&download_name);
-                a.set_attribute("style", "display: none").unwrap();
-
-                body.append_child(&a).unwrap();
-                a.click();
-                body.remove_child(&a).unwrap();
-                Url::revoke_object_url(&url).unwrap();
+                save_file(&data, mime_type, &download_name);
                 progress_message.set("Download complete".to_string());
             }
             Err(e) => {
@@ -1,32 +1,80 @@
 mod classes;
+mod element_filter;
 mod element_legend_injection;
 mod filtergroup;
 mod filtertype;
+mod hierarchy_filter;
+mod individuals;
 mod meta_filter;
 mod properties;
 mod special_operators;
 
 use super::{GraphDataContext, WorkbenchMenuItems};
-use crate::components::user_input::file_upload::handle_internal_sparql;
-use grapher::prelude::{Characteristic, ElementType, GraphDisplayData};
+use crate::components::user_input::file_upload::handle_filtered_graph;
+use grapher::prelude::{Characteristic, ElementType};
 use grapher::prelude::{EVENT_DISPATCHER, RenderEvent};
 use leptos::prelude::*;
-use leptos::task::spawn_local;
 use log::{debug, error};
 use std::collections::HashMap;
-use vowlr_sparql_queries::prelude::QueryAssembler;
+use vowlr_util::flags::ElementKind;
 
 use classes::{is_owl_class, is_rdf_class};
+use element_filter::ElementFilterSettings;
 use filtergroup::FilterGroup;
+use hierarchy_filter::HierarchyFilter;
+use individuals::is_individual;
 use meta_filter::filter;
 use properties::is_property;
 use special_operators::is_set_operator;
 
-fn update_graph(query: String, graph_data: RwSignal<GraphDisplayData>) {
-    spawn_local(async move {
-        let output_result = handle_internal_sparql(query).await;
+/// Draws the graph without the element types unchecked in `checks`
+/// and the element kinds unchecked in `kind_checks`.
+///
+/// The elements are selected from the graph cached on the server,
+/// so the store is only queried again if the query or the serializer options changed.
+fn update_graph(
+    context: GraphDataContext,
+    checks: &HashMap<ElementType, bool>,
+    kind_checks: &HashMap<ElementKind, bool>,
+) {
+    let GraphDataContext {
+        graph_data,
+        graph_flags,
+        serializer_options,
+        element_filter,
+        hidden_elements,
+        hidden_kinds,
+        query_outcome,
+        ..
+    } = context;
+    let query = match context.graph_query() {
+        Ok(query) => query,
+        Err(e) => {
+            error!("{}", e);
+            return;
+        }
+    };
+    let options = serializer_options.get_untracked();
+    let filter = element_filter.get_untracked();
+    let hidden = checks
+        .iter()
+        .filter(|&(_, &checked)| !checked)
+        .map(|(element, _)| *element)
+        .collect::<Vec<_>>();
+    let kinds = kind_checks
+        .iter()
+        .filter(|&(_, &checked)| !checked)
+        .map(|(kind, _)| *kind)
+        .collect::<Vec<_>>();
+    hidden_elements.set(hidden.clone());
+    hidden_kinds.set(kinds.clone());
+    context.spawn_graph_request(|request| async move {
+        let output_result =
+            handle_filtered_graph(query, options, filter, hidden, kinds, request).await;
         match output_result {
-            Ok(new_graph_data) => {
+            Ok((new_graph_data, flags, outcome)) => {
+                query_outcome.set(outcome);
+                graph_flags.set(flags);
                 graph_data.set(new_graph_data.clone());
                 let _ = EVENT_DISPATCHER
                     .rend_write_chan
@@ -39,19 +87,30 @@ fn update_graph(query: String, graph_data: RwSignal<GraphDisplayData>) {
 
 #[component]
 pub fn FilterMenu() -> impl IntoView {
+    let graph_context = expect_context::<GraphDataContext>();
     let GraphDataContext {
-        graph_data,
         total_graph_data,
-    } = expect_context::<GraphDataContext>();
+        total_graph_flags,
+        serializer_options,
+        element_filter,
+        hierarchy,
+        ..
+    } = graph_context;
+    // Elements of a kind are counted by their kind, not by the type they're drawn as.
     let element_counts = Memo::new(move |_| {
         let mut counts: HashMap<ElementType, usize> = HashMap::new();
         total_graph_data.with(|data| {
-            for element in &data.elements {
-                *counts.entry(*element).or_insert(0) += 1;
-            }
+            total_graph_flags.with(|flags| {
+                for (index, element) in data.elements.iter().enumerate() {
+                    if !flags.kinds.contains_key(&index) {
+                        *counts.entry(*element).or_insert(0) += 1;
+                    }
+                }
+            })
         });
         counts
     });
+    let kind_counts = Memo::new(move |_| total_graph_flags.with(|flags| flags.counts()));
 
     // let char_counts = Memo::new(move |_| {
     //     let mut counts: HashMap<Characteristic, usize> = HashMap::new();
@@ -78,6 +137,7 @@ pub fn FilterMenu() -> impl IntoView {
     // });
 
     let element_checks = RwSignal::new(HashMap::new());
+    let kind_checks = RwSignal::new(HashMap::new());
 
     element_checks.update_untracked(|map| {
         for elem in element_counts.read().keys() {
@@ -108,21 +168,41 @@ pub fn FilterMenu() -> impl IntoView {
     let open_rdf = RwSignal::new(false);
     let open_set_operations = RwSignal::new(false);
     let open_properties = RwSignal::new(false);
+    let open_relations = RwSignal::new(false);
+    let open_individuals = RwSignal::new(false);
     // let (open_chars, set_open_chars) = signal(false);
 
     Effect::watch(
-        move || element_checks.get(),
-        move |checks, _, _| {
-            let query =
-                QueryAssembler::assemble_filtered_query(checks /* , char_checks.get()*/);
-            leptos::logging::log!("{}", query);
-            update_graph(query, graph_data);
-        },
+        move || (element_checks.get(), kind_checks.get()),
+        move |(checks, kinds), _, _| update_graph(graph_context, checks, kinds),
         false,
     );
 
     view! {
         <WorkbenchMenuItems title="Filter by Type">
+            // Changing the class hierarchy keeps the element filter.
+            <HierarchyFilter
+                hierarchy=hierarchy
+                on_change=Callback::new(move |_| {
+                    update_graph(
+                        graph_context,
+                        &element_checks.get_untracked(),
+                        &kind_checks.get_untracked(),
+                    );
+                })
+            />
+
+            <ElementFilterSettings
+                element_filter=element_filter
+                on_change=Callback::new(move |_| {
+                    update_graph(
+                        graph_context,
+                        &element_checks.get_untracked(),
+                        &kind_checks.get_untracked(),
+                    );
+                })
+            />
+
             <div class="flex gap-2 items-center pb-3 mb-3 border-b">
                 <button
                     class="text-sm text-blue-600 hover:text-blue-800"
@@ -212,6 +292,58 @@ pub fn FilterMenu() -> impl IntoView {
                 counts=element_counts
             />
 
+            <FilterGroup<
+            ElementKind,
+        >
+                name="Relations"
+                is_open=open_relations
+                items=ElementKind::ALL
+                    .into_iter()
+                    .filter(|kind| kind_counts.get().contains_key(kind))
+                    .collect::<Vec<_>>()
+                checks=kind_checks
+                counts=kind_counts
+            />
+
+            <FilterGroup<
+            ElementType,
+        >
+                name="Individuals"
+                is_open=open_individuals
+                items=filter(
+                    element_counts
+                        .get()
+                        .into_keys()
+                        .into_iter()
+                        .collect::<Vec<_>>(),
+                    vec![is_individual],
+                )
+                checks=element_checks
+                counts=element_counts
+            />
+
+            <label class="flex gap-2 items-center py-2">
+                <input
+                    type="checkbox"
+                    class="w-4 h-4 cursor-pointer"
+                    prop:checked=move || {
+                        serializer_options.with(|options| options.property_hierarchy)
+                    }
+                    on:change=move |ev| {
+                        serializer_options
+                            .update(|options| {
+                                options.property_hierarchy = event_target_checked(&ev);
+                            });
+                        update_graph(
+                            graph_context,
+                            &element_checks.get_untracked(),
+                            &kind_checks.get_untracked(),
+                        );
+                    }
+                />
+                <span class="font-medium">"Property Hierarchy"</span>
+            </label>
+
         // <FilterGroup
         // name="Characteristics"
         // is_open=open_chars
@@ -0,0 +1,125 @@
+use crate::blocks::workbench::options_menu::parse_namespaces;
+use leptos::prelude::*;
+use vowlr_util::options::ElementFilter;
+
+/// Filters the drawn elements by namespace, label, degree, deprecation and externality.
+///
+/// The filter is applied to the graph cached on the server, so it doesn't query the store again.
+#[component]
+pub fn ElementFilterSettings(
+    #[prop(into)] element_filter: RwSignal<ElementFilter>,
+    /// Called whenever the filter changes.
+    on_change: Callback<()>,
+) -> impl IntoView {
+    view! {
+        <div class="pb-3 mb-3 text-sm border-b">
+            <div class="mb-1 font-medium">"Elements"</div>
+            <label class="block mb-1">"Only namespaces (one per line):"</label>
+            <textarea
+                class="p-1 w-full bg-gray-200 rounded"
+                prop:value=move || {
+                    element_filter.with(|filter| filter.include_namespaces.join("\n"))
+                }
+                on:change=move |ev| {
+                    let namespaces = parse_namespaces(&event_target_value(&ev));
+                    element_filter.update(|filter| filter.include_namespaces = namespaces);
+                    on_change.run(());
+                }
+            ></textarea>
+            <label class="block mt-1 mb-1">"Hide namespaces (one per line):"</label>
+            <textarea
+                class="p-1 w-full bg-gray-200 rounded"
+                prop:value=move || {
+                    element_filter.with(|filter| filter.exclude_namespaces.join("\n"))
+                }
+                on:change=move |ev| {
+                    let namespaces = parse_namespaces(&event_target_value(&ev));
+                    element_filter.update(|filter| filter.exclude_namespaces = namespaces);
+                    on_change.run(());
+                }
+            ></textarea>
+            <label class="block mt-1 mb-1">"Labels matching (regular expression):"</label>
+            <input
+                type="text"
+                class="p-1 w-full bg-gray-200 rounded"
+                prop:value=move || {
+                    element_filter
+                        .with(|filter| filter.label_pattern.clone().unwrap_or_default())
+                }
+                on:change=move |ev| {
+                    let pattern = event_target_value(&ev);
+                    element_filter.update(|filter| {
+                        filter.label_pattern = (!pattern.trim().is_empty())
+                            .then_some(pattern)
+                    });
+                    on_change.run(());
+                }
+            />
+            <label class="flex gap-2 items-center mt-2">
+                <input
+                    type="checkbox"
+                    class="w-4 h-4 cursor-pointer"
+                    prop:checked=move || {
+                        element_filter.with(|filter| filter.min_degree > 1)
+                    }
+                    on:change=move |ev| {
+                        let min_degree = if event_target_checked(&ev) { 2 } else { 0 };
+                        element_filter.update(|filter| filter.min_degree = min_degree);
+                        on_change.run(());
+                    }
+                />
+                "Hide leaves"
+            </label>
+            <label class="flex gap-2 items-center mt-1">
+                "Hide nodes with more edges than:"
+                <input
+                    type="number"
+                    min="1"
+                    class="p-1 w-16 bg-gray-200 rounded"
+                    prop:value=move || {
+                        element_filter
+                            .with(|filter| {
+                                filter
+                                    .max_degree
+                                    .map(|max| max.to_string())
+                                    .unwrap_or_default()
+                            })
+                    }
+                    on:change=move |ev| {
+                        let max_degree = event_target_value(&ev).parse::<usize>().ok();
+                        element_filter.update(|filter| filter.max_degree = max_degree);
+                        on_change.run(());
+                    }
+                />
+            </label>
+            <label class="flex gap-2 items-center mt-1">
+                <input
+                    type="checkbox"
+                    class="w-4 h-4 cursor-pointer"
+                    prop:checked=move || {
+                        element_filter.with(|filter| filter.deprecated)
+                    }
+                    on:change=move |ev| {
+                        let deprecated = event_target_checked(&ev);
+                        element_filter.update(|filter| filter.deprecated = deprecated);
+                        on_change.run(());
+                    }
+                />
+                "Deprecated elements"
+            </label>
+            <label class="flex gap-2 items-center mt-1">
+                <input
+                    type="checkbox"
+                    class="w-4 h-4 cursor-pointer"
+                    prop:checked=move || element_filter.with(|filter| filter.external)
+                    on:change=move |ev| {
+                        let external = event_target_checked(&ev);
+                        element_filter.update(|filter| filter.external = external);
+                        on_change.run(());
+                    }
+                />
+                "External elements"
+            </label>
+        </div>
+    }
+}
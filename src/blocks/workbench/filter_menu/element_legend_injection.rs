@@ -2,6 +2,7 @@ use grapher::prelude::{
     ElementType, GenericEdge, GenericNode, GenericType, OwlEdge, OwlNode, OwlType, RdfEdge,
     RdfType, RdfsEdge, RdfsNode, RdfsType,
 };
+use vowlr_util::flags::ElementKind;
 
 pub trait ElementLegend {
     /// Get the legend of `self`.
@@ -58,6 +59,16 @@ impl ElementLegend for RdfsEdge {
     }
 }
 
+impl ElementLegend for ElementKind {
+    fn legend(self) -> Option<String> {
+        match self {
+            // Drawn as the closest element type, see `ElementKind`.
+            ElementKind::SubPropertyOf => Some("/node_legends/SubclassOf.png".to_string()),
+            ElementKind::DisjointnessHub => Some("/node_legends/RdfsResource.png".to_string()),
+        }
+    }
+}
+
 impl ElementLegend for RdfEdge {
     fn legend(self) -> Option<String> {
         match self {
@@ -0,0 +1,96 @@
+use leptos::prelude::*;
+use vowlr_sparql_queries::prelude::{Direction, Hierarchy, MAX_DEPTH};
+
+/// Restricts the graph to the subtree below a root class or the ancestors above it.
+#[component]
+pub fn HierarchyFilter(
+    #[prop(into)] hierarchy: RwSignal<Option<Hierarchy>>,
+    /// Called whenever `hierarchy` is applied or cleared.
+    on_change: Callback<()>,
+) -> impl IntoView {
+    let root = RwSignal::new(String::new());
+    let direction = RwSignal::new(Direction::Descendants);
+    let depth = RwSignal::new(2_usize);
+    let all_levels = RwSignal::new(false);
+
+    let apply = move |_| {
+        let root = root.get_untracked().trim().to_string();
+        if !root.is_empty() {
+            hierarchy.set(Some(Hierarchy {
+                root,
+                direction: direction.get_untracked(),
+                depth: (!all_levels.get_untracked()).then(|| depth.get_untracked()),
+            }));
+            on_change.run(());
+        }
+    };
+
+    view! {
+        <div class="pb-3 mb-3 border-b">
+            <div class="mb-1 font-medium">"Class Hierarchy"</div>
+            <input
+                type="text"
+                class="p-1 w-full text-sm bg-gray-200 rounded"
+                placeholder="http://purl.obolibrary.org/obo/BFO_0000040"
+                prop:value=move || root.get()
+                on:change=move |ev| root.set(event_target_value(&ev))
+            />
+            <select
+                class="p-1 mt-1 w-full text-sm bg-gray-200 rounded"
+                on:change=move |ev| {
+                    direction
+                        .set(
+                            if event_target_value(&ev) == "ancestors" {
+                                Direction::Ancestors
+                            } else {
+                                Direction::Descendants
+                            },
+                        );
+                }
+            >
+                <option value="descendants">"Subtree below the class"</option>
+                <option value="ancestors">"Ancestors of the class"</option>
+            </select>
+            <div class="flex gap-2 items-center mt-1 text-sm">
+                "Depth:"
+                <input
+                    type="number"
+                    min="1"
+                    max=MAX_DEPTH.to_string()
+                    class="p-1 w-16 bg-gray-200 rounded"
+                    disabled=move || all_levels.get()
+                    prop:value=move || depth.get().to_string()
+                    on:change=move |ev| {
+                        if let Ok(levels) = event_target_value(&ev).parse::<usize>() {
+                            depth.set(levels.clamp(1, MAX_DEPTH));
+                        }
+                    }
+                />
+                <label class="flex gap-1 items-center">
+                    <input
+                        type="checkbox"
+                        class="w-4 h-4 cursor-pointer"
+                        prop:checked=move || all_levels.get()
+                        on:change=move |ev| all_levels.set(event_target_checked(&ev))
+                    />
+                    "All"
+                </label>
+            </div>
+            <div class="flex gap-2 mt-1">
+                <button class="p-1 text-xs bg-gray-200 rounded" on:click=apply>
+                    "Apply"
+                </button>
+                <button
+                    class="p-1 text-xs bg-gray-200 rounded"
+                    disabled=move || hierarchy.with(Option::is_none)
+                    on:click=move |_| {
+                        hierarchy.set(None);
+                        on_change.run(());
+                    }
+                >
+                    "Clear"
+                </button>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,9 @@
+use grapher::prelude::{ElementType, GenericType};
+
+pub fn is_individual(item: ElementType) -> bool {
+    match item {
+        ElementType::Generic(GenericType::Node(_)) => true,
+        ElementType::Generic(GenericType::Edge(_)) => true,
+        _ => false,
+    }
+}
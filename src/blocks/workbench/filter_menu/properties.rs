@@ -1,8 +1,8 @@
-use grapher::prelude::{ElementType, GenericType, OwlType, RdfType, RdfsType};
+use grapher::prelude::{ElementType, OwlType, RdfType, RdfsType};
 
+/// Edges between individuals are listed with the individuals instead.
 pub fn is_property(item: ElementType) -> bool {
     match item {
-        ElementType::Generic(GenericType::Edge(_)) => true,
         ElementType::Owl(OwlType::Edge(_)) => true,
         ElementType::Rdf(RdfType::Edge(_)) => true,
         ElementType::Rdfs(RdfsType::Edge(_)) => true,
@@ -1,10 +1,8 @@
 use super::{GraphDataContext, WorkbenchMenuItems};
 use crate::components::{icon::Icon, user_input::file_upload::*};
-use grapher::prelude::{EVENT_DISPATCHER, RenderEvent};
 use leptos::prelude::*;
-use leptos::task::spawn_local;
 use log::{error, info};
-use vowlr_sparql_queries::prelude::DEFAULT_QUERY;
+use vowlr_util::options::VisualizationMode;
 use web_sys::HtmlInputElement;
 
 #[component]
@@ -49,10 +47,7 @@ fn SelectStaticInput() -> impl IntoView {
 
 #[component]
 fn UploadInput() -> impl IntoView {
-    let GraphDataContext {
-        graph_data,
-        total_graph_data,
-    } = expect_context::<GraphDataContext>();
+    let graph_context = expect_context::<GraphDataContext>();
     let upload = FileUpload::new();
     let local_loading_done = upload.local_action.value();
     let remote_loading_done = upload.remote_action.value();
@@ -65,19 +60,12 @@ fn UploadInput() -> impl IntoView {
     Effect::new(move || {
         if let Some(value) = local_loading_done.get() {
             match value {
-                Ok(_) => spawn_local(async move {
-                    let output_result = handle_internal_sparql(DEFAULT_QUERY.to_string()).await;
-                    match output_result {
-                        Ok(new_graph_data) => {
-                            graph_data.set(new_graph_data.clone());
-                            total_graph_data.set(new_graph_data.clone());
-                            let _ = EVENT_DISPATCHER
-                                .rend_write_chan
-                                .send(RenderEvent::LoadGraph(new_graph_data));
-                        }
-                        Err(e) => error!("{}", e),
-                    }
-                }),
+                Ok(_) => {
+                    // A newly loaded ontology is drawn in full.
+                    graph_context.focus.set(Vec::new());
+                    graph_context.hierarchy.set(None);
+                    graph_context.reload_graph();
+                }
                 Err(e) => error!("{}", e),
             }
         }
@@ -86,19 +74,12 @@ fn UploadInput() -> impl IntoView {
     Effect::new(move || {
         if let Some(value) = remote_loading_done.get() {
             match value {
-                Ok(_) => spawn_local(async move {
-                    let output_result = handle_internal_sparql(DEFAULT_QUERY.to_string()).await;
-                    match output_result {
-                        Ok(new_graph_data) => {
-                            graph_data.set(new_graph_data.clone());
-                            total_graph_data.set(new_graph_data.clone());
-                            let _ = EVENT_DISPATCHER
-                                .rend_write_chan
-                                .send(RenderEvent::LoadGraph(new_graph_data));
-                        }
-                        Err(e) => error!("{}", e),
-                    }
-                }),
+                Ok(_) => {
+                    // A newly loaded ontology is drawn in full.
+                    graph_context.focus.set(Vec::new());
+                    graph_context.hierarchy.set(None);
+                    graph_context.reload_graph();
+                }
                 Err(e) => error!("{}", e),
             }
         }
@@ -208,28 +189,44 @@ fn UploadInput() -> impl IntoView {
     }
 }
 
+#[component]
+fn SelectModeInput() -> impl IntoView {
+    let graph_context = expect_context::<GraphDataContext>();
+    let serializer_options = graph_context.serializer_options;
+
+    view! {
+        <div class="mb-2">
+            <label class="block mb-1">"Visualization Mode:"</label>
+            <select
+                class="p-1 w-full text-sm bg-gray-200 rounded border-b-0"
+                on:change=move |ev| {
+                    let mode = VisualizationMode::from(event_target_value(&ev).as_str());
+                    serializer_options.update(|options| options.mode = mode);
+                    graph_context.reload_graph();
+                }
+            >
+                {VisualizationMode::ALL
+                    .into_iter()
+                    .map(|mode| {
+                        let selected = serializer_options
+                            .with_untracked(|options| options.mode == mode);
+                        view! { <option selected=selected>{mode.name()}</option> }
+                    })
+                    .collect_view()}
+            </select>
+        </div>
+    }
+}
+
 #[component]
 fn FetchData() -> impl IntoView {
+    let graph_context = expect_context::<GraphDataContext>();
+
     view! {
         <div class="flex flex-col gap-2">
             <button
                 class="flex relative justify-center items-center p-1 mt-1 text-xs bg-gray-200 rounded text-[#000000]"
-                on:click=move |_| {
-                    spawn_local(async {
-                        let output_result = handle_internal_sparql(
-                                DEFAULT_QUERY.to_string(),
-                            )
-                            .await;
-                        match output_result {
-                            Ok(graph_data) => {
-                                let _ = EVENT_DISPATCHER
-                                    .rend_write_chan
-                                    .send(RenderEvent::LoadGraph(graph_data));
-                            }
-                            Err(e) => error!("{}", e),
-                        }
-                    })
-                }
+                on:click=move |_| graph_context.reload_graph()
             >
                 <Icon class="pr-0.5" icon=icondata::AiReloadOutlined />
                 "reload data"
@@ -240,7 +237,9 @@ fn FetchData() -> impl IntoView {
 
 #[component]
 fn Sparql() -> impl IntoView {
+    let graph_context = expect_context::<GraphDataContext>();
     let upload = FileUpload::new();
+    let sparql_loading_done = upload.sparql_action.value();
     let upload_progress = upload.tracker.upload_progress.clone();
     let parsing_status = upload.tracker.parsing_status.clone();
     let parsing_done = upload.tracker.parsing_done.clone();
@@ -262,6 +261,20 @@ fn Sparql() -> impl IntoView {
         }
     };
 
+    Effect::new(move || {
+        if let Some(value) = sparql_loading_done.get() {
+            match value {
+                Ok(_) => {
+                    // A newly loaded ontology is drawn in full.
+                    graph_context.focus.set(Vec::new());
+                    graph_context.hierarchy.set(None);
+                    graph_context.reload_graph();
+                }
+                Err(e) => error!("{}", e),
+            }
+        }
+    });
+
     let run_sparql = move || {
         tracker_sparql.upload_sparql(
             endpoint_signal.get(),
@@ -367,6 +380,7 @@ pub fn OntologyMenu() -> impl IntoView {
     view! {
         <WorkbenchMenuItems title="Load Ontology">
             <SelectStaticInput />
+            <SelectModeInput />
             <UploadInput />
             <Sparql />
             <FetchData />
@@ -1,8 +1,9 @@
-use super::WorkbenchMenuItems;
+use super::{GraphDataContext, WorkbenchMenuItems};
 use crate::components::tooltip::{ToolTip, ToolTipPosition};
 use crate::components::user_input::range_select::Slider;
 use grapher::prelude::*;
 use leptos::prelude::*;
+use vowlr_util::options::LabelFallback;
 
 #[component]
 pub fn SimulatorSettings() -> impl IntoView {
@@ -115,3 +116,240 @@ pub fn OptionsMenu() -> impl IntoView {
         </WorkbenchMenuItems>
     }
 }
+
+#[component]
+pub fn LabelSettings() -> impl IntoView {
+    let graph_context = expect_context::<GraphDataContext>();
+    let serializer_options = graph_context.serializer_options;
+
+    view! {
+        <fieldset>
+            <legend>"Labels"</legend>
+            <div class="flex flex-col content-around m-4 size-fit">
+                <label class="block mb-1">"Fallback for unlabelled elements:"</label>
+                <select
+                    class="p-1 w-full bg-gray-200 rounded"
+                    on:change=move |ev| {
+                        let fallback = LabelFallback::from(event_target_value(&ev).as_str());
+                        serializer_options.update(|options| options.label_fallback = fallback);
+                        graph_context.reload_graph();
+                    }
+                >
+                    {LabelFallback::ALL
+                        .into_iter()
+                        .map(|fallback| {
+                            let selected = serializer_options
+                                .with_untracked(|options| options.label_fallback == fallback);
+                            view! { <option selected=selected>{fallback.name()}</option> }
+                        })
+                        .collect_view()}
+                </select>
+            </div>
+        </fieldset>
+    }
+}
+
+/// Splits user input into namespaces, one per line.
+pub(super) fn parse_namespaces(input: &str) -> Vec<String> {
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[component]
+pub fn ExternalSettings() -> impl IntoView {
+    let graph_context = expect_context::<GraphDataContext>();
+    let serializer_options = graph_context.serializer_options;
+
+    view! {
+        <fieldset>
+            <legend>"External elements"</legend>
+            <div class="flex flex-col content-around m-4 size-fit">
+                <label class="block mb-1">"Always external (one namespace per line):"</label>
+                <textarea
+                    class="p-1 w-full bg-gray-200 rounded"
+                    prop:value=move || {
+                        serializer_options.with(|options| options.external_namespaces.join("\n"))
+                    }
+                    on:change=move |ev| {
+                        let namespaces = parse_namespaces(&event_target_value(&ev));
+                        serializer_options.update(|options| {
+                            options.external_namespaces = namespaces
+                        });
+                        graph_context.reload_graph();
+                    }
+                ></textarea>
+                <label class="block mt-2 mb-1">"Never external (one namespace per line):"</label>
+                <textarea
+                    class="p-1 w-full bg-gray-200 rounded"
+                    prop:value=move || {
+                        serializer_options.with(|options| options.internal_namespaces.join("\n"))
+                    }
+                    on:change=move |ev| {
+                        let namespaces = parse_namespaces(&event_target_value(&ev));
+                        serializer_options.update(|options| {
+                            options.internal_namespaces = namespaces
+                        });
+                        graph_context.reload_graph();
+                    }
+                ></textarea>
+            </div>
+        </fieldset>
+    }
+}
+
+#[component]
+pub fn IndividualSettings() -> impl IntoView {
+    let graph_context = expect_context::<GraphDataContext>();
+    let serializer_options = graph_context.serializer_options;
+
+    view! {
+        <fieldset>
+            <legend>"Individuals"</legend>
+            <div class="flex gap-2 items-center mx-4 mt-4 size-fit">
+                <input
+                    type="checkbox"
+                    id="individual-layer"
+                    class="w-4 h-4 cursor-pointer"
+                    prop:checked=move || {
+                        serializer_options.with(|options| options.individual_layer)
+                    }
+                    on:change=move |ev| {
+                        serializer_options
+                            .update(|options| {
+                                options.individual_layer = event_target_checked(&ev);
+                            });
+                        graph_context.reload_graph();
+                    }
+                />
+                <label for="individual-layer">"Draw individuals and their assertions"</label>
+            </div>
+            <div class="flex flex-col content-around m-4 size-fit">
+                <label class="block mb-1">"Hide all individuals if there are more than:"</label>
+                <input
+                    type="number"
+                    min="0"
+                    class="p-1 w-full bg-gray-200 rounded"
+                    prop:value=move || {
+                        serializer_options.with(|options| options.individual_limit.to_string())
+                    }
+                    on:change=move |ev| {
+                        if let Ok(limit) = event_target_value(&ev).parse::<usize>() {
+                            serializer_options.update(|options| options.individual_limit = limit);
+                            graph_context.reload_graph();
+                        }
+                    }
+                />
+            </div>
+        </fieldset>
+    }
+}
+
+#[component]
+pub fn AnnotationSettings() -> impl IntoView {
+    let graph_context = expect_context::<GraphDataContext>();
+    let serializer_options = graph_context.serializer_options;
+
+    view! {
+        <fieldset>
+            <legend>"Annotations"</legend>
+            <div class="flex gap-2 items-center m-4 size-fit">
+                <input
+                    type="checkbox"
+                    id="annotation-layer"
+                    class="w-4 h-4 cursor-pointer"
+                    prop:checked=move || {
+                        serializer_options.with(|options| options.annotation_layer)
+                    }
+                    on:change=move |ev| {
+                        serializer_options
+                            .update(|options| {
+                                options.annotation_layer = event_target_checked(&ev);
+                            });
+                        graph_context.reload_graph();
+                    }
+                />
+                <label for="annotation-layer">
+                    "Draw annotations with IRI values, e.g. cross references"
+                </label>
+            </div>
+        </fieldset>
+    }
+}
+
+#[component]
+pub fn InferenceSettings() -> impl IntoView {
+    let graph_context = expect_context::<GraphDataContext>();
+    let serializer_options = graph_context.serializer_options;
+
+    view! {
+        <fieldset>
+            <legend>"Inference"</legend>
+            <div class="flex gap-2 items-center m-4 size-fit">
+                <input
+                    type="checkbox"
+                    id="inference"
+                    class="w-4 h-4 cursor-pointer"
+                    prop:checked=move || serializer_options.with(|options| options.inference)
+                    on:change=move |ev| {
+                        serializer_options
+                            .update(|options| {
+                                options.inference = event_target_checked(&ev);
+                            });
+                        graph_context.reload_graph();
+                    }
+                />
+                <label for="inference">
+                    "Draw inferred subclasses, domains, ranges and assertions"
+                </label>
+            </div>
+        </fieldset>
+    }
+}
+
+#[component]
+pub fn DiagnosticSettings() -> impl IntoView {
+    let graph_context = expect_context::<GraphDataContext>();
+    let serializer_options = graph_context.serializer_options;
+
+    view! {
+        <fieldset>
+            <legend>"Diagnostics"</legend>
+            <div class="flex gap-2 items-center m-4 size-fit">
+                <input
+                    type="checkbox"
+                    id="diagnostics"
+                    class="w-4 h-4 cursor-pointer"
+                    prop:checked=move || serializer_options.with(|options| options.diagnostics)
+                    on:change=move |ev| {
+                        serializer_options
+                            .update(|options| {
+                                options.diagnostics = event_target_checked(&ev);
+                            });
+                        graph_context.reload_graph();
+                    }
+                />
+                <label for="diagnostics">
+                    "Highlight unsatisfiable classes, subclass cycles and properties with disjoint domains or ranges"
+                </label>
+            </div>
+        </fieldset>
+    }
+}
+
+#[component]
+pub fn SerializerOptionsMenu() -> impl IntoView {
+    view! {
+        <WorkbenchMenuItems title="Serialization">
+            <LabelSettings />
+            <ExternalSettings />
+            <IndividualSettings />
+            <AnnotationSettings />
+            <InferenceSettings />
+            <DiagnosticSettings />
+        </WorkbenchMenuItems>
+    }
+}
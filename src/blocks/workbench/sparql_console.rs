@@ -0,0 +1,299 @@
+use super::export_menu::save_file;
+use super::{GraphDataContext, WorkbenchMenuItems};
+use grapher::prelude::{EVENT_DISPATCHER, GraphDisplayData, RenderEvent};
+use leptos::prelude::*;
+use leptos::server_fn::codec::Rkyv;
+use leptos::task::spawn_local;
+use log::error;
+#[cfg(feature = "server")]
+use vowlr_database::store::VOWLRStore;
+use vowlr_util::{
+    flags::GraphFlags,
+    limits::QueryOutcome,
+    results::{ResultFormat, SparqlResults},
+};
+
+/// The number of rows shown on each page of the results table.
+const PAGE_SIZE: usize = 25;
+
+/// Runs `query` of any form against the loaded documents.
+///
+/// See `VOWLRStore::console_query`.
+#[server(input = Rkyv, output = Rkyv)]
+pub async fn console_query(query: String) -> Result<SparqlResults, ServerFnError> {
+    let store = VOWLRStore::default();
+    Ok(store.console_query(&query).await?)
+}
+
+/// Runs the SPARQL Update `update` against the loaded documents.
+///
+/// See `VOWLRStore::update`.
+#[server(input = Rkyv, output = Rkyv)]
+pub async fn console_update(update: String) -> Result<(), ServerFnError> {
+    let store = VOWLRStore::default();
+    Ok(store.update(&update).await?)
+}
+
+/// Draws the triples of the `CONSTRUCT` or `DESCRIBE` `query` as a new graph.
+///
+/// See `VOWLRStore::construct_graph`.
+#[server(input = Rkyv, output = Rkyv)]
+pub async fn construct_graph(
+    query: String,
+    languages: Vec<String>,
+) -> Result<(GraphDisplayData, GraphFlags, QueryOutcome), ServerFnError> {
+    let store = VOWLRStore::default();
+    Ok(store.construct_graph(&query, &languages).await?)
+}
+
+/// Writes `results` in `format` for downloading.
+///
+/// See `VOWLRStore::write_results`.
+#[server(input = Rkyv, output = Rkyv)]
+pub async fn write_results(
+    results: SparqlResults,
+    format: ResultFormat,
+) -> Result<Vec<u8>, ServerFnError> {
+    let store = VOWLRStore::default();
+    Ok(store.write_results(&results, format)?)
+}
+
+/// Shows the solutions or triples of `results` a page at a time,
+/// with buttons to download them in each of their formats.
+#[component]
+fn ResultsTable(results: SparqlResults) -> impl IntoView {
+    let formats = results.formats();
+    let table = results.table().cloned().unwrap_or_default();
+    let page = RwSignal::new(0_usize);
+    let pages = table.rows.len().div_ceil(PAGE_SIZE).max(1);
+    let (rows, truncated) = (table.rows.len(), table.truncated);
+    let header = table
+        .variables
+        .iter()
+        .map(|variable| view! { <th class="px-1 text-left border-b">{format!("?{variable}")}</th> })
+        .collect_view();
+    let table = StoredValue::new(table);
+    let results = StoredValue::new(results);
+
+    let download = move |format: ResultFormat| {
+        let results = results.get_value();
+        spawn_local(async move {
+            match write_results(results, format).await {
+                Ok(data) => save_file(
+                    &data,
+                    format.mime_type(),
+                    &format!("results.{}", format.extension()),
+                ),
+                Err(e) => error!("{}", e),
+            }
+        });
+    };
+
+    view! {
+        <div class="overflow-auto mt-2 max-h-[400px]">
+            <table class="text-xs border-collapse">
+                <thead>
+                    <tr>{header}</tr>
+                </thead>
+                <tbody>
+                    {move || {
+                        table
+                            .with_value(|table| {
+                                table
+                                    .rows
+                                    .iter()
+                                    .skip(page.get() * PAGE_SIZE)
+                                    .take(PAGE_SIZE)
+                                    .map(|row| {
+                                        let cells = row
+                                            .iter()
+                                            .map(|term| {
+                                                view! {
+                                                    <td class="px-1 align-top border-b break-all">
+                                                        {term.as_ref().map(ToString::to_string).unwrap_or_default()}
+                                                    </td>
+                                                }
+                                            })
+                                            .collect_view();
+                                        view! { <tr>{cells}</tr> }
+                                    })
+                                    .collect_view()
+                            })
+                    }}
+                </tbody>
+            </table>
+        </div>
+        <div class="flex justify-between items-center mt-1 text-xs">
+            <button
+                class="p-1 bg-gray-200 rounded"
+                disabled=move || page.get() == 0
+                on:click=move |_| page.update(|page| *page -= 1)
+            >
+                "Previous"
+            </button>
+            {move || format!("Page {} of {pages}", page.get() + 1)}
+            <button
+                class="p-1 bg-gray-200 rounded"
+                disabled=move || page.get() + 1 >= pages
+                on:click=move |_| page.update(|page| *page += 1)
+            >
+                "Next"
+            </button>
+        </div>
+        <p class="mt-1 text-xs">
+            {if truncated {
+                format!("Only the first {rows} results are shown.")
+            } else {
+                format!("{rows} results")
+            }}
+        </p>
+        <div class="flex gap-1 mt-1 text-xs">
+            "Download:"
+            {formats
+                .iter()
+                .copied()
+                .map(|format| {
+                    view! {
+                        <button
+                            class="px-1 bg-gray-200 rounded"
+                            on:click=move |_| download(format)
+                        >
+                            {format.name()}
+                        </button>
+                    }
+                })
+                .collect_view()}
+        </div>
+    }
+}
+
+/// Runs arbitrary queries and updates against the loaded documents and shows their results.
+#[component]
+fn SparqlConsole() -> impl IntoView {
+    let graph_context = expect_context::<GraphDataContext>();
+    let GraphDataContext {
+        graph_data,
+        total_graph_data,
+        graph_flags,
+        total_graph_flags,
+        label_languages,
+        query_outcome,
+        ..
+    } = graph_context;
+    let query = RwSignal::new("SELECT ?s ?p ?o\nWHERE { ?s ?p ?o }\nLIMIT 100".to_string());
+    let results = RwSignal::new(None::<Result<SparqlResults, String>>);
+    let update_result = RwSignal::new(None::<Result<(), String>>);
+    let running = RwSignal::new(false);
+
+    let run = move |_| {
+        running.set(true);
+        update_result.set(None);
+        let query = query.get_untracked();
+        spawn_local(async move {
+            results.set(Some(console_query(query).await.map_err(|e| e.to_string())));
+            running.set(false);
+        });
+    };
+
+    let run_update = move |_| {
+        running.set(true);
+        results.set(None);
+        let update = query.get_untracked();
+        spawn_local(async move {
+            let result = graph_context.write(console_update(update)).await;
+            update_result.set(Some(result.map_err(|e| e.to_string())));
+            running.set(false);
+            // Even failed updates may have changed the documents.
+            graph_context.reload_graph();
+        });
+    };
+
+    let draw = move |_| {
+        let query = query.get_untracked();
+        let languages = label_languages.get_untracked();
+        graph_context.spawn_graph_request(async move {
+            match construct_graph(query, languages).await {
+                Ok((new_graph_data, flags, outcome)) => {
+                    query_outcome.set(outcome);
+                    graph_flags.set(flags.clone());
+                    total_graph_flags.set(flags);
+                    graph_data.set(new_graph_data.clone());
+                    total_graph_data.set(new_graph_data.clone());
+                    let _ = EVENT_DISPATCHER
+                        .rend_write_chan
+                        .send(RenderEvent::LoadGraph(new_graph_data));
+                }
+                Err(e) => error!("{}", e),
+            }
+        });
+    };
+
+    view! {
+        <fieldset>
+            <legend>"Local SPARQL Query"</legend>
+            <div class="flex flex-col gap-1">
+                <textarea
+                    class="p-1 w-full font-mono text-xs bg-gray-200 rounded"
+                    rows=8
+                    prop:value=move || query.get()
+                    on:input=move |ev| query.set(event_target_value(&ev))
+                ></textarea>
+                <div class="flex gap-1">
+                    <button
+                        class="p-1 text-xs text-white bg-blue-500 rounded grow"
+                        disabled=move || running.get()
+                        on:click=run
+                    >
+                        {move || if running.get() { "Running..." } else { "Run query" }}
+                    </button>
+                    <button
+                        class="p-1 text-xs bg-gray-200 rounded grow"
+                        disabled=move || running.get()
+                        on:click=run_update
+                    >
+                        "Run update"
+                    </button>
+                </div>
+                {move || match update_result.get() {
+                    None => ().into_any(),
+                    Some(Ok(())) => {
+                        view! { <p class="text-xs">"The update was applied."</p> }.into_any()
+                    }
+                    Some(Err(e)) => {
+                        view! { <p class="text-xs text-red-600 break-all">{e}</p> }.into_any()
+                    }
+                }}
+                {move || match results.get() {
+                    None => ().into_any(),
+                    Some(Err(e)) => {
+                        view! { <p class="text-xs text-red-600 break-all">{e}</p> }.into_any()
+                    }
+                    Some(Ok(SparqlResults::Boolean(answer))) => {
+                        view! { <p class="text-sm">{answer.to_string()}</p> }.into_any()
+                    }
+                    Some(Ok(results @ SparqlResults::Solutions(_))) => {
+                        view! { <ResultsTable results=results /> }.into_any()
+                    }
+                    Some(Ok(results @ SparqlResults::Graph(_))) => {
+                        view! {
+                            <ResultsTable results=results />
+                            <button class="p-1 mt-1 text-xs bg-gray-200 rounded" on:click=draw>
+                                "Draw as graph"
+                            </button>
+                        }
+                            .into_any()
+                    }
+                }}
+            </div>
+        </fieldset>
+    }
+}
+
+#[component]
+pub fn SparqlConsoleMenu() -> impl IntoView {
+    view! {
+        <WorkbenchMenuItems title="SPARQL Console">
+            <SparqlConsole />
+        </WorkbenchMenuItems>
+    }
+}
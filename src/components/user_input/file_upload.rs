@@ -1,6 +1,7 @@
+use crate::blocks::workbench::GraphDataContext;
 use futures::StreamExt;
 use gloo_timers::callback::Interval;
-use grapher::prelude::GraphDisplayData;
+use grapher::prelude::{ElementType, GraphDisplayData};
 use leptos::prelude::*;
 use leptos::server_fn::ServerFnError;
 use leptos::server_fn::codec::{MultipartData, MultipartFormData, Rkyv, StreamingText, TextStream};
@@ -10,13 +11,18 @@ use log::{debug, info, warn};
 use reqwest::Client;
 use std::cell::RefCell;
 #[cfg(feature = "server")]
+use std::collections::HashSet;
+#[cfg(feature = "server")]
 use std::path::Path;
 use std::rc::Rc;
 #[cfg(feature = "server")]
-use vowlr_database::prelude::{GraphDisplayDataSolutionSerializer, QueryResults};
-#[cfg(feature = "server")]
 use vowlr_database::store::VOWLRStore;
-use vowlr_util::datatypes::DataType;
+use vowlr_util::{
+    datatypes::DataType,
+    flags::{ElementKind, GraphFlags},
+    limits::{GraphRequest, QueryOutcome},
+    options::{ElementFilter, SerializerOptions},
+};
 use web_sys::{FileList, FormData};
 
 #[cfg(feature = "ssr")]
@@ -226,24 +232,52 @@ pub async fn handle_sparql(
     Ok((dtype, total))
 }
 
+/// Draws the graph of `query` with the elements drawn by `filter`,
+/// with its flags and whether it was truncated.
+///
+/// A newer `request` of the same client cancels this one, see `VOWLRStore::for_request`.
+///
+/// See `VOWLRStore::filtered_graph`.
 #[server (input = Rkyv, output = Rkyv)]
-pub async fn handle_internal_sparql(query: String) -> Result<GraphDisplayData, ServerFnError> {
+pub async fn handle_internal_sparql(
+    query: String,
+    options: SerializerOptions,
+    filter: ElementFilter,
+    request: Option<GraphRequest>,
+) -> Result<(GraphDisplayData, GraphFlags, QueryOutcome), ServerFnError> {
     let vowlr = VOWLRStore::default();
+    Ok(vowlr
+        .for_request(
+            request,
+            vowlr.filtered_graph(&query, options, &filter, &HashSet::new(), &HashSet::new()),
+        )
+        .await?)
+}
 
-    let mut data_buffer = GraphDisplayData::new();
-    let mut solution_serializer = GraphDisplayDataSolutionSerializer::new();
-    let query_stream = vowlr.session.query(query.as_str()).await.unwrap();
-    if let QueryResults::Solutions(solutions) = query_stream {
-        solution_serializer
-            .serialize_nodes_stream(&mut data_buffer, solutions)
-            .await
-            .unwrap();
-    } else {
-        return Err(ServerFnError::ServerError(
-            "Query stream is not a solutions stream".to_string(),
-        ));
-    }
-    Ok(data_buffer)
+/// Draws the graph of `query` with the elements drawn by `filter`,
+/// without the elements whose type is `hidden` and those whose kind is `hidden_kinds`.
+///
+/// A newer `request` of the same client cancels this one, see `VOWLRStore::for_request`.
+///
+/// See `VOWLRStore::filtered_graph`.
+#[server (input = Rkyv, output = Rkyv)]
+pub async fn handle_filtered_graph(
+    query: String,
+    options: SerializerOptions,
+    filter: ElementFilter,
+    hidden: Vec<ElementType>,
+    hidden_kinds: Vec<ElementKind>,
+    request: Option<GraphRequest>,
+) -> Result<(GraphDisplayData, GraphFlags, QueryOutcome), ServerFnError> {
+    let vowlr = VOWLRStore::default();
+    let hidden: HashSet<_> = hidden.into_iter().collect();
+    let hidden_kinds: HashSet<_> = hidden_kinds.into_iter().collect();
+    Ok(vowlr
+        .for_request(
+            request,
+            vowlr.filtered_graph(&query, options, &filter, &hidden, &hidden_kinds),
+        )
+        .await?)
 }
 
 pub struct UploadProgress {
@@ -391,22 +425,28 @@ pub struct FileUpload {
 impl FileUpload {
     pub fn new() -> Self {
         let mode = RwSignal::new("local".to_string());
+        // Uploads change the loaded documents, after which the graph is reloaded.
+        let graph_context = expect_context::<GraphDataContext>();
 
         let local_action =
-            Action::<FormData, Result<(DataType, usize), ServerFnError>>::new_local(|data| {
-                handle_local(data.clone().into())
+            Action::<FormData, Result<(DataType, usize), ServerFnError>>::new_local(move |data| {
+                graph_context.write(handle_local(data.clone().into()))
             });
 
         let remote_action =
-            Action::<String, Result<(DataType, usize), ServerFnError>>::new(|url| {
-                handle_remote(url.clone())
+            Action::<String, Result<(DataType, usize), ServerFnError>>::new(move |url| {
+                graph_context.write(handle_remote(url.clone()))
             });
 
         let sparql_action = Action::<
             (String, String, Option<String>),
             Result<(DataType, usize), ServerFnError>,
-        >::new(|(endpoint, query, format)| {
-            handle_sparql(endpoint.clone(), query.clone(), format.clone())
+        >::new(move |(endpoint, query, format)| {
+            graph_context.write(handle_sparql(
+                endpoint.clone(),
+                query.clone(),
+                format.clone(),
+            ))
         });
 
         let tracker = Rc::new(UploadProgress::new());
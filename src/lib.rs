@@ -18,6 +18,8 @@ pub mod blocks;
 pub mod components;
 pub mod hydration_scripts;
 pub mod pages;
+#[cfg(all(feature = "ssr", feature = "server"))]
+pub mod sparql_endpoint;
 
 // Hydration code for the frontend.
 #[cfg(feature = "hydrate")]
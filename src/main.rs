@@ -9,6 +9,7 @@ use leptos_meta::MetaTags;
 use log::info;
 use vowlr::app::App;
 use vowlr::hydration_scripts::HydrationScripts as Hydro;
+use vowlr::sparql_endpoint::{self, EndpointOptions};
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
@@ -21,6 +22,10 @@ async fn main() -> std::io::Result<()> {
 
     let conf = get_configuration(None).unwrap();
     let addr = conf.leptos_options.site_addr;
+    let endpoint_options = EndpointOptions::from_env();
+    if endpoint_options.updates {
+        info!("SPARQL updates and Graph Store writes are enabled");
+    }
 
     HttpServer::new(move || {
         // Generate the list of routes in your Leptos App
@@ -62,6 +67,7 @@ async fn main() -> std::io::Result<()> {
                     }
                 }
             })
+            .configure(sparql_endpoint::configure(endpoint_options))
             .service(Files::new("/", site_root.as_ref()))
             //.wrap(middleware::Compress::default())
             .wrap(
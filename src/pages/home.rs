@@ -1,15 +1,80 @@
-use crate::blocks::{right_side_bar::RightSidebar, workbench::NewWorkbench};
+use crate::blocks::{
+    right_side_bar::RightSidebar,
+    workbench::{GraphDataContext, NewWorkbench, first_graph_request},
+};
+use grapher::prelude::GraphDisplayData;
 use leptos::prelude::*;
 use leptos_meta::*;
+use vowlr_util::{
+    flags::GraphFlags,
+    limits::QueryOutcome,
+    options::{ElementFilter, SerializerOptions},
+};
+
+/// Warns that only part of the graph is drawn, as its query had too many solutions
+/// or the ontology has more individuals than are drawn.
+#[component]
+fn TruncatedNotice() -> impl IntoView {
+    let GraphDataContext { query_outcome, .. } = expect_context::<GraphDataContext>();
+
+    view! {
+        <div class="flex fixed bottom-4 left-1/2 flex-col gap-2 items-center -translate-x-1/2 z-[3]">
+            <Show when=move || query_outcome.with(|outcome| outcome.truncated)>
+                <p class="py-2 px-4 text-sm bg-yellow-100 rounded border border-yellow-400">
+                    {move || {
+                        format!(
+                            "The graph is truncated to the first {} query solutions. Explore or filter the ontology to draw the rest.",
+                            query_outcome.with(|outcome| outcome.solutions),
+                        )
+                    }}
+                </p>
+            </Show>
+            <Show when=move || query_outcome.with(|outcome| outcome.hidden_individuals > 0)>
+                <p class="py-2 px-4 text-sm bg-yellow-100 rounded border border-yellow-400">
+                    {move || {
+                        format!(
+                            "The {} individuals of the ontology are hidden, as they exceed the limit. Raise it in the options to draw them.",
+                            query_outcome.with(|outcome| outcome.hidden_individuals),
+                        )
+                    }}
+                </p>
+            </Show>
+        </div>
+    }
+}
 
 #[component]
 pub fn Home() -> impl IntoView {
+    // Shared by the workbench and the sidebar.
+    let graph_context = GraphDataContext {
+        graph_data: RwSignal::new(GraphDisplayData::new()),
+        total_graph_data: RwSignal::new(GraphDisplayData::new()),
+        graph_flags: RwSignal::new(GraphFlags::default()),
+        total_graph_flags: RwSignal::new(GraphFlags::default()),
+        label_languages: RwSignal::new(Vec::new()),
+        hidden_elements: RwSignal::new(Vec::new()),
+        hidden_kinds: RwSignal::new(Vec::new()),
+        serializer_options: RwSignal::new(SerializerOptions::default()),
+        element_filter: RwSignal::new(ElementFilter::default()),
+        focus: RwSignal::new(Vec::new()),
+        hierarchy: RwSignal::new(None),
+        query_outcome: RwSignal::new(QueryOutcome::default()),
+        pending_request: StoredValue::new(None),
+        latest_request: StoredValue::new(first_graph_request()),
+        pending_writes: StoredValue::new(0),
+        written_generation: StoredValue::new(0),
+    };
+    provide_context(graph_context);
+    // Effects only run in the browser, which is where the graph is drawn.
+    Effect::new(move || graph_context.follow_store_changes());
+
     view! {
         <Title text="VOWL-R" />
         <main class="-z-99">
             <canvas class="fixed -z-98 size-full" id="canvas" />
             <NewWorkbench />
             <RightSidebar />
+            <TruncatedNotice />
         </main>
     }
 }
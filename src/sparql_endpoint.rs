@@ -0,0 +1,405 @@
+//! Serves the loaded documents through the SPARQL 1.1 Protocol, so other tools can query them.
+//!
+//! Queries are accepted at `/sparql` using GET, form-encoded POST and direct POST,
+//! with the results format negotiated through the `Accept` header.
+//!
+//! The loaded documents are also served as the default graph of the SPARQL 1.1
+//! Graph Store HTTP Protocol at `/rdf-graphs?default`, so they can be edited as a whole.
+
+use actix_web::{
+    HttpRequest, HttpResponse,
+    http::header::{Accept, CONTENT_TYPE, Header, HeaderName, Quality},
+    web,
+};
+use futures::StreamExt;
+use log::{info, warn};
+use std::collections::HashMap;
+use vowlr_database::{results::format_of_media_type, store::VOWLRStore};
+use vowlr_parser::errors::{WebVowlStoreError, WebVowlStoreErrorKind};
+use vowlr_util::datatypes::DataType;
+
+/// The path of the endpoint.
+pub const SPARQL_PATH: &str = "/sparql";
+/// The path of the Graph Store Protocol endpoint.
+pub const GRAPH_STORE_PATH: &str = "/rdf-graphs";
+
+/// The types of documents read and written through the Graph Store Protocol,
+/// most preferred first.
+const GRAPH_DOCUMENTS: [DataType; 8] = [
+    DataType::TTL,
+    DataType::NTriples,
+    DataType::RDF,
+    DataType::JsonLd,
+    DataType::NQuads,
+    DataType::TriG,
+    DataType::OWX,
+    DataType::OFN,
+];
+
+/// Options of the SPARQL endpoint.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EndpointOptions {
+    /// Whether SPARQL Update requests and Graph Store Protocol writes are accepted.
+    ///
+    /// Disabled by default, so other tools can't modify the loaded documents.
+    pub updates: bool,
+    /// The maximum number of solutions or triples a query may have.
+    ///
+    /// Queries with more fail rather than return part of their results,
+    /// as the results formats have no way of saying that results are missing.
+    /// Unlimited by default, so queries are only bounded by the timeout.
+    pub max_solutions: Option<usize>,
+}
+
+impl EndpointOptions {
+    /// The environment variable enabling updates and writes when set to `true`.
+    pub const UPDATES_VARIABLE: &str = "VOWLR_SPARQL_UPDATES";
+    /// The environment variable setting [`EndpointOptions::max_solutions`].
+    pub const MAX_SOLUTIONS_VARIABLE: &str = "VOWLR_SPARQL_MAX_SOLUTIONS";
+
+    /// Reads the options from the environment.
+    pub fn from_env() -> Self {
+        Self {
+            updates: std::env::var(Self::UPDATES_VARIABLE).is_ok_and(|value| value == "true"),
+            max_solutions: std::env::var(Self::MAX_SOLUTIONS_VARIABLE)
+                .ok()
+                .and_then(|value| value.parse().ok()),
+        }
+    }
+}
+
+/// Registers the endpoints, configured by `options`.
+pub fn configure(options: EndpointOptions) -> impl FnOnce(&mut web::ServiceConfig) {
+    move |config| {
+        config
+            .app_data(web::Data::new(options))
+            .service(
+                web::resource(SPARQL_PATH)
+                    .route(web::get().to(get))
+                    .route(web::post().to(post)),
+            )
+            .service(
+                web::resource(GRAPH_STORE_PATH)
+                    .route(web::get().to(get_graph))
+                    .route(web::head().to(get_graph))
+                    .route(web::put().to(put_graph))
+                    .route(web::post().to(post_graph))
+                    .route(web::delete().to(delete_graph)),
+            );
+    }
+}
+
+/// Returns the value of the header `name`, or an empty string without one.
+fn header<'a>(request: &'a HttpRequest, name: &HeaderName) -> &'a str {
+    request
+        .headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+}
+
+/// Returns the media type of the request body, without parameters such as the charset.
+fn content_type(request: &HttpRequest) -> String {
+    header(request, &CONTENT_TYPE)
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// Picks the one of `formats` the client prefers by the `Accept` header of `request`.
+///
+/// A format is acceptable if `accepts` the media type of a range, or if the range is a
+/// wildcard like `text/*` covering its `mime_type`.
+/// Without any preference, the first of `formats` is picked.
+/// Returns `None` if none of `formats` is acceptable.
+fn negotiate<T: Clone>(
+    request: &HttpRequest,
+    formats: &[T],
+    mime_type: impl Fn(&T) -> &'static str,
+    accepts: impl Fn(&str, &T) -> bool,
+) -> Option<T> {
+    let accept = Accept::parse(request).unwrap_or_else(|_| Accept(Vec::new()));
+    if accept.is_empty() {
+        return formats.first().cloned();
+    }
+    let mut ranges = accept
+        .iter()
+        .filter(|range| range.quality != Quality::ZERO)
+        .collect::<Vec<_>>();
+    // A stable sort, so ranges of equal quality keep the order of the client.
+    ranges.sort_by(|a, b| b.quality.cmp(&a.quality));
+    ranges.into_iter().find_map(|range| {
+        let range = range.item.essence_str();
+        formats
+            .iter()
+            .find(|format| {
+                range == "*/*"
+                    || accepts(range, format)
+                    || range
+                        .strip_suffix("/*")
+                        .is_some_and(|kind| mime_type(format).split('/').next() == Some(kind))
+            })
+            .cloned()
+    })
+}
+
+/// Returns the response to the store failing with `error`.
+///
+/// Timeouts are the server giving up on a valid request, so they aren't reported as bad requests.
+fn store_error(error: WebVowlStoreError) -> HttpResponse {
+    let body = error.to_string();
+    match error.kind() {
+        WebVowlStoreErrorKind::Timeout(_) => HttpResponse::ServiceUnavailable().body(body),
+        WebVowlStoreErrorKind::InvalidInput(_)
+        | WebVowlStoreErrorKind::IriParseError(_)
+        | WebVowlStoreErrorKind::QueryEvaluationError(_) => HttpResponse::BadRequest().body(body),
+        _ => HttpResponse::InternalServerError().body(body),
+    }
+}
+
+/// Handles a query passed in the query string.
+async fn get(
+    request: HttpRequest,
+    params: web::Query<HashMap<String, String>>,
+    options: web::Data<EndpointOptions>,
+) -> HttpResponse {
+    match params.get("query") {
+        Some(query) => run_query(&request, query, &options).await,
+        None if params.contains_key("update") => {
+            HttpResponse::MethodNotAllowed().body("Updates must be sent using POST")
+        }
+        None => HttpResponse::BadRequest().body("Missing the query parameter"),
+    }
+}
+
+/// Handles a query or update passed in the body, either form-encoded or directly.
+async fn post(
+    request: HttpRequest,
+    body: web::Bytes,
+    options: web::Data<EndpointOptions>,
+) -> HttpResponse {
+    let Ok(body) = String::from_utf8(body.to_vec()) else {
+        return HttpResponse::BadRequest().body("The request body is not valid UTF-8");
+    };
+    match content_type(&request).as_str() {
+        "application/sparql-query" => run_query(&request, &body, &options).await,
+        "application/sparql-update" => run_update(&body, &options).await,
+        "application/x-www-form-urlencoded" => {
+            let Ok(params) = web::Query::<HashMap<String, String>>::from_query(&body) else {
+                return HttpResponse::BadRequest().body("The request body is not a valid form");
+            };
+            match (params.get("query"), params.get("update")) {
+                (Some(query), None) => run_query(&request, query, &options).await,
+                (None, Some(update)) => run_update(update, &options).await,
+                _ => HttpResponse::BadRequest()
+                    .body("Exactly one of the query and update parameters is required"),
+            }
+        }
+        _ => HttpResponse::UnsupportedMediaType().body(
+            "Use application/sparql-query, application/sparql-update \
+             or application/x-www-form-urlencoded",
+        ),
+    }
+}
+
+/// Runs `query` and writes its results in the format negotiated with the client.
+///
+/// Queries with more solutions than allowed by `options` fail.
+async fn run_query(request: &HttpRequest, query: &str, options: &EndpointOptions) -> HttpResponse {
+    let store = VOWLRStore::default();
+    let max_solutions = options.max_solutions.unwrap_or(usize::MAX);
+    let results = match store.query_results(query, max_solutions).await {
+        Ok(results) => results,
+        Err(e) => {
+            warn!("SPARQL endpoint query failed: {e}");
+            return store_error(e);
+        }
+    };
+    if results.truncated() {
+        warn!("SPARQL endpoint query has more than {max_solutions} results");
+        return HttpResponse::InternalServerError().body(format!(
+            "The query has more than {max_solutions} results, the maximum set by {}",
+            EndpointOptions::MAX_SOLUTIONS_VARIABLE
+        ));
+    }
+
+    let formats = results.formats();
+    let Some(format) = negotiate(
+        request,
+        formats,
+        |format| format.mime_type(),
+        |media_type, format| format_of_media_type(media_type, formats) == Some(*format),
+    ) else {
+        let supported = formats
+            .iter()
+            .map(|format| format.mime_type())
+            .collect::<Vec<_>>();
+        return HttpResponse::NotAcceptable().body(format!(
+            "The results can be written as {}",
+            supported.join(", ")
+        ));
+    };
+    info!("Answering SPARQL endpoint query as {}", format.name());
+
+    let body = match store.write_results(&results, format) {
+        Ok(body) => body,
+        Err(e) => {
+            warn!("SPARQL endpoint query failed: {e}");
+            return HttpResponse::InternalServerError().body(e.to_string());
+        }
+    };
+    HttpResponse::Ok()
+        .content_type(format!("{}; charset=utf-8", format.mime_type()))
+        .body(body)
+}
+
+/// Runs `update`, if updates are enabled by `options`.
+async fn run_update(update: &str, options: &EndpointOptions) -> HttpResponse {
+    if let Err(response) = writable(options) {
+        return response;
+    }
+    match VOWLRStore::default().update(update).await {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(e) => {
+            warn!("SPARQL endpoint update failed: {e}");
+            store_error(e)
+        }
+    }
+}
+
+/// Checks that a Graph Store Protocol request targets the default graph.
+///
+/// All documents are loaded into the default graph, so there are no named graphs to serve.
+fn default_graph(params: &HashMap<String, String>) -> Result<(), HttpResponse> {
+    match (params.contains_key("default"), params.get("graph")) {
+        (true, None) => Ok(()),
+        (false, Some(graph)) => Err(HttpResponse::NotFound().body(format!(
+            "The graph <{graph}> doesn't exist. All documents are loaded into the default graph"
+        ))),
+        _ => Err(HttpResponse::BadRequest()
+            .body("Exactly one of the default and graph parameters is required")),
+    }
+}
+
+/// Checks that writes are enabled by `options`.
+fn writable(options: &EndpointOptions) -> Result<(), HttpResponse> {
+    if options.updates {
+        Ok(())
+    } else {
+        Err(HttpResponse::Forbidden().body(format!(
+            "Writes are disabled. Set {}=true to enable them",
+            EndpointOptions::UPDATES_VARIABLE
+        )))
+    }
+}
+
+/// Writes the loaded documents in the type negotiated with the client.
+async fn get_graph(
+    request: HttpRequest,
+    params: web::Query<HashMap<String, String>>,
+) -> HttpResponse {
+    if let Err(response) = default_graph(&params) {
+        return response;
+    }
+    let Some(format) = negotiate(
+        &request,
+        &GRAPH_DOCUMENTS,
+        |format| format.mime_type(),
+        |media_type, format| format.mime_type() == media_type,
+    ) else {
+        let supported = GRAPH_DOCUMENTS
+            .iter()
+            .map(DataType::mime_type)
+            .collect::<Vec<_>>();
+        return HttpResponse::NotAcceptable().body(format!(
+            "The graph can be written as {}",
+            supported.join(", ")
+        ));
+    };
+    info!("Answering Graph Store request as {}", format.mime_type());
+
+    let content_type = format.mime_type();
+    match VOWLRStore::default().serialize_stream(format).await {
+        Ok(stream) => HttpResponse::Ok()
+            .content_type(content_type)
+            .streaming(stream.map(|chunk| {
+                chunk
+                    .map(web::Bytes::from)
+                    .map_err(|e| std::io::Error::other(e.to_string()))
+            })),
+        Err(e) => {
+            warn!("Graph Store request failed: {e}");
+            HttpResponse::InternalServerError().body(e.to_string())
+        }
+    }
+}
+
+/// Loads the document in the request body, replacing the loaded documents if `replace`.
+async fn insert_graph(
+    request: HttpRequest,
+    params: web::Query<HashMap<String, String>>,
+    body: web::Bytes,
+    options: web::Data<EndpointOptions>,
+    replace: bool,
+) -> HttpResponse {
+    if let Err(response) = default_graph(&params).and_then(|()| writable(&options)) {
+        return response;
+    }
+    let content_type = content_type(&request);
+    let Some(format) = GRAPH_DOCUMENTS
+        .into_iter()
+        .find(|format| format.mime_type() == content_type)
+    else {
+        return HttpResponse::UnsupportedMediaType()
+            .body(format!("Documents of type {content_type} can't be loaded"));
+    };
+    match VOWLRStore::default()
+        .insert_document(&body, format, replace)
+        .await
+    {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(e) => {
+            warn!("Graph Store request failed: {e}");
+            store_error(e)
+        }
+    }
+}
+
+/// Replaces the loaded documents with the document in the request body.
+async fn put_graph(
+    request: HttpRequest,
+    params: web::Query<HashMap<String, String>>,
+    body: web::Bytes,
+    options: web::Data<EndpointOptions>,
+) -> HttpResponse {
+    insert_graph(request, params, body, options, true).await
+}
+
+/// Loads the document in the request body alongside the loaded documents.
+async fn post_graph(
+    request: HttpRequest,
+    params: web::Query<HashMap<String, String>>,
+    body: web::Bytes,
+    options: web::Data<EndpointOptions>,
+) -> HttpResponse {
+    insert_graph(request, params, body, options, false).await
+}
+
+/// Removes all loaded documents.
+async fn delete_graph(
+    params: web::Query<HashMap<String, String>>,
+    options: web::Data<EndpointOptions>,
+) -> HttpResponse {
+    if let Err(response) = default_graph(&params).and_then(|()| writable(&options)) {
+        return response;
+    }
+    match VOWLRStore::default().clear().await {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(e) => {
+            warn!("Graph Store request failed: {e}");
+            HttpResponse::InternalServerError().body(e.to_string())
+        }
+    }
+}